@@ -0,0 +1,101 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Conversions to and from the APEX (Additive System of Photographic Exposure) units used by
+//! some Exif tags, such as `Exif.Photo.ApertureValue` and `Exif.Photo.ShutterSpeedValue`.
+//!
+//! In APEX units, a correct exposure satisfies `Av + Tv = Bv + Sv = Ev`, where `Av` is the
+//! aperture value, `Tv` the time value, `Bv` the brightness value, and `Sv` the speed value.
+//!
+//! # Examples
+//! ```
+//! use rexiv2::apex;
+//! assert_eq!(apex::f_number_to_av(4.0), 4.0);
+//! assert_eq!(apex::exposure_time_to_tv(1.0 / 8.0), 3.0);
+//! // f/4 at 1/8s is an exposure value of 7.
+//! assert_eq!(apex::ev(4.0, 1.0 / 8.0), 7.0);
+//! ```
+
+/// The ISO speed that corresponds to a speed value of zero.
+const SV_ZERO_ISO: f64 = 3.125;
+
+/// Convert an f-number to an aperture value (`Av`).
+pub fn f_number_to_av(f_number: f64) -> f64 {
+    2.0 * f_number.log2()
+}
+
+/// Convert an aperture value (`Av`) to an f-number.
+///
+/// # Examples
+/// ```
+/// assert_eq!(rexiv2::apex::av_to_f_number(5.0), 2f64.powf(2.5));
+/// ```
+pub fn av_to_f_number(av: f64) -> f64 {
+    2f64.powf(av / 2.0)
+}
+
+/// Convert an exposure time in seconds to a time value (`Tv`).
+pub fn exposure_time_to_tv(seconds: f64) -> f64 {
+    -seconds.log2()
+}
+
+/// Convert a time value (`Tv`) to an exposure time in seconds.
+///
+/// # Examples
+/// ```
+/// assert_eq!(rexiv2::apex::tv_to_exposure_time(10.0), 1.0 / 1024.0);
+/// ```
+pub fn tv_to_exposure_time(tv: f64) -> f64 {
+    2f64.powf(-tv)
+}
+
+/// Convert an ISO speed to a speed value (`Sv`).
+///
+/// # Examples
+/// ```
+/// assert_eq!(rexiv2::apex::iso_to_sv(100.0), 5.0);
+/// ```
+pub fn iso_to_sv(iso: f64) -> f64 {
+    (iso / SV_ZERO_ISO).log2()
+}
+
+/// Convert a speed value (`Sv`) to an ISO speed.
+pub fn sv_to_iso(sv: f64) -> f64 {
+    SV_ZERO_ISO * 2f64.powf(sv)
+}
+
+/// Calculate the exposure value (`Ev`) of the given f-number and exposure time in seconds.
+///
+/// This describes the camera settings alone; see [`ev_at_iso_100`] to account for the ISO
+/// speed too.
+pub fn ev(f_number: f64, seconds: f64) -> f64 {
+    f_number_to_av(f_number) + exposure_time_to_tv(seconds)
+}
+
+/// Calculate the exposure value of the given settings, normalized to ISO 100 (often called
+/// `EV100`), which measures how bright the scene was.
+///
+/// # Examples
+/// ```
+/// // f/4 at 1/8s and ISO 200 is the same scene brightness as f/4 at 1/4s and ISO 100.
+/// assert_eq!(rexiv2::apex::ev_at_iso_100(4.0, 1.0 / 8.0, 200.0), 6.0);
+/// assert_eq!(rexiv2::apex::ev_at_iso_100(4.0, 1.0 / 4.0, 100.0), 6.0);
+/// ```
+pub fn ev_at_iso_100(f_number: f64, seconds: f64, iso: f64) -> f64 {
+    ev(f_number, seconds) - (iso / 100.0).log2()
+}
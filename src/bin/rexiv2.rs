@@ -0,0 +1,214 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! A small command-line tool for inspecting and editing metadata with rexiv2.
+
+use rexiv2::{Metadata, ReportOptions, Rexiv2Error};
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage:
+  rexiv2 show [--labels] [--raw] FILE...   List every tag and its value
+  rexiv2 get [--raw] FILE TAG...           Print the values of the given tags
+  rexiv2 set FILE TAG=VALUE...             Set tags and save the file
+  rexiv2 diff FILE FILE                    List the tags that differ between two files
+  rexiv2 strip [--exif] [--iptc] [--xmp] FILE...
+                                           Remove metadata (all of it by default)
+  rexiv2 redact FILE...                    Remove GPS location data
+  rexiv2 xmp FILE                          Print the metadata as an XMP document";
+
+fn main() -> ExitCode {
+    // File names are kept as they are, but everything else must be text.
+    let args: Vec<OsString> = std::env::args_os().skip(1).collect();
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => match command.to_str() {
+            Some(command) => (command, args),
+            None => return not_text(command),
+        },
+        None => return usage(),
+    };
+
+    let (flags, operands): (Vec<&OsString>, Vec<&OsString>) = args
+        .iter()
+        .partition(|arg| arg.to_string_lossy().starts_with("--"));
+    let known_flags: &[&str] = match command {
+        "show" => &["--labels", "--raw"],
+        "get" => &["--raw"],
+        "strip" => &["--exif", "--iptc", "--xmp"],
+        _ => &[],
+    };
+    if let Some(unknown) = flags
+        .iter()
+        .find(|flag| !known_flags.iter().any(|known| flag.as_os_str() == *known))
+    {
+        eprintln!(
+            "rexiv2: unknown option {} for {command}",
+            unknown.to_string_lossy()
+        );
+        return usage();
+    }
+    let flag = |name: &str| flags.iter().any(|flag| flag.as_os_str() == name);
+
+    if let Err(err) = rexiv2::initialize() {
+        eprintln!("rexiv2: {err}");
+        return ExitCode::FAILURE;
+    }
+    let result = match (command, operands.as_slice()) {
+        ("show", files) if !files.is_empty() => show(
+            files,
+            ReportOptions::new()
+                .labels(flag("--labels"))
+                .interpreted(!flag("--raw")),
+        ),
+        ("get", [file, tags @ ..]) if !tags.is_empty() => match strings(tags) {
+            Ok(tags) => get(file, &tags, flag("--raw")),
+            Err(arg) => return not_text(arg),
+        },
+        ("set", [file, edits @ ..]) if !edits.is_empty() => match strings(edits) {
+            Ok(edits) => set(file, &edits),
+            Err(arg) => return not_text(arg),
+        },
+        ("diff", [old, new]) => diff(old, new),
+        ("strip", files) if !files.is_empty() => {
+            let all = !flag("--exif") && !flag("--iptc") && !flag("--xmp");
+            strip(
+                files,
+                all || flag("--exif"),
+                all || flag("--iptc"),
+                all || flag("--xmp"),
+            )
+        }
+        ("redact", files) if !files.is_empty() => redact(files),
+        ("xmp", [file]) => Metadata::new_from_path(file)
+            .and_then(|meta| meta.to_xmp_document(true))
+            .map(|xmp| print!("{xmp}")),
+        _ => return usage(),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("rexiv2: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
+
+/// Report an argument that should have been text, such as a command or tag name, but wasn't
+/// valid UTF-8.
+fn not_text(arg: &OsStr) -> ExitCode {
+    eprintln!("rexiv2: {} is not valid UTF-8", arg.to_string_lossy());
+    usage()
+}
+
+/// Convert arguments that must be text, or return the first one that isn't.
+fn strings<'a>(args: &[&'a OsString]) -> Result<Vec<&'a str>, &'a OsStr> {
+    args.iter()
+        .map(|arg| arg.to_str().ok_or(arg.as_os_str()))
+        .collect()
+}
+
+fn show(files: &[&OsString], options: ReportOptions) -> Result<(), Rexiv2Error> {
+    for (i, file) in files.iter().enumerate() {
+        let meta = Metadata::new_from_path(file)?;
+        if files.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("==== {} ====", Path::new(file).display());
+        }
+        print!("{}", meta.to_report(options)?);
+    }
+    Ok(())
+}
+
+/// Print the interpreted value of each tag, or with `raw` set, each of its raw values on a line
+/// of its own.
+fn get(file: &OsStr, tags: &[&str], raw: bool) -> Result<(), Rexiv2Error> {
+    let meta = Metadata::new_from_path(file)?;
+    for tag in tags {
+        if raw {
+            for value in meta.get_tag_multiple_strings(*tag)? {
+                println!("{value}");
+            }
+        } else {
+            println!("{}", meta.get_tag_interpreted_string(*tag)?);
+        }
+    }
+    Ok(())
+}
+
+fn set(file: &OsStr, edits: &[&str]) -> Result<(), Rexiv2Error> {
+    let meta = Metadata::new_from_path(file)?;
+    for edit in edits {
+        let (tag, value) = edit.split_once('=').ok_or_else(|| {
+            Rexiv2Error::InvalidValue(format!("Expected TAG=VALUE, found {edit:?}"))
+        })?;
+        meta.set_tag_string(tag, value)?;
+    }
+    meta.save_to_file_atomic(file)
+}
+
+/// Print the interpreted values of tags that are only in the old file (`-`), only in the new
+/// file (`+`), or in both with different values (both).
+fn diff(old: &OsStr, new: &OsStr) -> Result<(), Rexiv2Error> {
+    let old = Metadata::new_from_path(old)?;
+    let new = Metadata::new_from_path(new)?;
+    for difference in old.diff(&new) {
+        if let Some(old) = difference.old {
+            println!("- {}: {old}", difference.tag);
+        }
+        if let Some(new) = difference.new {
+            println!("+ {}: {new}", difference.tag);
+        }
+    }
+    Ok(())
+}
+
+fn strip(files: &[&OsString], exif: bool, iptc: bool, xmp: bool) -> Result<(), Rexiv2Error> {
+    for file in files {
+        let meta = Metadata::new_from_path(file)?;
+        if exif {
+            meta.clear_exif();
+        }
+        if iptc {
+            meta.clear_iptc();
+        }
+        if xmp {
+            meta.clear_xmp();
+        }
+        meta.save_to_file_atomic(file)?;
+    }
+    Ok(())
+}
+
+fn redact(files: &[&OsString]) -> Result<(), Rexiv2Error> {
+    for file in files {
+        let meta = Metadata::new_from_path(file)?;
+        meta.delete_gps_info();
+        meta.save_to_file_atomic(file)?;
+    }
+    Ok(())
+}
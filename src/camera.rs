@@ -0,0 +1,61 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+/// The camera a photograph was taken with, from `Exif.Image.Make` and `Exif.Image.Model`, or their
+/// XMP mirrors `Xmp.tiff.Make` and `Xmp.tiff.Model`.
+///
+/// # Examples
+/// ```
+/// let camera = rexiv2::Camera {
+///     make: Some("Canon".to_string()),
+///     model: Some("Canon EOS R5".to_string()),
+/// };
+/// assert_eq!(camera.name(), Some("Canon EOS R5".to_string()));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Camera {
+    /// The manufacturer of the camera.
+    pub make: Option<String>,
+    /// The model name or number of the camera.
+    pub model: Option<String>,
+}
+
+impl Camera {
+    /// A name for the camera suitable for display: the model, prefixed with the manufacturer's
+    /// name unless the model already includes it (as with e.g. `Canon EOS R5`). Returns `None` if
+    /// the model isn't known.
+    ///
+    /// # Examples
+    /// ```
+    /// let camera = rexiv2::Camera {
+    ///     make: Some("NIKON CORPORATION".to_string()),
+    ///     model: Some("Z 6".to_string()),
+    /// };
+    /// assert_eq!(camera.name(), Some("NIKON CORPORATION Z 6".to_string()));
+    /// ```
+    pub fn name(&self) -> Option<String> {
+        match (&self.make, &self.model) {
+            (Some(make), Some(model))
+                if !model.to_lowercase().starts_with(&make.to_lowercase()) =>
+            {
+                Some(format!("{make} {model}"))
+            }
+            (_, model) => model.clone(),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{easy_access, trim_text, Metadata};
+
+/// The camera settings a photograph was taken with, as usually shown alongside it in a gallery.
+///
+/// Fields are `None` when the metadata doesn't record them. Descriptive settings, such as the
+/// metering mode, are human-readable values like those returned by
+/// `Metadata::get_tag_interpreted_string`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CaptureSettings {
+    /// The exposure time, in seconds.
+    pub exposure_time: Option<num_rational::Ratio<i32>>,
+    /// The f-number of the lens.
+    pub f_number: Option<f64>,
+    /// The ISO sensitivity of the camera.
+    pub iso: Option<u32>,
+    /// The focal length of the lens, in millimetres.
+    pub focal_length: Option<f64>,
+    /// Whether and how the flash fired.
+    pub flash: Option<String>,
+    /// The metering mode, e.g. `"Multi-segment"`.
+    pub metering_mode: Option<String>,
+    /// The program used to set the exposure, e.g. `"Aperture priority"`.
+    pub exposure_program: Option<String>,
+    /// The name of the lens.
+    pub lens: Option<String>,
+}
+
+/// Gather the capture settings recorded in the given metadata.
+pub(crate) fn gather(meta: &Metadata) -> CaptureSettings {
+    // Listing the tags once saves probing for each of the many fallbacks individually.
+    let present = meta.get_exif_tags().unwrap_or_default();
+    let interpreted = |keys: &[&str]| {
+        keys.iter()
+            .filter(|key| present.iter().any(|tag| tag == *key))
+            .find_map(|key| meta.get_tag_interpreted_string(*key).ok())
+            .and_then(|value| trim_text(&value))
+    };
+
+    CaptureSettings {
+        exposure_time: meta.get_exposure_time(),
+        f_number: meta.get_fnumber(),
+        iso: meta.get_iso_sensitivity().map(|iso| iso.value),
+        focal_length: meta.get_focal_length(),
+        flash: interpreted(&easy_access::FLASH),
+        metering_mode: interpreted(&easy_access::METERING_MODE),
+        exposure_program: interpreted(&easy_access::EXPOSURE_PROGRAM),
+        lens: interpreted(&easy_access::LENS_NAME),
+    }
+}
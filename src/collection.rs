@@ -0,0 +1,76 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::iptc_xmp::{clear_struct_array, struct_array_len};
+use super::{ensure_xmp_namespace, tags, Metadata, Result};
+
+/// The namespace of the Metadata Working Group collections schema.
+pub(crate) const MWG_COLL_NAMESPACE: &str =
+    "http://www.metadataworkinggroup.com/schemas/collections/";
+
+/// A collection (such as an album) that an image belongs to, as described by the Metadata Working
+/// Group collections schema in `Xmp.mwg-coll.Collections`.
+///
+/// Only the fields that are set are written to the collection's entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Collection {
+    /// The name of the collection (`CollectionName`).
+    pub name: Option<String>,
+    /// A URI uniquely identifying the collection (`CollectionURI`).
+    pub uri: Option<String>,
+}
+
+impl Collection {
+    /// Read every collection stored in `Xmp.mwg-coll.Collections`.
+    pub(crate) fn read_all(meta: &Metadata) -> Vec<Collection> {
+        let array_tag = tags::xmp::MWG_COLL_COLLECTIONS;
+        (1..=struct_array_len(meta, array_tag))
+            .map(|index| {
+                let get = |field| {
+                    let tag = format!("{array_tag}[{index}]/mwg-coll:{field}");
+                    meta.get_tag_string(&tag).ok()
+                };
+                Collection {
+                    name: get("CollectionName"),
+                    uri: get("CollectionURI"),
+                }
+            })
+            .filter(|collection| collection != &Collection::default())
+            .collect()
+    }
+
+    /// Replace the contents of `Xmp.mwg-coll.Collections` with the given collections.
+    pub(crate) fn write_all(meta: &Metadata, collections: &[Collection]) -> Result<()> {
+        let array_tag = tags::xmp::MWG_COLL_COLLECTIONS;
+        ensure_xmp_namespace(MWG_COLL_NAMESPACE, "mwg-coll");
+        clear_struct_array(meta, array_tag)?;
+        let collections = collections.iter().filter(|c| *c != &Collection::default());
+        for (index, collection) in (1..).zip(collections) {
+            for (field, value) in [
+                ("CollectionName", &collection.name),
+                ("CollectionURI", &collection.uri),
+            ] {
+                if let Some(value) = value {
+                    let tag = format!("{array_tag}[{index}]/mwg-coll:{field}");
+                    meta.set_tag_string(&tag, value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,613 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{tags, Result, Rexiv2Error};
+
+/// Selects which of a file's date/time tags an operation applies to.
+///
+/// Each group covers both the Exif tag and its XMP equivalents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DateTimeSet {
+    /// When the file was last changed: `Exif.Image.DateTime`, `Xmp.xmp.ModifyDate`, and
+    /// `Xmp.tiff.DateTime`.
+    pub modified: bool,
+    /// When the photo was taken: `Exif.Photo.DateTimeOriginal`, `Xmp.exif.DateTimeOriginal`, and
+    /// `Xmp.photoshop.DateCreated`.
+    pub original: bool,
+    /// When the image was digitized: `Exif.Photo.DateTimeDigitized`,
+    /// `Xmp.exif.DateTimeDigitized`, and `Xmp.xmp.CreateDate`.
+    pub digitized: bool,
+    /// The UTC time of the GPS fix: `Exif.GPSInfo.GPSDateStamp` and `GPSTimeStamp`, and
+    /// `Xmp.exif.GPSTimeStamp`.
+    pub gps: bool,
+}
+
+impl DateTimeSet {
+    /// Every date/time tag.
+    pub const ALL: DateTimeSet = DateTimeSet {
+        modified: true,
+        original: true,
+        digitized: true,
+        gps: true,
+    };
+    /// The tags recording when the photo was taken and digitized, which are usually the same.
+    pub const CAPTURE: DateTimeSet = DateTimeSet {
+        modified: false,
+        original: true,
+        digitized: true,
+        gps: false,
+    };
+
+    /// The Exif and XMP tags holding a local date and time, as opposed to the GPS tags.
+    pub(crate) fn local_tags(&self) -> Vec<&'static str> {
+        let mut names = vec![];
+        if self.modified {
+            names.extend([
+                tags::exif::DATE_TIME,
+                tags::xmp::XMP_MODIFY_DATE,
+                tags::xmp::TIFF_DATE_TIME,
+            ]);
+        }
+        if self.original {
+            names.extend([
+                tags::exif::DATE_TIME_ORIGINAL,
+                tags::xmp::EXIF_DATE_TIME_ORIGINAL,
+                tags::xmp::PHOTOSHOP_DATE_CREATED,
+            ]);
+        }
+        if self.digitized {
+            names.extend([
+                tags::exif::DATE_TIME_DIGITIZED,
+                tags::xmp::EXIF_DATE_TIME_DIGITIZED,
+                tags::xmp::XMP_CREATE_DATE,
+            ]);
+        }
+        names
+    }
+}
+
+/// A tag, or group of tags, from which the date a photo was taken can be read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CaptureDateSource {
+    /// `Exif.Photo.DateTimeOriginal`, with its fractional seconds and offset.
+    ExifOriginal,
+    /// `Exif.Photo.DateTimeDigitized`, with its fractional seconds and offset.
+    ExifDigitized,
+    /// `Exif.Image.DateTime`, with its fractional seconds and offset. This is often updated
+    /// when the file is edited.
+    ExifModified,
+    /// `Xmp.exif.DateTimeOriginal`.
+    XmpOriginal,
+    /// `Xmp.photoshop.DateCreated`.
+    XmpDateCreated,
+    /// `Xmp.xmp.CreateDate`.
+    XmpCreateDate,
+    /// `Iptc.Application2.DateCreated`, with `Iptc.Application2.TimeCreated` if present.
+    Iptc,
+    /// `Exif.GPSInfo.GPSDateStamp` and `GPSTimeStamp`, which are in UTC.
+    Gps,
+    /// The fallback value given to `CaptureDatePolicy::fallback`, such as the time the file was
+    /// last modified.
+    Fallback,
+}
+
+/// The order in which to look for the date a photo was taken, for use with
+/// `Metadata::resolve_capture_date`.
+///
+/// The default order is Exif `DateTimeOriginal`, Exif `DateTimeDigitized`, XMP
+/// `DateTimeOriginal`, XMP `DateCreated`, XMP `CreateDate`, then IPTC `DateCreated`.
+///
+/// # Examples
+/// ```
+/// use rexiv2::{CaptureDatePolicy, CaptureDateSource, ExifDateTime};
+/// let modified = ExifDateTime::new(2022, 8, 9, 10, 0, 0)?;
+/// let policy = CaptureDatePolicy::default().fallback(modified);
+/// assert_eq!(policy.sources().last(), Some(&CaptureDateSource::Fallback));
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CaptureDatePolicy {
+    sources: Vec<CaptureDateSource>,
+    fallback: Option<ExifDateTime>,
+}
+
+impl CaptureDatePolicy {
+    /// Create a policy that tries the given sources in order.
+    pub fn new(sources: Vec<CaptureDateSource>) -> CaptureDatePolicy {
+        CaptureDatePolicy { sources, fallback: None }
+    }
+
+    /// Use the given date if no tag holds one. It's tried wherever
+    /// `CaptureDateSource::Fallback` appears in the order, which is added at the end if absent.
+    pub fn fallback(mut self, date: ExifDateTime) -> CaptureDatePolicy {
+        if !self.sources.contains(&CaptureDateSource::Fallback) {
+            self.sources.push(CaptureDateSource::Fallback);
+        }
+        self.fallback = Some(date);
+        self
+    }
+
+    /// The sources to try, in order.
+    pub fn sources(&self) -> &[CaptureDateSource] {
+        &self.sources
+    }
+
+    /// The fallback date, if one was given.
+    pub fn fallback_date(&self) -> Option<&ExifDateTime> {
+        self.fallback.as_ref()
+    }
+}
+
+impl Default for CaptureDatePolicy {
+    fn default() -> CaptureDatePolicy {
+        CaptureDatePolicy::new(vec![
+            CaptureDateSource::ExifOriginal,
+            CaptureDateSource::ExifDigitized,
+            CaptureDateSource::XmpOriginal,
+            CaptureDateSource::XmpDateCreated,
+            CaptureDateSource::XmpCreateDate,
+            CaptureDateSource::Iptc,
+        ])
+    }
+}
+
+/// A date and time as stored in Exif metadata, such as the moment a photo was taken.
+///
+/// The Exif format is `YYYY:MM:DD HH:MM:SS`. Fractions of a second and the offset from UTC
+/// are kept in separate tags, but are included here so a full timestamp can be represented.
+/// When parsed or displayed as a string, these are appended as e.g. `.250` and `+02:00`.
+///
+/// # Examples
+/// ```
+/// use rexiv2::ExifDateTime;
+/// let date: ExifDateTime = "2022:08:07 11:19:44".parse()?;
+/// assert_eq!((date.year, date.month, date.day), (2022, 8, 7));
+/// assert_eq!(date.to_string(), "2022:08:07 11:19:44");
+/// assert!("2022-08-07 11:19:44".parse::<ExifDateTime>().is_err());
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExifDateTime {
+    /// The year, from 0 to 9999.
+    pub year: u16,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1 to 31.
+    pub day: u8,
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+    /// The second, from 0 to 59.
+    pub second: u8,
+    /// Nanoseconds past the second, if known.
+    pub nanosecond: Option<u32>,
+    /// The offset from UTC in minutes, if known.
+    pub offset_minutes: Option<i16>,
+}
+
+impl ExifDateTime {
+    /// Create a date and time with no fractional seconds or offset, checking that it's valid.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::ExifDateTime;
+    /// assert!(ExifDateTime::new(2024, 2, 29, 12, 0, 0).is_ok());
+    /// assert!(ExifDateTime::new(2023, 2, 29, 12, 0, 0).is_err());
+    /// ```
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<ExifDateTime> {
+        let date = ExifDateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond: None,
+            offset_minutes: None,
+        };
+        date.validate()?;
+        Ok(date)
+    }
+
+    /// Check that every field is within range, including that the day exists in the month.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut date = rexiv2::ExifDateTime::new(2022, 8, 7, 11, 19, 44)?;
+    /// date.offset_minutes = Some(25 * 60);
+    /// assert!(date.validate().is_err());
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        let valid = self.year <= 9999
+            && (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+            && self.nanosecond.map_or(true, |ns| ns < 1_000_000_000)
+            && self
+                .offset_minutes
+                .map_or(true, |offset| offset.unsigned_abs() < 24 * 60);
+        if valid {
+            Ok(())
+        } else {
+            Err(Rexiv2Error::InvalidValue(format!(
+                "Invalid date/time: {self:?}"
+            )))
+        }
+    }
+
+    /// The date and time alone, in the format used by Exif tags such as `DateTimeOriginal`.
+    ///
+    /// # Examples
+    /// ```
+    /// let date: rexiv2::ExifDateTime = "2022:08:07 11:19:44.5+02:00".parse()?;
+    /// assert_eq!(date.to_exif_string(), "2022:08:07 11:19:44");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn to_exif_string(&self) -> String {
+        format!(
+            "{:04}:{:02}:{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// Parse a date and time in the ISO 8601 format used by XMP, such as
+    /// `"2022-08-07T11:19:44.25+02:00"`.
+    ///
+    /// Values without a time of day, such as `"2022-08-07"`, are rejected.
+    ///
+    /// # Examples
+    /// ```
+    /// let date = rexiv2::ExifDateTime::from_xmp_str("2022-08-07T11:19+02:00")?;
+    /// assert_eq!(date.to_string(), "2022:08:07 11:19:00+02:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn from_xmp_str(s: &str) -> Result<ExifDateTime> {
+        parse_xmp(s.trim())
+            .filter(|date| date.validate().is_ok())
+            .ok_or_else(|| Rexiv2Error::InvalidValue(format!("Invalid XMP date/time: {s:?}")))
+    }
+
+    /// The date and time in the ISO 8601 format used by XMP, including any fractional seconds
+    /// and offset.
+    ///
+    /// # Examples
+    /// ```
+    /// let date: rexiv2::ExifDateTime = "2022:08:07 11:19:44.5+02:00".parse()?;
+    /// assert_eq!(date.to_xmp_string(), "2022-08-07T11:19:44.5+02:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn to_xmp_string(&self) -> String {
+        let mut xmp = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        );
+        if let Some(subsec) = self.subsec_string() {
+            xmp.push('.');
+            xmp.push_str(&subsec);
+        }
+        if let Some(offset) = self.offset_string() {
+            xmp.push_str(&offset);
+        }
+        xmp
+    }
+
+    /// The date in the format used by the IPTC `DateCreated` tag, e.g. `"2022-08-07"`.
+    pub(crate) fn iptc_date_string(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// The time of day and offset in the format used by the IPTC `TimeCreated` tag, e.g.
+    /// `"11:19:44+02:00"`.
+    pub(crate) fn iptc_time_string(&self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}{}",
+            self.hour,
+            self.minute,
+            self.second,
+            self.offset_string().as_deref().unwrap_or("+00:00")
+        )
+    }
+
+    /// Move the date and time by a number of seconds, which may be negative. The fractions of a
+    /// second and the offset are left as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// let date: rexiv2::ExifDateTime = "2022:12:31 23:00:00".parse()?;
+    /// assert_eq!(date.shifted(2 * 60 * 60)?.to_string(), "2023:01:01 01:00:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn shifted(&self, seconds: i64) -> Result<ExifDateTime> {
+        self.local_seconds()
+            .checked_add(seconds)
+            .and_then(|total| self.with_local_seconds(total))
+            .ok_or_else(|| {
+                Rexiv2Error::InvalidValue(format!(
+                    "Shifting {self} by {seconds} seconds is out of range"
+                ))
+            })
+    }
+
+    /// The number of seconds between the Unix epoch and this date and time, ignoring the
+    /// offset and fractions of a second.
+    pub(crate) fn local_seconds(&self) -> i64 {
+        let days = days_from_civil(self.year as i64, self.month as i64, self.day as i64);
+        days * 86_400 + self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64
+    }
+
+    /// A copy of this date and time moved to the given number of seconds since the Unix epoch,
+    /// ignoring the offset and fractions of a second. Returns `None` outside years 0 to 9999.
+    pub(crate) fn with_local_seconds(&self, seconds: i64) -> Option<ExifDateTime> {
+        let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+        let time = seconds.rem_euclid(86_400);
+        Some(ExifDateTime {
+            year: u16::try_from(year).ok().filter(|&year| year <= 9999)?,
+            month: month as u8,
+            day: day as u8,
+            hour: (time / 3600) as u8,
+            minute: (time / 60 % 60) as u8,
+            second: (time % 60) as u8,
+            ..*self
+        })
+    }
+
+    /// The fractions of a second, in the format used by Exif `SubSecTime` tags (e.g. `"25"` for
+    /// a quarter of a second).
+    pub fn subsec_string(&self) -> Option<String> {
+        self.nanosecond.map(|ns| {
+            let digits = format!("{ns:09}");
+            let digits = digits.trim_end_matches('0');
+            if digits.is_empty() {
+                "0".to_string()
+            } else {
+                digits.to_string()
+            }
+        })
+    }
+
+    /// The offset from UTC, in the format used by Exif `OffsetTime` tags (e.g. `"+02:00"`).
+    pub fn offset_string(&self) -> Option<String> {
+        self.offset_minutes.map(format_offset)
+    }
+}
+
+impl std::str::FromStr for ExifDateTime {
+    type Err = Rexiv2Error;
+
+    /// Parse a date and time in Exif format, optionally followed by fractions of a second and an
+    /// offset from UTC, as in `"2022:08:07 11:19:44.25+02:00"`.
+    fn from_str(s: &str) -> Result<ExifDateTime> {
+        parse(s.trim())
+            .filter(|date| date.validate().is_ok())
+            .ok_or_else(|| Rexiv2Error::InvalidValue(format!("Invalid date/time: {s:?}")))
+    }
+}
+
+impl std::fmt::Display for ExifDateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_exif_string())?;
+        if let Some(subsec) = self.subsec_string() {
+            write!(f, ".{subsec}")?;
+        }
+        if let Some(offset) = self.offset_string() {
+            write!(f, "{offset}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Indicates whether an Exif date/time value is blank, which the standard uses for unknown dates.
+pub(crate) fn is_blank(s: &str) -> bool {
+    s.chars().all(|c| c == ' ' || c == ':' || c == '\0')
+}
+
+/// Parse the contents of an Exif `SubSecTime` tag into nanoseconds.
+pub(crate) fn parse_subsec(s: &str) -> Option<u32> {
+    let s = s.trim();
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    // Only the first nine digits are significant at nanosecond precision.
+    let digits = &s[..s.len().min(9)];
+    let value: u32 = digits.parse().ok()?;
+    Some(value * 10u32.pow(9 - digits.len() as u32))
+}
+
+/// Format an offset from UTC in minutes as in an Exif `OffsetTime` tag, such as `"+02:00"`.
+pub(crate) fn format_offset(offset_minutes: i16) -> String {
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let offset = offset_minutes.unsigned_abs();
+    format!("{}{:02}:{:02}", sign, offset / 60, offset % 60)
+}
+
+/// Parse the contents of an Exif `OffsetTime` tag, such as `"+02:00"`, into minutes.
+pub(crate) fn parse_offset(s: &str) -> Option<i16> {
+    let s = s.trim();
+    if s == "Z" {
+        return Some(0);
+    }
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = rest.split_once(':')?;
+    let hours = parse_digits(hours, 2)? as i16;
+    let minutes = parse_digits(minutes, 2)? as i16;
+    Some(sign * (hours * 60 + minutes))
+}
+
+/// Parse the full date/time format described in `ExifDateTime::from_str`.
+fn parse(s: &str) -> Option<ExifDateTime> {
+    let base = s.get(..19).filter(|base| base.is_ascii())?;
+    let rest = &s[19..];
+    let bytes = base.as_bytes();
+    if bytes[4] != b':'
+        || bytes[7] != b':'
+        || bytes[10] != b' '
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+
+    let (subsec, offset) = match rest.find(['+', '-', 'Z']) {
+        Some(pos) => (&rest[..pos], Some(&rest[pos..])),
+        None => (rest, None),
+    };
+    let nanosecond = match subsec {
+        "" => None,
+        subsec => Some(parse_subsec(subsec.strip_prefix('.')?)?),
+    };
+    let offset_minutes = match offset {
+        Some(offset) => Some(parse_offset(offset)?),
+        None => None,
+    };
+
+    Some(ExifDateTime {
+        year: parse_digits(&base[0..4], 4)? as u16,
+        month: parse_digits(&base[5..7], 2)? as u8,
+        day: parse_digits(&base[8..10], 2)? as u8,
+        hour: parse_digits(&base[11..13], 2)? as u8,
+        minute: parse_digits(&base[14..16], 2)? as u8,
+        second: parse_digits(&base[17..19], 2)? as u8,
+        nanosecond,
+        offset_minutes,
+    })
+}
+
+/// Parse the ISO 8601 format described in `ExifDateTime::from_xmp_str`.
+fn parse_xmp(s: &str) -> Option<ExifDateTime> {
+    let (date, time) = s.split_once('T')?;
+    let date_bytes = date.as_bytes();
+    if date_bytes.len() != 10 || date_bytes[4] != b'-' || date_bytes[7] != b'-' {
+        return None;
+    }
+    // Seconds are optional in XMP.
+    let (hours_minutes, seconds, rest) = if time.get(5..6) == Some(":") {
+        (time.get(..5)?, time.get(6..8)?, time.get(8..)?)
+    } else {
+        (time.get(..5)?, "00", time.get(5..)?)
+    };
+    parse(&format!(
+        "{} {}:{}{}",
+        date.replace('-', ":"),
+        hours_minutes,
+        seconds,
+        rest
+    ))
+}
+
+/// Parse an XMP date that may lack a time of day, taking such dates to be at midnight.
+pub(crate) fn parse_xmp_date(s: &str) -> Option<ExifDateTime> {
+    let s = s.trim();
+    if s.len() == 10 {
+        parse_xmp(&format!("{s}T00:00:00"))
+    } else {
+        parse_xmp(s)
+    }
+    .filter(|date| date.validate().is_ok())
+}
+
+/// Parse the values of an IPTC date tag such as `DateCreated` and optionally the matching time
+/// tag such as `TimeCreated`. Both Exiv2's format (`"2022-08-07"`, `"11:19:44+02:00"`) and the
+/// compact IIM one (`"20220807"`, `"111944+0200"`) are accepted.
+pub(crate) fn parse_iptc(date: &str, time: Option<&str>) -> Option<ExifDateTime> {
+    let date = date.trim();
+    let date = match date.len() {
+        8 => format!("{}-{}-{}", date.get(..4)?, date.get(4..6)?, date.get(6..)?),
+        _ => date.to_string(),
+    };
+    let time = time.map_or("00:00:00", str::trim);
+    let time = if time.get(2..3) == Some(":") {
+        time.to_string()
+    } else {
+        let offset = match time.get(6..) {
+            Some("") | None => String::new(),
+            Some(offset) => format!("{}:{}", offset.get(..3)?, offset.get(3..)?),
+        };
+        format!(
+            "{}:{}:{}{}",
+            time.get(..2)?,
+            time.get(2..4)?,
+            time.get(4..6)?,
+            offset
+        )
+    };
+    parse_xmp(&format!("{date}T{time}")).filter(|date| date.validate().is_ok())
+}
+
+/// Parse a field of exactly `len` ASCII digits.
+fn parse_digits(s: &str, len: usize) -> Option<u32> {
+    if s.len() == len && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// The number of days between the Unix epoch and the given date in the proleptic Gregorian
+/// calendar. See <http://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of `days_from_civil`, returning the year, month, and day.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -0,0 +1,149 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+/// The start of the URIs in the IPTC controlled vocabulary of digital source types.
+const VOCABULARY: &str = "http://cv.iptc.org/newscodes/digitalsourcetype/";
+
+/// All the digital source types with a dedicated variant, along with their codes.
+const KNOWN_SOURCE_TYPES: [(DigitalSourceType, &str); 17] = [
+    (DigitalSourceType::DigitalCapture, "digitalCapture"),
+    (
+        DigitalSourceType::ComputationalCapture,
+        "computationalCapture",
+    ),
+    (DigitalSourceType::NegativeFilm, "negativeFilm"),
+    (DigitalSourceType::PositiveFilm, "positiveFilm"),
+    (DigitalSourceType::Print, "print"),
+    (DigitalSourceType::HumanEdits, "humanEdits"),
+    (
+        DigitalSourceType::AlgorithmicallyEnhanced,
+        "algorithmicallyEnhanced",
+    ),
+    (DigitalSourceType::DigitalCreation, "digitalCreation"),
+    (DigitalSourceType::DataDrivenMedia, "dataDrivenMedia"),
+    (
+        DigitalSourceType::TrainedAlgorithmicMedia,
+        "trainedAlgorithmicMedia",
+    ),
+    (DigitalSourceType::AlgorithmicMedia, "algorithmicMedia"),
+    (DigitalSourceType::ScreenCapture, "screenCapture"),
+    (DigitalSourceType::VirtualRecording, "virtualRecording"),
+    (DigitalSourceType::Composite, "composite"),
+    (DigitalSourceType::CompositeCapture, "compositeCapture"),
+    (DigitalSourceType::CompositeSynthetic, "compositeSynthetic"),
+    (
+        DigitalSourceType::CompositeWithTrainedAlgorithmicMedia,
+        "compositeWithTrainedAlgorithmicMedia",
+    ),
+];
+
+/// How the content of an image was created, from the IPTC controlled vocabulary of digital source
+/// types, as stored in `Xmp.iptcExt.DigitalSourceType`.
+///
+/// # Examples
+/// ```
+/// use rexiv2::DigitalSourceType;
+/// let source = DigitalSourceType::TrainedAlgorithmicMedia;
+/// assert_eq!(
+///     source.uri(),
+///     "http://cv.iptc.org/newscodes/digitalsourcetype/trainedAlgorithmicMedia",
+/// );
+/// assert!(source.is_generative_ai());
+/// assert_eq!(DigitalSourceType::from_uri(&source.uri()), source);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DigitalSourceType {
+    /// Captured directly from a real-life scene by a digital camera or similar device.
+    DigitalCapture,
+    /// Captured from a real-life scene, then processed by algorithms such as those combining
+    /// several exposures in a smartphone.
+    ComputationalCapture,
+    /// Digitised from a negative on film.
+    NegativeFilm,
+    /// Digitised from a positive on a transparency or other film.
+    PositiveFilm,
+    /// Digitised from a non-transparent print.
+    Print,
+    /// Edited by a person using non-generative tools.
+    HumanEdits,
+    /// Modified by algorithms that don't add new content, such as noise reduction.
+    AlgorithmicallyEnhanced,
+    /// Created by a person using non-generative tools, such as a drawing application.
+    DigitalCreation,
+    /// Created from data, such as a satellite image or a chart.
+    DataDrivenMedia,
+    /// Created by a generative AI model trained on sampled content.
+    TrainedAlgorithmicMedia,
+    /// Created purely by an algorithm not based on any sampled training data, such as a fractal.
+    AlgorithmicMedia,
+    /// Captured from the screen of a device.
+    ScreenCapture,
+    /// Recorded from a virtual scene, such as a video game.
+    VirtualRecording,
+    /// Combined from several elements of unspecified origin.
+    Composite,
+    /// Combined from several captured elements.
+    CompositeCapture,
+    /// Combined from elements, at least one of which is synthetic.
+    CompositeSynthetic,
+    /// Combined from elements, at least one of which was created by generative AI.
+    CompositeWithTrainedAlgorithmicMedia,
+    /// A value from outside the IPTC vocabulary, holding the original URI.
+    Other(String),
+}
+
+impl DigitalSourceType {
+    /// Look up a digital source type from its URI in the IPTC vocabulary. The `https` form of
+    /// the URI is also accepted.
+    ///
+    /// Unrecognised values are returned as `DigitalSourceType::Other`.
+    pub fn from_uri(uri: &str) -> DigitalSourceType {
+        let uri = uri.trim();
+        let code = uri
+            .strip_prefix(VOCABULARY)
+            .or_else(|| uri.strip_prefix("https://cv.iptc.org/newscodes/digitalsourcetype/"));
+        KNOWN_SOURCE_TYPES
+            .iter()
+            .find(|(_, known_code)| code == Some(*known_code))
+            .map(|(source_type, _)| source_type.clone())
+            .unwrap_or_else(|| DigitalSourceType::Other(uri.to_string()))
+    }
+
+    /// The URI of the digital source type in the IPTC vocabulary.
+    pub fn uri(&self) -> String {
+        match self {
+            DigitalSourceType::Other(uri) => uri.clone(),
+            _ => {
+                let code = KNOWN_SOURCE_TYPES
+                    .iter()
+                    .find(|(source_type, _)| source_type == self)
+                    .map_or("", |(_, code)| code);
+                format!("{VOCABULARY}{code}")
+            }
+        }
+    }
+
+    /// Indicates whether the content was created, in whole or in part, by generative AI.
+    pub fn is_generative_ai(&self) -> bool {
+        matches!(
+            self,
+            DigitalSourceType::TrainedAlgorithmicMedia
+                | DigitalSourceType::CompositeWithTrainedAlgorithmicMedia
+        )
+    }
+}
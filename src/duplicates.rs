@@ -0,0 +1,161 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{tags, ExifDateTime, Metadata};
+use std::collections::HashMap;
+
+/// The details identifying the shot an image came from, used to spot duplicates and bursts.
+///
+/// Two images with equal signatures are almost certainly copies of the same shot, as long as the
+/// signature isn't empty.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ContentSignature {
+    /// The moment the shot was taken, including fractions of a second if recorded (see
+    /// `Metadata::get_capture_timestamp()`).
+    pub capture_time: Option<ExifDateTime>,
+    /// The serial number of the camera body (`Exif.Photo.BodySerialNumber`).
+    pub camera_serial: Option<String>,
+    /// The identifier the camera or software assigned to the image (`Exif.Photo.ImageUniqueID`).
+    pub image_unique_id: Option<String>,
+}
+
+impl ContentSignature {
+    pub(crate) fn read_from(meta: &Metadata) -> ContentSignature {
+        let get = |tag| meta.get_trimmed_string(&[tag]);
+        ContentSignature {
+            capture_time: meta.get_capture_timestamp().ok(),
+            camera_serial: get(tags::exif::BODY_SERIAL_NUMBER),
+            image_unique_id: get(tags::exif::IMAGE_UNIQUE_ID),
+        }
+    }
+
+    /// Indicates whether none of the details are known, in which case the signature can't be
+    /// used to match images.
+    pub fn is_empty(&self) -> bool {
+        *self == ContentSignature::default()
+    }
+
+    /// The capture time in milliseconds, for measuring the gaps between shots.
+    fn capture_millis(&self) -> Option<i64> {
+        let time = self.capture_time.as_ref()?;
+        let millis = time.nanosecond.unwrap_or(0) / 1_000_000;
+        Some(time.local_seconds() * 1000 + millis as i64)
+    }
+}
+
+/// Group the images that are probably duplicates of each other or part of the same burst, from
+/// their signatures.
+///
+/// Images are grouped if they have the same unique image ID, or if they come from the same camera
+/// (or cameras with unknown serial numbers) and were taken at most `max_gap_millis` milliseconds
+/// apart. Bursts are chained, so a long burst forms a single group even if its first and last
+/// shots are far apart. Passing zero only groups shots taken at the same moment.
+///
+/// Returns the indices of the signatures in each group of two or more images, in ascending order.
+///
+/// # Examples
+/// ```
+/// use rexiv2::ContentSignature;
+/// let shot = |time: &str| ContentSignature {
+///     capture_time: Some(time.parse().unwrap()),
+///     camera_serial: Some("0123456789".to_string()),
+///     image_unique_id: None,
+/// };
+/// let signatures = [
+///     shot("2024:05:01 10:00:00.10"),
+///     shot("2024:05:01 12:30:00"),
+///     shot("2024:05:01 10:00:00.30"),
+///     shot("2024:05:01 10:00:00.50"),
+/// ];
+/// assert_eq!(rexiv2::group_duplicates(&signatures, 250), [vec![0, 2, 3]]);
+/// ```
+pub fn group_duplicates(signatures: &[ContentSignature], max_gap_millis: u64) -> Vec<Vec<usize>> {
+    let mut groups = Groups::new(signatures.len());
+
+    let mut by_unique_id = HashMap::new();
+    for (index, signature) in signatures.iter().enumerate() {
+        if let Some(id) = &signature.image_unique_id {
+            match by_unique_id.get(id) {
+                Some(&first) => groups.join(first, index),
+                None => {
+                    by_unique_id.insert(id, index);
+                }
+            }
+        }
+    }
+
+    let mut shots: Vec<_> = signatures
+        .iter()
+        .enumerate()
+        .filter_map(|(index, signature)| {
+            Some((&signature.camera_serial, signature.capture_millis()?, index))
+        })
+        .collect();
+    shots.sort();
+    for pair in shots.windows(2) {
+        let ((first_serial, first_time, first), (second_serial, second_time, second)) =
+            (pair[0], pair[1]);
+        if first_serial == second_serial && (second_time - first_time) as u64 <= max_gap_millis {
+            groups.join(first, second);
+        }
+    }
+
+    groups.into_groups()
+}
+
+/// A disjoint-set forest, for merging the groups of images.
+struct Groups {
+    parents: Vec<usize>,
+}
+
+impl Groups {
+    fn new(len: usize) -> Groups {
+        Groups { parents: (0..len).collect() }
+    }
+
+    fn root(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    fn join(&mut self, first: usize, second: usize) {
+        let (first, second) = (self.root(first), self.root(second));
+        // Keep the lowest index as the root, so groups come out in order of their first image.
+        self.parents[first.max(second)] = first.min(second);
+    }
+
+    fn into_groups(mut self) -> Vec<Vec<usize>> {
+        let mut groups: Vec<Vec<usize>> = vec![];
+        let mut group_of_root: HashMap<usize, usize> = HashMap::new();
+        for index in 0..self.parents.len() {
+            let root = self.root(index);
+            match group_of_root.get(&root) {
+                Some(&group) => groups[group].push(index),
+                None => {
+                    group_of_root.insert(root, groups.len());
+                    groups.push(vec![index]);
+                }
+            }
+        }
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+}
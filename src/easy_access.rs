@@ -0,0 +1,167 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+// The tags consulted by Exiv2's "easy access" functions (see Exiv2's easyaccess.cpp), in the
+// order it consults them. Makernote tags are only listed where their interpreted values are
+// comparable to the standard Exif tag's.
+
+pub(crate) const SATURATION: [&str; 15] = [
+    "Exif.Photo.Saturation",
+    "Exif.CanonCs.Saturation",
+    "Exif.MinoltaCsNew.Saturation",
+    "Exif.MinoltaCsOld.Saturation",
+    "Exif.MinoltaCs7D.Saturation",
+    "Exif.MinoltaCs5D.Saturation",
+    "Exif.Fujifilm.Color",
+    "Exif.Nikon3.Saturation",
+    "Exif.Panasonic.Saturation",
+    "Exif.Pentax.Saturation",
+    "Exif.PentaxDng.Saturation",
+    "Exif.Sigma.Saturation",
+    "Exif.Casio.Saturation",
+    "Exif.Casio2.Saturation",
+    "Exif.Casio2.Saturation2",
+];
+
+pub(crate) const SHARPNESS: [&str; 15] = [
+    "Exif.Photo.Sharpness",
+    "Exif.CanonCs.Sharpness",
+    "Exif.Fujifilm.Sharpness",
+    "Exif.MinoltaCsNew.Sharpness",
+    "Exif.MinoltaCsOld.Sharpness",
+    "Exif.MinoltaCs7D.Sharpness",
+    "Exif.MinoltaCs5D.Sharpness",
+    "Exif.Olympus.SharpnessFactor",
+    "Exif.Panasonic.Sharpness",
+    "Exif.Pentax.Sharpness",
+    "Exif.PentaxDng.Sharpness",
+    "Exif.Sigma.Sharpness",
+    "Exif.Casio.Sharpness",
+    "Exif.Casio2.Sharpness",
+    "Exif.Casio2.Sharpness2",
+];
+
+pub(crate) const CONTRAST: [&str; 15] = [
+    "Exif.Photo.Contrast",
+    "Exif.CanonCs.Contrast",
+    "Exif.Fujifilm.Tone",
+    "Exif.MinoltaCsNew.Contrast",
+    "Exif.MinoltaCsOld.Contrast",
+    "Exif.MinoltaCs7D.Contrast",
+    "Exif.MinoltaCs5D.Contrast",
+    "Exif.Olympus.Contrast",
+    "Exif.Panasonic.Contrast",
+    "Exif.Pentax.Contrast",
+    "Exif.PentaxDng.Contrast",
+    "Exif.Sigma.Contrast",
+    "Exif.Casio.Contrast",
+    "Exif.Casio2.Contrast",
+    "Exif.Casio2.Contrast2",
+];
+
+pub(crate) const WHITE_BALANCE: [&str; 14] = [
+    "Exif.CanonSi.WhiteBalance",
+    "Exif.Fujifilm.WhiteBalance",
+    "Exif.Sigma.WhiteBalance",
+    "Exif.Nikon1.WhiteBalance",
+    "Exif.Nikon2.WhiteBalance",
+    "Exif.Nikon3.WhiteBalance",
+    "Exif.Olympus.WhiteBalance",
+    "Exif.OlympusCs.WhiteBalance",
+    "Exif.Panasonic.WhiteBalance",
+    "Exif.MinoltaCs5D.WhiteBalance",
+    "Exif.MinoltaCs7D.WhiteBalance",
+    "Exif.MinoltaCsNew.WhiteBalance",
+    "Exif.MinoltaCsOld.WhiteBalance",
+    "Exif.Photo.WhiteBalance",
+];
+
+pub(crate) const METERING_MODE: [&str; 8] = [
+    "Exif.Photo.MeteringMode",
+    "Exif.Image.MeteringMode",
+    "Exif.CanonCs.MeteringMode",
+    "Exif.MinoltaCs5D.MeteringMode",
+    "Exif.MinoltaCsOld.MeteringMode",
+    "Exif.OlympusCs.MeteringMode",
+    "Exif.Pentax.MeteringMode",
+    "Exif.Sigma.MeteringMode",
+];
+
+pub(crate) const EXPOSURE_PROGRAM: [&str; 9] = [
+    "Exif.Photo.ExposureProgram",
+    "Exif.Image.ExposureProgram",
+    "Exif.CanonCs.ExposureProgram",
+    "Exif.MinoltaCs7D.ExposureMode",
+    "Exif.MinoltaCs5D.ExposureMode",
+    "Exif.MinoltaCsNew.ExposureMode",
+    "Exif.MinoltaCsOld.ExposureMode",
+    "Exif.OlympusCs.ExposureMode",
+    "Exif.Sigma.ExposureMode",
+];
+
+pub(crate) const FLASH: [&str; 4] = [
+    "Exif.Photo.Flash",
+    "Exif.Image.Flash",
+    "Exif.Pentax.Flash",
+    "Exif.PentaxDng.Flash",
+];
+
+pub(crate) const LENS_NAME: [&str; 9] = [
+    "Exif.Photo.LensModel",
+    "Exif.CanonCs.LensType",
+    "Exif.NikonLd1.LensIDNumber",
+    "Exif.NikonLd2.LensIDNumber",
+    "Exif.NikonLd3.LensIDNumber",
+    "Exif.Pentax.LensType",
+    "Exif.PentaxDng.LensType",
+    "Exif.Minolta.LensID",
+    "Exif.OlympusEq.LensType",
+];
+
+pub(crate) const SERIAL_NUMBER: [&str; 9] = [
+    "Exif.Image.CameraSerialNumber",
+    "Exif.Photo.BodySerialNumber",
+    "Exif.Canon.SerialNumber",
+    "Exif.Fujifilm.SerialNumber",
+    "Exif.Nikon3.SerialNumber",
+    "Exif.Nikon3.SerialNO",
+    "Exif.Olympus.SerialNumber2",
+    "Exif.OlympusEq.SerialNumber",
+    "Exif.Sigma.SerialNumber",
+];
+
+// Makernotes record these in their own units, so only the standard tags are consulted.
+
+pub(crate) const SHUTTER_SPEED_VALUE: [&str; 2] = [
+    "Exif.Photo.ShutterSpeedValue",
+    "Exif.Image.ShutterSpeedValue",
+];
+
+pub(crate) const BRIGHTNESS_VALUE: [&str; 2] =
+    ["Exif.Photo.BrightnessValue", "Exif.Image.BrightnessValue"];
+
+pub(crate) const EXPOSURE_BIAS_VALUE: [&str; 2] = [
+    "Exif.Photo.ExposureBiasValue",
+    "Exif.Image.ExposureBiasValue",
+];
+
+pub(crate) const MAX_APERTURE_VALUE: [&str; 2] =
+    ["Exif.Photo.MaxApertureValue", "Exif.Image.MaxApertureValue"];
+
+pub(crate) const SUBJECT_DISTANCE: [&str; 2] =
+    ["Exif.Photo.SubjectDistance", "Exif.Image.SubjectDistance"];
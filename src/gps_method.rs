@@ -0,0 +1,97 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+/// All the location finding methods with a dedicated variant, along with their names.
+const KNOWN_METHODS: [(GpsProcessingMethod, &str); 4] = [
+    (GpsProcessingMethod::Gps, "GPS"),
+    (GpsProcessingMethod::CellId, "CELLID"),
+    (GpsProcessingMethod::Wlan, "WLAN"),
+    (GpsProcessingMethod::Manual, "MANUAL"),
+];
+
+/// The method used to find the location of an image, as stored in
+/// `Exif.GPSInfo.GPSProcessingMethod`.
+///
+/// # Examples
+/// ```
+/// use rexiv2::GpsProcessingMethod;
+/// assert_eq!(GpsProcessingMethod::from_name("CELLID"), GpsProcessingMethod::CellId);
+/// assert_eq!(GpsProcessingMethod::Manual.name(), "MANUAL");
+/// assert_eq!(
+///     GpsProcessingMethod::from_name("QZSS"),
+///     GpsProcessingMethod::Other("QZSS".to_string()),
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GpsProcessingMethod {
+    /// Found by a satellite navigation receiver.
+    Gps,
+    /// Found from the mobile phone cells in range.
+    CellId,
+    /// Found from the wireless networks in range.
+    Wlan,
+    /// Entered by hand, e.g. by placing the image on a map.
+    Manual,
+    /// A method not defined by the Exif standard, holding its original name.
+    Other(String),
+}
+
+impl GpsProcessingMethod {
+    /// Look up a location finding method from its name, as defined by the Exif standard. Names
+    /// are matched case-insensitively.
+    ///
+    /// Unrecognised names are returned as `GpsProcessingMethod::Other`.
+    pub fn from_name(name: &str) -> GpsProcessingMethod {
+        let name = name.trim();
+        KNOWN_METHODS
+            .iter()
+            .find(|(_, known_name)| known_name.eq_ignore_ascii_case(name))
+            .map(|(method, _)| method.clone())
+            .unwrap_or_else(|| GpsProcessingMethod::Other(name.to_string()))
+    }
+
+    /// The name of the location finding method, as recorded in the metadata.
+    pub fn name(&self) -> &str {
+        match self {
+            GpsProcessingMethod::Other(name) => name,
+            _ => KNOWN_METHODS
+                .iter()
+                .find(|(method, _)| method == self)
+                .map_or("", |(_, name)| name),
+        }
+    }
+}
+
+impl std::fmt::Display for GpsProcessingMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Extract the text of a value of Exif's comment type, as formatted by Exiv2, e.g.
+/// `charset=Ascii GPS` or `charset="Unicode" WLAN`.
+pub(crate) fn comment_text(value: &str) -> &str {
+    let text = match value.strip_prefix("charset=") {
+        Some(rest) => match rest.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').map_or("", |(_, text)| text),
+            None => rest.split_once(' ').map_or("", |(_, text)| text),
+        },
+        None => value,
+    };
+    text.trim_matches(|c: char| c.is_whitespace() || c == '\0')
+}
@@ -0,0 +1,367 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Just enough of the HEIF container format to tell apart the images it holds, and to find the
+//! Exif data describing each of them.
+
+use std::collections::HashMap;
+
+/// Item types that hold a picture in their own right, rather than metadata.
+const IMAGE_ITEM_TYPES: [&[u8; 4]; 7] = [
+    b"hvc1", b"av01", b"jpeg", b"grid", b"iden", b"iovl", b"unci",
+];
+
+/// Count the top-level images held in a HEIF container, such as a HEIC or AVIF file.
+///
+/// Bursts, live photos, and other multi-shot captures can hold several images in the same file;
+/// a plain photo holds one. Thumbnails, grid tiles, and auxiliary images (such as depth maps and
+/// alpha planes) are not counted. Use [`Metadata::new_from_heif_image`] to load the metadata of a
+/// particular image.
+///
+/// Returns 0 if the data isn't a HEIF container, or if it can't be parsed.
+///
+/// [`Metadata::new_from_heif_image`]: crate::Metadata::new_from_heif_image
+///
+/// # Examples
+/// ```
+/// assert_eq!(rexiv2::heif_image_count(b"Hello, world!"), 0);
+/// ```
+pub fn heif_image_count(data: &[u8]) -> usize {
+    HeifItems::parse(data).map_or(0, |items| items.images.len())
+}
+
+/// The Exif data of the image at the given index, as returned by `heif_image_count`, in the form
+/// of a JPEG APP1 segment.
+///
+/// Returns `None` if there is no such image, or if it has no Exif data of its own.
+pub(crate) fn exif_for_image(data: &[u8], index: usize) -> Option<Vec<u8>> {
+    let items = HeifItems::parse(data)?;
+    let image = *items.images.get(index)?;
+    let exif = items.exif_items.iter().find(|exif| {
+        items
+            .describes
+            .get(exif)
+            .map_or(false, |ids| ids.contains(&image))
+    })?;
+    let payload = items.item_data(data, *exif)?;
+
+    // The payload starts with the offset from the end of that field to the TIFF header, which is
+    // usually preceded by the same "Exif\0\0" marker as in JPEG files.
+    let offset = u32::from_be_bytes(payload.get(0..4)?.try_into().ok()?) as usize;
+    let tiff = payload.get(4usize.checked_add(offset)?..)?;
+    let mut segment = b"Exif\0\0".to_vec();
+    segment.extend_from_slice(tiff);
+    Some(segment)
+}
+
+/// Where the data of an item is stored.
+struct ItemLocation {
+    /// Whether the extents are relative to the `idat` box rather than the whole file.
+    in_idat: bool,
+    base_offset: u64,
+    extents: Vec<(u64, u64)>,
+}
+
+/// The parts of a HEIF `meta` box needed to select an image and find its metadata.
+struct HeifItems<'a> {
+    /// The top-level image items, with the primary one first.
+    images: Vec<u32>,
+    exif_items: Vec<u32>,
+    /// The items each metadata item describes, from the `cdsc` references.
+    describes: HashMap<u32, Vec<u32>>,
+    locations: HashMap<u32, ItemLocation>,
+    idat: &'a [u8],
+}
+
+impl<'a> HeifItems<'a> {
+    fn parse(data: &'a [u8]) -> Option<HeifItems<'a>> {
+        if data.get(4..8) != Some(b"ftyp") {
+            return None;
+        }
+        let (_, meta) = boxes(data).find(|(kind, _)| kind == b"meta")?;
+        // `meta` is a full box, so its children follow the version and flags.
+        let meta = meta.get(4..)?;
+
+        let mut primary = None;
+        let mut infos = vec![];
+        let mut references = vec![];
+        let mut locations = HashMap::new();
+        let mut idat: &[u8] = &[];
+        for (kind, content) in boxes(meta) {
+            match &kind {
+                b"pitm" => {
+                    let mut reader = Reader::full_box(content)?;
+                    primary = Some(reader.item_id()?);
+                }
+                b"iinf" => infos = parse_iinf(content)?,
+                b"iref" => references = parse_iref(content)?,
+                b"iloc" => locations = parse_iloc(content)?,
+                b"idat" => idat = content,
+                _ => {}
+            }
+        }
+
+        let refers_from = |kind: &[u8; 4], id: u32| {
+            references
+                .iter()
+                .any(|r: &ItemReference| &r.kind == kind && r.from_id == id)
+        };
+        let refers_to = |kind: &[u8; 4], id: u32| {
+            references
+                .iter()
+                .any(|r: &ItemReference| &r.kind == kind && r.to_ids.contains(&id))
+        };
+        let mut images: Vec<u32> = infos
+            .iter()
+            .filter(|info| !info.hidden && IMAGE_ITEM_TYPES.contains(&&info.item_type))
+            .map(|info| info.id)
+            .filter(|&id| {
+                !refers_from(b"thmb", id) && !refers_from(b"auxl", id) && !refers_to(b"dimg", id)
+            })
+            .collect();
+        if let Some(position) = images.iter().position(|&id| Some(id) == primary) {
+            let primary = images.remove(position);
+            images.insert(0, primary);
+        }
+
+        let exif_items = infos
+            .iter()
+            .filter(|info| &info.item_type == b"Exif")
+            .map(|info| info.id)
+            .collect();
+        let describes = references
+            .into_iter()
+            .filter(|reference| &reference.kind == b"cdsc")
+            .map(|reference| (reference.from_id, reference.to_ids))
+            .collect();
+        Some(HeifItems { images, exif_items, describes, locations, idat })
+    }
+
+    /// Gather the data of an item from its extents.
+    fn item_data(&self, data: &[u8], id: u32) -> Option<Vec<u8>> {
+        let location = self.locations.get(&id)?;
+        let source = if location.in_idat { self.idat } else { data };
+        let mut item = vec![];
+        for &(offset, length) in &location.extents {
+            let start = usize::try_from(location.base_offset.checked_add(offset)?).ok()?;
+            let extent = if length == 0 {
+                // A zero length means the extent runs to the end of the data.
+                source.get(start..)?
+            } else {
+                source.get(start..start.checked_add(usize::try_from(length).ok()?)?)?
+            };
+            item.extend_from_slice(extent);
+        }
+        Some(item)
+    }
+}
+
+struct ItemInfo {
+    id: u32,
+    item_type: [u8; 4],
+    hidden: bool,
+}
+
+fn parse_iinf(content: &[u8]) -> Option<Vec<ItemInfo>> {
+    let mut reader = Reader::full_box(content)?;
+    // The number of entries is implied by the boxes that follow it.
+    if reader.version == 0 {
+        reader.u16()?;
+    } else {
+        reader.u32()?;
+    }
+    let infos = boxes(reader.rest())
+        .filter(|(kind, _)| kind == b"infe")
+        .filter_map(|(_, infe)| {
+            let mut reader = Reader::full_box(infe)?;
+            // Older versions of the box predate item types, and so can't describe images.
+            if reader.version < 2 {
+                return None;
+            }
+            let id = if reader.version == 2 {
+                u32::from(reader.u16()?)
+            } else {
+                reader.u32()?
+            };
+            reader.u16()?; // Protection index.
+            Some(ItemInfo {
+                id,
+                item_type: reader.fourcc()?,
+                hidden: reader.flags & 1 != 0,
+            })
+        })
+        .collect();
+    Some(infos)
+}
+
+/// A typed reference from one item to others, such as from a thumbnail to the image it shows.
+struct ItemReference {
+    kind: [u8; 4],
+    from_id: u32,
+    to_ids: Vec<u32>,
+}
+
+fn parse_iref(content: &[u8]) -> Option<Vec<ItemReference>> {
+    let reader = Reader::full_box(content)?;
+    let version = reader.version;
+    let references = boxes(reader.rest())
+        .filter_map(|(kind, reference)| {
+            let mut reader = Reader::new(reference, version);
+            let from_id = reader.item_id()?;
+            let count = reader.u16()?;
+            let to_ids = (0..count)
+                .map(|_| reader.item_id())
+                .collect::<Option<Vec<_>>>()?;
+            Some(ItemReference { kind, from_id, to_ids })
+        })
+        .collect();
+    Some(references)
+}
+
+fn parse_iloc(content: &[u8]) -> Option<HashMap<u32, ItemLocation>> {
+    let mut reader = Reader::full_box(content)?;
+    let sizes = reader.u8()?;
+    let (offset_size, length_size) = (sizes >> 4, sizes & 0xF);
+    let sizes = reader.u8()?;
+    let base_offset_size = sizes >> 4;
+    let index_size = if reader.version == 0 { 0 } else { sizes & 0xF };
+    let count = if reader.version < 2 {
+        u32::from(reader.u16()?)
+    } else {
+        reader.u32()?
+    };
+
+    let mut locations = HashMap::new();
+    for _ in 0..count {
+        let id = if reader.version < 2 {
+            u32::from(reader.u16()?)
+        } else {
+            reader.u32()?
+        };
+        let construction_method = if reader.version == 0 {
+            0
+        } else {
+            reader.u16()? & 0xF
+        };
+        reader.u16()?; // Data reference index.
+        let base_offset = reader.uint(base_offset_size)?;
+        let extent_count = reader.u16()?;
+        let mut extents = vec![];
+        for _ in 0..extent_count {
+            reader.uint(index_size)?;
+            extents.push((reader.uint(offset_size)?, reader.uint(length_size)?));
+        }
+        // Items constructed from other items are only used for image data, never for Exif.
+        if construction_method < 2 {
+            let in_idat = construction_method == 1;
+            locations.insert(id, ItemLocation { in_idat, base_offset, extents });
+        }
+    }
+    Some(locations)
+}
+
+/// Iterate over the boxes in the given data, yielding the type and contents of each.
+///
+/// Iteration stops at the first box whose size doesn't fit in the data.
+fn boxes(data: &[u8]) -> impl Iterator<Item = ([u8; 4], &[u8])> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let header = data.get(pos..pos + 8)?;
+        let kind: [u8; 4] = header[4..8].try_into().ok()?;
+        let (header_len, size) = match u32::from_be_bytes(header[0..4].try_into().ok()?) {
+            // A size of zero means the box extends to the end of the data.
+            0 => (8, data.len() - pos),
+            1 => {
+                let size = u64::from_be_bytes(data.get(pos + 8..pos + 16)?.try_into().ok()?);
+                (16, usize::try_from(size).ok()?)
+            }
+            size => (8, size as usize),
+        };
+        let content = data.get(pos + header_len..pos.checked_add(size)?)?;
+        pos += size;
+        Some((kind, content))
+    })
+}
+
+/// A cursor over the big-endian fields of a box.
+struct Reader<'a> {
+    data: &'a [u8],
+    version: u8,
+    flags: u32,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], version: u8) -> Reader<'a> {
+        Reader { data, version, flags: 0 }
+    }
+
+    /// Start reading a full box, whose contents begin with a version and flags.
+    fn full_box(data: &'a [u8]) -> Option<Reader<'a>> {
+        let header = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?);
+        Some(Reader {
+            data: &data[4..],
+            version: (header >> 24) as u8,
+            flags: header & 0xFF_FFFF,
+        })
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        self.data
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let taken = self.data.get(..len)?;
+        self.data = &self.data[len..];
+        Some(taken)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.take(2)?.try_into().ok()?))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn fourcc(&mut self) -> Option<[u8; 4]> {
+        self.take(4)?.try_into().ok()
+    }
+
+    /// Read an unsigned integer of 0, 4, or 8 bytes, as used by `iloc` boxes.
+    fn uint(&mut self, size: u8) -> Option<u64> {
+        match size {
+            0 => Some(0),
+            4 => self.u32().map(u64::from),
+            8 => Some(u64::from_be_bytes(self.take(8)?.try_into().ok()?)),
+            _ => None,
+        }
+    }
+
+    /// Read an item ID, which is 16 bits wide in version 0 boxes and 32 bits wide otherwise.
+    fn item_id(&mut self) -> Option<u32> {
+        if self.version == 0 {
+            self.u16().map(u32::from)
+        } else {
+            self.u32()
+        }
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers for working with the pixel data of images and thumbnails via the `image` crate.
+
+use image::codecs::jpeg::JpegEncoder;
+use image::{ColorType, DynamicImage};
+
+use super::{Metadata, Orientation, Result, Rexiv2Error};
+
+/// The lowest JPEG quality `Metadata::set_thumbnail_from_buffer_with` will drop to in order to
+/// keep a thumbnail within its size limit.
+const MIN_JPEG_QUALITY: u8 = 10;
+
+/// Options that control how thumbnails are prepared by
+/// [`Metadata::set_thumbnail_from_buffer_with`].
+///
+/// By default, thumbnails are scaled down to fit within the 160x120 pixels recommended by the
+/// Exif standard, converted to JPEG if they're in another format, and compressed until they take
+/// up no more than 48 KiB, which leaves room for the rest of the Exif data within the 64 KiB a
+/// JPEG file can hold.
+///
+/// # Examples
+/// ```
+/// let options = rexiv2::ThumbnailOptions::new().max_dimensions(256, 256).jpeg_quality(90);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThumbnailOptions {
+    max_width: u32,
+    max_height: u32,
+    jpeg_quality: u8,
+    max_bytes: usize,
+    convert_to_jpeg: bool,
+}
+
+impl Default for ThumbnailOptions {
+    fn default() -> ThumbnailOptions {
+        ThumbnailOptions {
+            max_width: 160,
+            max_height: 120,
+            jpeg_quality: 85,
+            max_bytes: 48 * 1024,
+            convert_to_jpeg: true,
+        }
+    }
+}
+
+impl ThumbnailOptions {
+    /// Create the default set of options.
+    pub fn new() -> ThumbnailOptions {
+        ThumbnailOptions::default()
+    }
+
+    /// Scale thumbnails down, preserving their aspect ratio, to fit within the given dimensions.
+    pub fn max_dimensions(mut self, width: u32, height: u32) -> ThumbnailOptions {
+        self.max_width = width.max(1);
+        self.max_height = height.max(1);
+        self
+    }
+
+    /// The quality, from 1 to 100, to encode thumbnails at when they need re-encoding.
+    pub fn jpeg_quality(mut self, quality: u8) -> ThumbnailOptions {
+        self.jpeg_quality = quality.clamp(1, 100);
+        self
+    }
+
+    /// Lower the quality of thumbnails larger than the given number of bytes until they fit, or
+    /// fail if they still don't at the lowest quality.
+    pub fn max_bytes(mut self, bytes: usize) -> ThumbnailOptions {
+        self.max_bytes = bytes;
+        self
+    }
+
+    /// Whether to convert thumbnails in other formats, such as PNG, to JPEG. If not, they're
+    /// rejected, since Exif thumbnails must be JPEG images.
+    pub fn convert_to_jpeg(mut self, convert: bool) -> ThumbnailOptions {
+        self.convert_to_jpeg = convert;
+        self
+    }
+}
+
+/// Transform the given image according to an orientation, producing pixels that are the
+/// right way up.
+///
+/// `Normal` and `Unspecified` orientations return an unmodified copy of the image.
+///
+/// # Examples
+/// ```
+/// let img = image::DynamicImage::new_rgb8(3, 2);
+/// let rotated = rexiv2::apply_orientation(&img, rexiv2::Orientation::Rotate90);
+/// assert_eq!((rotated.width(), rotated.height()), (2, 3));
+/// ```
+pub fn apply_orientation(image: &DynamicImage, orientation: Orientation) -> DynamicImage {
+    match orientation {
+        Orientation::Unspecified | Orientation::Normal => image.clone(),
+        Orientation::HorizontalFlip => image.fliph(),
+        Orientation::Rotate180 => image.rotate180(),
+        Orientation::VerticalFlip => image.flipv(),
+        Orientation::Rotate90HorizontalFlip => image.rotate90().fliph(),
+        Orientation::Rotate90 => image.rotate90(),
+        Orientation::Rotate90VerticalFlip => image.rotate90().flipv(),
+        Orientation::Rotate270 => image.rotate270(),
+    }
+}
+
+/// Decode the image in the given buffer and rotate it according to its orientation tag.
+///
+/// # Examples
+/// ```
+/// let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+///                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+///                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+///                78, 68, 174, 66, 96, 130];
+/// let img = rexiv2::load_oriented_from_buffer(&minipng)?;
+/// assert_eq!((img.width(), img.height()), (1, 1));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn load_oriented_from_buffer(data: &[u8]) -> Result<DynamicImage> {
+    let orientation = Metadata::new_from_buffer(data)?.get_orientation();
+    let image = image::load_from_memory(data)
+        .map_err(|err| Rexiv2Error::Internal(Some(format!("Couldn't decode image: {err}"))))?;
+    Ok(apply_orientation(&image, orientation))
+}
+
+impl Metadata {
+    /// Decode the Exif thumbnail and rotate it according to `get_thumbnail_orientation`, so that
+    /// it's the right way up.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let meta = rexiv2::Metadata::new_from_path("myphoto.jpg")?;
+    /// let thumbnail = meta.get_thumbnail_oriented()?;
+    /// println!("Thumbnail is {}x{}", thumbnail.width(), thumbnail.height());
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_thumbnail_oriented(&self) -> Result<DynamicImage> {
+        let data = self.get_thumbnail().ok_or(Rexiv2Error::NoValue)?;
+        let image = image::load_from_memory(data).map_err(|err| {
+            Rexiv2Error::Internal(Some(format!("Couldn't decode thumbnail: {err}")))
+        })?;
+        Ok(apply_orientation(&image, self.get_thumbnail_orientation()))
+    }
+
+    /// Set or replace the Exif thumbnail with the image in a buffer, scaling it down,
+    /// converting it to JPEG, and compressing it as the options require.
+    ///
+    /// Images that are already JPEGs within the limits are stored as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let mut png = vec![];
+    /// image::DynamicImage::new_rgb8(640, 480)
+    ///     .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+    ///     .unwrap();
+    /// let jpeg = rexiv2::ThumbnailOptions::new().max_dimensions(64, 64);
+    /// meta.set_thumbnail_from_buffer_with(&png, &jpeg)?;
+    ///
+    /// let unconverted = rexiv2::ThumbnailOptions::new().convert_to_jpeg(false);
+    /// assert!(meta.set_thumbnail_from_buffer_with(&png, &unconverted).is_err());
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_thumbnail_from_buffer_with(
+        &self,
+        data: &[u8],
+        options: &ThumbnailOptions,
+    ) -> Result<()> {
+        let thumbnail = prepare_thumbnail(data, options)?;
+        self.set_thumbnail_from_buffer(&thumbnail);
+        Ok(())
+    }
+}
+
+/// Scale, convert, and compress an image for use as an Exif thumbnail.
+fn prepare_thumbnail(data: &[u8], options: &ThumbnailOptions) -> Result<Vec<u8>> {
+    let is_jpeg = data.starts_with(&[0xff, 0xd8, 0xff]);
+    if !is_jpeg && !options.convert_to_jpeg {
+        return Err(Rexiv2Error::InvalidValue(
+            "Thumbnails must be JPEG images".to_string(),
+        ));
+    }
+    let image = image::load_from_memory(data)
+        .map_err(|err| Rexiv2Error::Internal(Some(format!("Couldn't decode image: {err}"))))?;
+    let fits = image.width() <= options.max_width && image.height() <= options.max_height;
+    if is_jpeg && fits && data.len() <= options.max_bytes {
+        return Ok(data.to_vec());
+    }
+
+    let image = if fits {
+        image
+    } else {
+        image.thumbnail(options.max_width, options.max_height)
+    };
+    let pixels = image.to_rgb8();
+    let mut quality = options.jpeg_quality;
+    loop {
+        let mut encoded = vec![];
+        JpegEncoder::new_with_quality(&mut encoded, quality)
+            .encode(&pixels, pixels.width(), pixels.height(), ColorType::Rgb8)
+            .map_err(|err| {
+                Rexiv2Error::Internal(Some(format!("Couldn't encode thumbnail: {err}")))
+            })?;
+        if encoded.len() <= options.max_bytes {
+            return Ok(encoded);
+        }
+        if quality <= MIN_JPEG_QUALITY {
+            return Err(Rexiv2Error::Internal(Some(format!(
+                "Thumbnail takes up {} bytes even at the lowest quality, more than the {} allowed",
+                encoded.len(),
+                options.max_bytes
+            ))));
+        }
+        quality = quality.saturating_sub(10).max(MIN_JPEG_QUALITY);
+    }
+}
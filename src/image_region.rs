@@ -0,0 +1,262 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::iptc_xmp::{clear_struct_array, get_nested_bag, struct_array_len};
+use super::{strip_default_lang, tags, Metadata, Result};
+
+/// An area of an image, as described by the IPTC Extension `ImageRegion` structure.
+///
+/// Regions mark out the subjects of an image, or suggest how it could be cropped, along with what
+/// they contain and the role they play. When a region is written, its structure only gets
+/// members for the fields that are set.
+///
+/// # Examples
+/// ```
+/// use rexiv2::{ImageRegion, RegionBoundary, RegionShape, RegionUnit};
+/// let crop = ImageRegion {
+///     id: Some("square-crop".to_string()),
+///     boundary: Some(RegionBoundary {
+///         unit: RegionUnit::Relative,
+///         shape: RegionShape::Rectangle { x: 0.25, y: 0.0, width: 0.5, height: 1.0 },
+///     }),
+///     roles: vec![ImageRegion::ROLE_CROPPING.to_string()],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImageRegion {
+    /// An identifier for the region, unique within the image (`rId`).
+    pub id: Option<String>,
+    /// A name for the region (`Name`).
+    pub name: Option<String>,
+    /// The area covered by the region (`RegionBoundary`).
+    pub boundary: Option<RegionBoundary>,
+    /// URIs describing the roles of the region, such as `ImageRegion::ROLE_CROPPING` (`rRole`).
+    pub roles: Vec<String>,
+    /// URIs describing the content of the region, such as `ImageRegion::CONTENT_HUMAN`
+    /// (`rCtype`).
+    pub content_types: Vec<String>,
+}
+
+/// The area of an image covered by an `ImageRegion`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionBoundary {
+    /// The unit the coordinates of the shape are measured in.
+    pub unit: RegionUnit,
+    /// The shape of the area, with its coordinates measured from the top left corner.
+    pub shape: RegionShape,
+}
+
+/// The unit the coordinates of a `RegionBoundary` are measured in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RegionUnit {
+    /// Pixels of the full-size image.
+    Pixel,
+    /// Fractions of the width or height of the image, from 0 to 1.
+    Relative,
+}
+
+/// The shape of a `RegionBoundary`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RegionShape {
+    /// A rectangle with its top left corner at `x`, `y`.
+    Rectangle {
+        /// The horizontal position of the left edge.
+        x: f64,
+        /// The vertical position of the top edge.
+        y: f64,
+        /// The width of the rectangle.
+        width: f64,
+        /// The height of the rectangle.
+        height: f64,
+    },
+    /// A circle with its centre at `x`, `y`. A relative radius is a fraction of the image width.
+    Circle {
+        /// The horizontal position of the centre.
+        x: f64,
+        /// The vertical position of the centre.
+        y: f64,
+        /// The radius of the circle.
+        radius: f64,
+    },
+    /// A polygon, from its vertices as `(x, y)` pairs.
+    Polygon(Vec<(f64, f64)>),
+}
+
+impl ImageRegion {
+    /// The role of a region suggesting how the image could be cropped.
+    pub const ROLE_CROPPING: &'static str = "http://cv.iptc.org/newscodes/imageregionrole/cropping";
+    /// The role of a region showing the main subject of the image.
+    pub const ROLE_MAIN_SUBJECT: &'static str =
+        "http://cv.iptc.org/newscodes/imageregionrole/mainSubjectArea";
+    /// The role of a region that may be covered, e.g. by text, without hiding the subject.
+    pub const ROLE_AREA_TO_AVOID: &'static str =
+        "http://cv.iptc.org/newscodes/imageregionrole/areaToAvoid";
+    /// The content type of a region showing a person.
+    pub const CONTENT_HUMAN: &'static str = "http://cv.iptc.org/newscodes/imageregiontype/human";
+    /// The content type of a region showing a face.
+    pub const CONTENT_FACE: &'static str = "http://cv.iptc.org/newscodes/imageregiontype/face";
+
+    /// Read every region stored in `Xmp.iptcExt.ImageRegion`.
+    pub(crate) fn read_all(meta: &Metadata) -> Vec<ImageRegion> {
+        let array_tag = tags::xmp::IPTC_EXT_IMAGE_REGION;
+        (1..=struct_array_len(meta, array_tag))
+            .map(|index| {
+                let region_tag = format!("{array_tag}[{index}]");
+                let get = |field| {
+                    let tag = format!("{region_tag}/Iptc4xmpExt:{field}");
+                    meta.get_tag_string(&tag).ok()
+                };
+                ImageRegion {
+                    id: get("rId"),
+                    name: get("Name").map(strip_default_lang),
+                    boundary: RegionBoundary::read(meta, &region_tag),
+                    roles: read_entities(meta, &format!("{region_tag}/Iptc4xmpExt:rRole")),
+                    content_types: read_entities(meta, &format!("{region_tag}/Iptc4xmpExt:rCtype")),
+                }
+            })
+            .filter(|region| region != &ImageRegion::default())
+            .collect()
+    }
+
+    /// Replace the contents of `Xmp.iptcExt.ImageRegion` with the given regions.
+    pub(crate) fn write_all(meta: &Metadata, regions: &[ImageRegion]) -> Result<()> {
+        let array_tag = tags::xmp::IPTC_EXT_IMAGE_REGION;
+        clear_struct_array(meta, array_tag)?;
+        let regions = regions
+            .iter()
+            .filter(|region| *region != &ImageRegion::default());
+        for (index, region) in (1..).zip(regions) {
+            let region_tag = format!("{array_tag}[{index}]");
+            for (field, value) in [("rId", &region.id), ("Name", &region.name)] {
+                if let Some(value) = value {
+                    meta.set_tag_string(&format!("{region_tag}/Iptc4xmpExt:{field}"), value)?;
+                }
+            }
+            if let Some(boundary) = &region.boundary {
+                boundary.write(meta, &region_tag)?;
+            }
+            let roles_tag = format!("{region_tag}/Iptc4xmpExt:rRole");
+            write_entities(meta, &roles_tag, &region.roles)?;
+            let content_types_tag = format!("{region_tag}/Iptc4xmpExt:rCtype");
+            write_entities(meta, &content_types_tag, &region.content_types)?;
+        }
+        Ok(())
+    }
+}
+
+impl RegionBoundary {
+    /// Read the boundary of the region stored at the given tag, if it is present and valid.
+    fn read(meta: &Metadata, region_tag: &str) -> Option<RegionBoundary> {
+        let boundary_tag = format!("{region_tag}/Iptc4xmpExt:RegionBoundary");
+        let get = |tag: &str, field| {
+            meta.get_tag_string(&format!("{tag}/Iptc4xmpExt:{field}"))
+                .ok()
+        };
+        let get_number = |tag: &str, field| get(tag, field)?.trim().parse::<f64>().ok();
+        let unit = match get(&boundary_tag, "rbUnit")?.as_str() {
+            "pixel" => RegionUnit::Pixel,
+            "relative" => RegionUnit::Relative,
+            _ => return None,
+        };
+        let shape = match get(&boundary_tag, "rbShape")?.as_str() {
+            "rectangle" => RegionShape::Rectangle {
+                x: get_number(&boundary_tag, "rbX")?,
+                y: get_number(&boundary_tag, "rbY")?,
+                width: get_number(&boundary_tag, "rbW")?,
+                height: get_number(&boundary_tag, "rbH")?,
+            },
+            "circle" => RegionShape::Circle {
+                x: get_number(&boundary_tag, "rbX")?,
+                y: get_number(&boundary_tag, "rbY")?,
+                radius: get_number(&boundary_tag, "rbRx")?,
+            },
+            "polygon" => {
+                let vertices_tag = format!("{boundary_tag}/Iptc4xmpExt:rbVertices");
+                let vertices = (1..=struct_array_len(meta, &vertices_tag))
+                    .map(|index| {
+                        let vertex_tag = format!("{vertices_tag}[{index}]");
+                        Some((
+                            get_number(&vertex_tag, "rbX")?,
+                            get_number(&vertex_tag, "rbY")?,
+                        ))
+                    })
+                    .collect::<Option<_>>()?;
+                RegionShape::Polygon(vertices)
+            }
+            _ => return None,
+        };
+        Some(RegionBoundary { unit, shape })
+    }
+
+    /// Write the boundary into the region stored at the given tag.
+    fn write(&self, meta: &Metadata, region_tag: &str) -> Result<()> {
+        let boundary_tag = format!("{region_tag}/Iptc4xmpExt:RegionBoundary");
+        let set = |tag: &str, field, value: &str| {
+            meta.set_tag_string(&format!("{tag}/Iptc4xmpExt:{field}"), value)
+        };
+        let unit = match self.unit {
+            RegionUnit::Pixel => "pixel",
+            RegionUnit::Relative => "relative",
+        };
+        set(&boundary_tag, "rbUnit", unit)?;
+        match &self.shape {
+            RegionShape::Rectangle { x, y, width, height } => {
+                set(&boundary_tag, "rbShape", "rectangle")?;
+                set(&boundary_tag, "rbX", &x.to_string())?;
+                set(&boundary_tag, "rbY", &y.to_string())?;
+                set(&boundary_tag, "rbW", &width.to_string())?;
+                set(&boundary_tag, "rbH", &height.to_string())?;
+            }
+            RegionShape::Circle { x, y, radius } => {
+                set(&boundary_tag, "rbShape", "circle")?;
+                set(&boundary_tag, "rbX", &x.to_string())?;
+                set(&boundary_tag, "rbY", &y.to_string())?;
+                set(&boundary_tag, "rbRx", &radius.to_string())?;
+            }
+            RegionShape::Polygon(vertices) => {
+                set(&boundary_tag, "rbShape", "polygon")?;
+                for (index, (x, y)) in (1..).zip(vertices) {
+                    let vertex_tag = format!("{boundary_tag}/Iptc4xmpExt:rbVertices[{index}]");
+                    set(&vertex_tag, "rbX", &x.to_string())?;
+                    set(&vertex_tag, "rbY", &y.to_string())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read the identifiers of an array of IPTC `Entity` structures, as used for region roles and
+/// content types.
+fn read_entities(meta: &Metadata, array_tag: &str) -> Vec<String> {
+    (1..=struct_array_len(meta, array_tag))
+        .flat_map(|index| get_nested_bag(meta, &format!("{array_tag}[{index}]/xmp:Identifier")))
+        .collect()
+}
+
+/// Write an array of IPTC `Entity` structures, each with a single identifier.
+fn write_entities(meta: &Metadata, array_tag: &str, identifiers: &[String]) -> Result<()> {
+    for (index, identifier) in (1..).zip(identifiers) {
+        meta.set_tag_string(
+            &format!("{array_tag}[{index}]/xmp:Identifier[1]"),
+            identifier,
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,180 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{Metadata, Result, Rexiv2Error};
+use img_parts::jpeg::{markers, JpegSegment};
+use img_parts::png::PngChunk;
+use img_parts::riff::{RiffChunk, RiffContent};
+use img_parts::webp::{CHUNK_VP8X, CHUNK_XMP};
+use img_parts::{Bytes, DynImage, ImageEXIF};
+
+/// The header that identifies a JPEG APP1 segment as holding XMP.
+const JPEG_XMP_PREFIX: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+/// The keyword and (empty, uncompressed) header fields of a PNG iTXt chunk holding XMP.
+const PNG_XMP_PREFIX: &[u8] = b"XML:com.adobe.xmp\0\0\0\0\0";
+const PNG_CHUNK_ITXT: [u8; 4] = *b"iTXt";
+/// The bit of the WebP VP8X flags that indicates the presence of XMP.
+const WEBP_XMP_FLAG: u8 = 0b0000_0100;
+
+/// The raw Exif and XMP blocks of a JPEG, PNG, or WebP image, as found in its container.
+///
+/// Extracting or replacing these only touches the container structure and leaves the rest of the
+/// file as it is, which is much faster than having Exiv2 rewrite the whole image.
+///
+/// # Examples
+/// ```no_run
+/// use rexiv2::MetadataSegments;
+/// let original = img_parts::Bytes::from(std::fs::read("original.jpg")?);
+/// let segments = MetadataSegments::from_image(original)?;
+/// let resized = img_parts::Bytes::from(std::fs::read("resized.jpg")?);
+/// std::fs::write("resized.jpg", segments.apply_to_image(resized)?)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MetadataSegments {
+    /// The Exif data, as a TIFF structure (without any `Exif\0\0` header).
+    pub exif: Option<Bytes>,
+    /// The XMP packet.
+    pub xmp: Option<Bytes>,
+}
+
+impl MetadataSegments {
+    /// Extract the Exif and XMP blocks from a JPEG, PNG, or WebP image.
+    pub fn from_image(image: Bytes) -> Result<MetadataSegments> {
+        let image = parse_image(image)?;
+        let xmp = match &image {
+            DynImage::Jpeg(jpeg) => jpeg
+                .segments_by_marker(markers::APP1)
+                .find_map(|segment| strip_prefix(segment.contents(), JPEG_XMP_PREFIX)),
+            DynImage::Png(png) => png
+                .chunks_by_type(PNG_CHUNK_ITXT)
+                .find_map(|chunk| strip_prefix(chunk.contents(), PNG_XMP_PREFIX)),
+            DynImage::WebP(webp) => webp
+                .chunk_by_id(CHUNK_XMP)
+                .and_then(|chunk| chunk.content().data().cloned()),
+        };
+        Ok(MetadataSegments { exif: image.exif(), xmp })
+    }
+
+    /// Replace the Exif and XMP blocks of a JPEG, PNG, or WebP image with these ones, returning
+    /// the new image. Blocks that are `None` are removed from the image.
+    ///
+    /// Adding XMP to a WebP image is only possible if it already uses the extended file format.
+    pub fn apply_to_image(&self, image: Bytes) -> Result<Bytes> {
+        let mut image = parse_image(image)?;
+        image.set_exif(self.exif.clone());
+        match &mut image {
+            DynImage::Jpeg(jpeg) => {
+                let segments = jpeg.segments_mut();
+                segments.retain(|segment| {
+                    segment.marker() != markers::APP1
+                        || !segment.contents().starts_with(JPEG_XMP_PREFIX)
+                });
+                if let Some(xmp) = &self.xmp {
+                    let contents = [JPEG_XMP_PREFIX, xmp].concat();
+                    if contents.len() > u16::MAX as usize - 2 {
+                        return Err(Rexiv2Error::Internal(Some(
+                            "XMP packet is too large for a JPEG segment".to_string(),
+                        )));
+                    }
+                    // Keep the JFIF and Exif segments first, as readers expect.
+                    let pos = segments
+                        .iter()
+                        .position(|segment| {
+                            !matches!(segment.marker(), markers::APP0 | markers::APP1)
+                        })
+                        .unwrap_or(segments.len());
+                    let segment = JpegSegment::new_with_contents(markers::APP1, contents.into());
+                    segments.insert(pos, segment);
+                }
+            }
+            DynImage::Png(png) => {
+                let chunks = png.chunks_mut();
+                chunks.retain(|chunk| {
+                    chunk.kind() != PNG_CHUNK_ITXT || !chunk.contents().starts_with(PNG_XMP_PREFIX)
+                });
+                if let Some(xmp) = &self.xmp {
+                    let contents = [PNG_XMP_PREFIX, xmp].concat();
+                    // Keep the IHDR chunk first, as required.
+                    chunks.insert(
+                        1.min(chunks.len()),
+                        PngChunk::new(PNG_CHUNK_ITXT, contents.into()),
+                    );
+                }
+            }
+            DynImage::WebP(webp) => {
+                webp.remove_chunks_by_id(CHUNK_XMP);
+                let chunks = webp.chunks_mut();
+                let vp8x = chunks.iter_mut().find(|chunk| chunk.id() == CHUNK_VP8X);
+                match (vp8x, &self.xmp) {
+                    (Some(vp8x), xmp) => {
+                        let mut flags = vp8x
+                            .content()
+                            .data()
+                            .map(|data| data.to_vec())
+                            .unwrap_or_default();
+                        if let Some(first) = flags.first_mut() {
+                            if xmp.is_some() {
+                                *first |= WEBP_XMP_FLAG;
+                            } else {
+                                *first &= !WEBP_XMP_FLAG;
+                            }
+                        }
+                        *vp8x.content_mut() = RiffContent::Data(flags.into());
+                    }
+                    (None, Some(_)) => {
+                        return Err(Rexiv2Error::Internal(Some(
+                            "Adding XMP requires a WebP image in the extended format".to_string(),
+                        )))
+                    }
+                    (None, None) => {}
+                }
+                if let Some(xmp) = &self.xmp {
+                    chunks.push(RiffChunk::new(CHUNK_XMP, RiffContent::Data(xmp.clone())));
+                }
+            }
+        }
+        Ok(image.encoder().bytes())
+    }
+
+    /// Load the Exif block into a new `Metadata`.
+    pub fn exif_metadata(&self) -> Result<Metadata> {
+        let exif = self.exif.as_ref().ok_or(Rexiv2Error::NoValue)?;
+        Metadata::new_from_app1_segment(&[b"Exif\0\0", &exif[..]].concat())
+    }
+
+    /// Load the XMP packet into a new `Metadata`.
+    pub fn xmp_metadata(&self) -> Result<Metadata> {
+        Metadata::new_from_buffer(self.xmp.as_ref().ok_or(Rexiv2Error::NoValue)?)
+    }
+}
+
+/// Work out which kind of container an image uses, and parse its structure.
+fn parse_image(image: Bytes) -> Result<DynImage> {
+    DynImage::from_bytes(image)
+        .map_err(|err| Rexiv2Error::Internal(Some(err.to_string())))?
+        .ok_or_else(|| Rexiv2Error::Internal(Some("Not a JPEG, PNG, or WebP image".to_string())))
+}
+
+fn strip_prefix(data: &Bytes, prefix: &[u8]) -> Option<Bytes> {
+    if data.starts_with(prefix) {
+        Some(data.slice(prefix.len()..))
+    } else {
+        None
+    }
+}
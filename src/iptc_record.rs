@@ -0,0 +1,272 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::iptc_xmp::struct_array_len;
+use super::{tags, Metadata, Result};
+
+/// The common editorial fields of the IPTC application record, as used by news organisations.
+///
+/// Each field corresponds to a single dataset, which is only present when the field is `Some`.
+///
+/// # Examples
+/// ```
+/// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+/// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+/// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+/// #                78, 68, 174, 66, 96, 130];
+/// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+/// let record = rexiv2::IptcApplicationRecord {
+///     headline: Some("Storm hits coast".to_string()),
+///     city: Some("Halifax".to_string()),
+///     urgency: Some(2),
+///     ..Default::default()
+/// };
+/// record.write_to(&meta)?;
+/// assert_eq!(meta.get_tag_string("Iptc.Application2.City")?, "Halifax");
+/// assert_eq!(rexiv2::IptcApplicationRecord::read_from(&meta), record);
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IptcApplicationRecord {
+    /// A publishable synopsis of the content (`Iptc.Application2.Headline`).
+    pub headline: Option<String>,
+    /// A textual description of the content (`Iptc.Application2.Caption`).
+    pub caption: Option<String>,
+    /// The provider of the content (`Iptc.Application2.Credit`).
+    pub credit: Option<String>,
+    /// The original owner of the content (`Iptc.Application2.Source`).
+    pub source: Option<String>,
+    /// The city where the content originates (`Iptc.Application2.City`).
+    pub city: Option<String>,
+    /// The province or state where the content originates (`Iptc.Application2.ProvinceState`).
+    pub province_state: Option<String>,
+    /// The country where the content originates (`Iptc.Application2.CountryName`).
+    pub country: Option<String>,
+    /// The subject category of the content (`Iptc.Application2.Category`).
+    pub category: Option<String>,
+    /// The editorial urgency, from 1 (most urgent) to 8 (least urgent)
+    /// (`Iptc.Application2.Urgency`).
+    pub urgency: Option<u8>,
+}
+
+impl IptcApplicationRecord {
+    /// Read the fields from the given metadata, decoding text as described for
+    /// `Metadata::get_iptc_tag_string()`. Fields that are missing or can't be decoded are `None`.
+    pub fn read_from(meta: &Metadata) -> IptcApplicationRecord {
+        let get = |tag| meta.get_iptc_tag_string(tag).ok();
+        IptcApplicationRecord {
+            headline: get(tags::iptc::HEADLINE),
+            caption: get(tags::iptc::CAPTION),
+            credit: get(tags::iptc::CREDIT),
+            source: get(tags::iptc::SOURCE),
+            city: get(tags::iptc::CITY),
+            province_state: get(tags::iptc::PROVINCE_STATE),
+            country: get(tags::iptc::COUNTRY_NAME),
+            category: get(tags::iptc::CATEGORY),
+            urgency: get(tags::iptc::URGENCY).and_then(|urgency| urgency.trim().parse().ok()),
+        }
+    }
+
+    /// Write the fields to their datasets, removing the datasets of any that are `None`.
+    pub fn write_to(&self, meta: &Metadata) -> Result<()> {
+        let urgency = self.urgency.map(|urgency| urgency.to_string());
+        meta.set_optional_strings(&[
+            (tags::iptc::HEADLINE, &self.headline),
+            (tags::iptc::CAPTION, &self.caption),
+            (tags::iptc::CREDIT, &self.credit),
+            (tags::iptc::SOURCE, &self.source),
+            (tags::iptc::CITY, &self.city),
+            (tags::iptc::PROVINCE_STATE, &self.province_state),
+            (tags::iptc::COUNTRY_NAME, &self.country),
+            (tags::iptc::CATEGORY, &self.category),
+            (tags::iptc::URGENCY, &urgency),
+        ])
+    }
+}
+
+/// The fields used to track the job or assignment that content was produced for, as used by wire
+/// services.
+///
+/// Where both exist, the IPTC dataset is read in preference to its XMP equivalent, and both are
+/// written, or both removed for a field that is `None`.
+///
+/// # Examples
+/// ```
+/// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+/// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+/// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+/// #                78, 68, 174, 66, 96, 130];
+/// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+/// let job = rexiv2::JobInfo {
+///     job_id: Some("NYC-2024-0412".to_string()),
+///     instructions: Some("Embargoed until 18:00 UTC".to_string()),
+///     ..Default::default()
+/// };
+/// job.write_to(&meta)?;
+/// assert_eq!(meta.get_tag_string("Xmp.photoshop.TransmissionReference")?, "NYC-2024-0412");
+/// assert_eq!(rexiv2::JobInfo::read_from(&meta), job);
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct JobInfo {
+    /// An identifier for the job or assignment (`Iptc.Application2.TransmissionReference` and
+    /// `Xmp.photoshop.TransmissionReference`).
+    pub job_id: Option<String>,
+    /// An identifier for a recurring feature the content belongs to, such as a daily column
+    /// (`Iptc.Application2.FixtureId`). There is no XMP equivalent.
+    pub fixture_id: Option<String>,
+    /// Instructions on how the content may be used, such as embargoes
+    /// (`Iptc.Application2.SpecialInstructions` and `Xmp.photoshop.Instructions`).
+    pub instructions: Option<String>,
+}
+
+impl JobInfo {
+    /// Read the fields from the given metadata, decoding IPTC text as described for
+    /// `Metadata::get_iptc_tag_string()`. Fields that are missing or can't be decoded are `None`.
+    pub fn read_from(meta: &Metadata) -> JobInfo {
+        let get = |iptc_tag, xmp_tag: Option<&str>| {
+            meta.get_iptc_tag_string(iptc_tag)
+                .ok()
+                .or_else(|| meta.get_tag_string(xmp_tag?).ok())
+        };
+        JobInfo {
+            job_id: get(
+                tags::iptc::TRANSMISSION_REFERENCE,
+                Some(tags::xmp::PHOTOSHOP_TRANSMISSION_REFERENCE),
+            ),
+            fixture_id: get(tags::iptc::FIXTURE_ID, None),
+            instructions: get(
+                tags::iptc::SPECIAL_INSTRUCTIONS,
+                Some(tags::xmp::PHOTOSHOP_INSTRUCTIONS),
+            ),
+        }
+    }
+
+    /// Write the job details to the IPTC datasets and their XMP equivalents, or clear them for
+    /// fields that are `None`.
+    pub fn write_to(&self, meta: &Metadata) -> Result<()> {
+        meta.set_optional_strings(&[
+            (tags::iptc::TRANSMISSION_REFERENCE, &self.job_id),
+            (tags::xmp::PHOTOSHOP_TRANSMISSION_REFERENCE, &self.job_id),
+            (tags::iptc::FIXTURE_ID, &self.fixture_id),
+            (tags::iptc::SPECIAL_INSTRUCTIONS, &self.instructions),
+            (tags::xmp::PHOTOSHOP_INSTRUCTIONS, &self.instructions),
+        ])
+    }
+}
+
+/// The fields used to credit an image, such as in a visible credit line or watermark.
+///
+/// Where both exist, the IPTC dataset is read in preference to its XMP equivalent, and both are
+/// written. A field left as `None` means none of its tags are present.
+///
+/// # Examples
+/// ```
+/// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+/// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+/// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+/// #                78, 68, 174, 66, 96, 130];
+/// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+/// let credit = rexiv2::CreditInfo {
+///     credit: Some("Jane Doe / Example Press".to_string()),
+///     web_statement: Some("https://example.com/licensing".to_string()),
+///     ..Default::default()
+/// };
+/// credit.write_to(&meta)?;
+/// assert_eq!(meta.get_tag_string("Xmp.photoshop.Credit")?, "Jane Doe / Example Press");
+/// assert_eq!(rexiv2::CreditInfo::read_from(&meta), credit);
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CreditInfo {
+    /// How the image should be credited when published (`Iptc.Application2.Credit` and
+    /// `Xmp.photoshop.Credit`).
+    pub credit: Option<String>,
+    /// The original owner of the image (`Iptc.Application2.Source` and `Xmp.photoshop.Source`).
+    pub source: Option<String>,
+    /// The name of the agency or other party supplying the image
+    /// (`Xmp.plus.ImageSupplier[1]/plus:ImageSupplierName`).
+    pub provider: Option<String>,
+    /// A web page describing the rights to the image (`Xmp.xmpRights.WebStatement`).
+    pub web_statement: Option<String>,
+}
+
+impl CreditInfo {
+    /// Read the fields from the given metadata, decoding IPTC text as described for
+    /// `Metadata::get_iptc_tag_string()`. Fields that are missing or can't be decoded are `None`.
+    pub fn read_from(meta: &Metadata) -> CreditInfo {
+        let get = |tag| meta.get_tag_string(tag).ok();
+        let get_iptc = |iptc_tag, xmp_tag| {
+            meta.get_iptc_tag_string(iptc_tag)
+                .ok()
+                .or_else(|| get(xmp_tag))
+        };
+        CreditInfo {
+            credit: get_iptc(tags::iptc::CREDIT, tags::xmp::PHOTOSHOP_CREDIT),
+            source: get_iptc(tags::iptc::SOURCE, tags::xmp::PHOTOSHOP_SOURCE),
+            provider: get(&supplier_name_tag()),
+            web_statement: get(tags::xmp::XMP_RIGHTS_WEB_STATEMENT),
+        }
+    }
+
+    /// Write the credit to the metadata, clearing the tags of any field that is `None`. Other
+    /// details of the image supplier, such as its ID, are left as they are.
+    pub fn write_to(&self, meta: &Metadata) -> Result<()> {
+        let supplier_name = supplier_name_tag();
+        meta.set_optional_strings(&[
+            (tags::iptc::CREDIT, &self.credit),
+            (tags::xmp::PHOTOSHOP_CREDIT, &self.credit),
+            (tags::iptc::SOURCE, &self.source),
+            (tags::xmp::PHOTOSHOP_SOURCE, &self.source),
+            (tags::xmp::XMP_RIGHTS_WEB_STATEMENT, &self.web_statement),
+            (&supplier_name, &self.provider),
+        ])?;
+        // Other fields of the supplier, such as its ID, are kept, but don't leave an empty array.
+        if struct_array_len(meta, tags::xmp::PLUS_IMAGE_SUPPLIER) == 0 {
+            meta.clear_tag(tags::xmp::PLUS_IMAGE_SUPPLIER);
+        }
+        Ok(())
+    }
+
+    /// A credit line to show alongside the image: the credit, or failing that the provider or the
+    /// source.
+    ///
+    /// # Examples
+    /// ```
+    /// let credit = rexiv2::CreditInfo {
+    ///     source: Some("Example Press".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(credit.credit_line(), Some("Example Press"));
+    /// ```
+    pub fn credit_line(&self) -> Option<&str> {
+        [&self.credit, &self.provider, &self.source]
+            .into_iter()
+            .flatten()
+            .map(|value| value.trim())
+            .find(|value| !value.is_empty())
+    }
+}
+
+/// The name of the tag holding the name of the first image supplier.
+fn supplier_name_tag() -> String {
+    format!(
+        "{}[1]/plus:ImageSupplierName",
+        tags::xmp::PLUS_IMAGE_SUPPLIER
+    )
+}
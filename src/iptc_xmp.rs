@@ -0,0 +1,294 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{strip_default_lang, tags, Metadata, Result};
+
+/// The contact details of the person who created the content, from the IPTC Core
+/// `CreatorContactInfo` XMP structure.
+///
+/// Each field is stored as a member of the structure, e.g. the city is found in the tag
+/// `Xmp.iptc.CreatorContactInfo/Iptc4xmpCore:CiAdrCity`, which only exists if the city is
+/// known.
+///
+/// # Examples
+/// ```
+/// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+/// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+/// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+/// #                78, 68, 174, 66, 96, 130];
+/// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+/// let contact = rexiv2::CreatorContactInfo {
+///     city: Some("Halifax".to_string()),
+///     email: Some("desk@example.com".to_string()),
+///     ..Default::default()
+/// };
+/// contact.write_to(&meta)?;
+/// assert_eq!(
+///     meta.get_tag_string("Xmp.iptc.CreatorContactInfo/Iptc4xmpCore:CiAdrCity")?,
+///     "Halifax",
+/// );
+/// assert_eq!(rexiv2::CreatorContactInfo::read_from(&meta), contact);
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CreatorContactInfo {
+    /// The street address, possibly spanning several lines (`CiAdrExtadr`).
+    pub address: Option<String>,
+    /// The city (`CiAdrCity`).
+    pub city: Option<String>,
+    /// The state, province, or other region (`CiAdrRegion`).
+    pub region: Option<String>,
+    /// The postal code (`CiAdrPcode`).
+    pub postal_code: Option<String>,
+    /// The country (`CiAdrCtry`).
+    pub country: Option<String>,
+    /// One or more work email addresses, separated by commas (`CiEmailWork`).
+    pub email: Option<String>,
+    /// One or more work phone numbers, separated by commas (`CiTelWork`).
+    pub phone: Option<String>,
+    /// One or more work web addresses, separated by commas (`CiUrlWork`).
+    pub website: Option<String>,
+}
+
+impl CreatorContactInfo {
+    /// Read the fields from the given metadata. Fields that are missing are `None`.
+    pub fn read_from(meta: &Metadata) -> CreatorContactInfo {
+        let get = |field| meta.get_tag_string(&creator_contact_tag(field)).ok();
+        CreatorContactInfo {
+            address: get("CiAdrExtadr"),
+            city: get("CiAdrCity"),
+            region: get("CiAdrRegion"),
+            postal_code: get("CiAdrPcode"),
+            country: get("CiAdrCtry"),
+            email: get("CiEmailWork"),
+            phone: get("CiTelWork"),
+            website: get("CiUrlWork"),
+        }
+    }
+
+    /// Write the contact details into the `CreatorContactInfo` structure, removing the member of
+    /// any field that is `None`.
+    pub fn write_to(&self, meta: &Metadata) -> Result<()> {
+        meta.set_optional_strings(
+            &[
+                ("CiAdrExtadr", &self.address),
+                ("CiAdrCity", &self.city),
+                ("CiAdrRegion", &self.region),
+                ("CiAdrPcode", &self.postal_code),
+                ("CiAdrCtry", &self.country),
+                ("CiEmailWork", &self.email),
+                ("CiTelWork", &self.phone),
+                ("CiUrlWork", &self.website),
+            ]
+            .map(|(field, value)| (creator_contact_tag(field), value)),
+        )
+    }
+}
+
+/// The name of the tag holding a member of the `CreatorContactInfo` structure.
+fn creator_contact_tag(field: &str) -> String {
+    format!(
+        "{}/Iptc4xmpCore:{field}",
+        tags::xmp::IPTC_CREATOR_CONTACT_INFO
+    )
+}
+
+/// A location, as described by the IPTC Extension `Location` structure.
+///
+/// It's used for both the place the content was created (see `Metadata::get_locations_created()`)
+/// and the places it shows (see `Metadata::get_locations_shown()`). A location's structure only
+/// holds members for the fields that are set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IptcLocation {
+    /// The name of a sublocation within a city, such as a district or landmark (`Sublocation`).
+    pub sublocation: Option<String>,
+    /// The city (`City`).
+    pub city: Option<String>,
+    /// The province or state (`ProvinceState`).
+    pub province_state: Option<String>,
+    /// The full name of the country (`CountryName`).
+    pub country_name: Option<String>,
+    /// The ISO 3166 code of the country (`CountryCode`).
+    pub country_code: Option<String>,
+    /// The name of the world region, such as a continent (`WorldRegion`).
+    pub world_region: Option<String>,
+}
+
+impl IptcLocation {
+    const FIELDS: [&'static str; 6] = [
+        "Sublocation",
+        "City",
+        "ProvinceState",
+        "CountryName",
+        "CountryCode",
+        "WorldRegion",
+    ];
+
+    fn fields(&self) -> [&Option<String>; 6] {
+        [
+            &self.sublocation,
+            &self.city,
+            &self.province_state,
+            &self.country_name,
+            &self.country_code,
+            &self.world_region,
+        ]
+    }
+
+    /// Read every location stored in the given array of structures.
+    pub(crate) fn read_array(meta: &Metadata, array_tag: &str) -> Vec<IptcLocation> {
+        (1..=struct_array_len(meta, array_tag))
+            .map(|index| {
+                let [sublocation, city, province_state, country_name, country_code, world_region] =
+                    IptcLocation::FIELDS.map(|field| {
+                        let tag = format!("{array_tag}[{index}]/Iptc4xmpExt:{field}");
+                        meta.get_tag_string(&tag).ok()
+                    });
+                IptcLocation {
+                    sublocation,
+                    city,
+                    province_state,
+                    country_name,
+                    country_code,
+                    world_region,
+                }
+            })
+            .filter(|location| location != &IptcLocation::default())
+            .collect()
+    }
+
+    /// Replace the given array of structures with the given locations.
+    pub(crate) fn write_array(
+        meta: &Metadata,
+        array_tag: &str,
+        locations: &[IptcLocation],
+    ) -> Result<()> {
+        clear_struct_array(meta, array_tag)?;
+        let locations = locations
+            .iter()
+            .filter(|location| *location != &IptcLocation::default());
+        for (index, location) in (1..).zip(locations) {
+            for (field, value) in IptcLocation::FIELDS.iter().zip(location.fields()) {
+                if let Some(value) = value {
+                    let tag = format!("{array_tag}[{index}]/Iptc4xmpExt:{field}");
+                    meta.set_tag_string(&tag, value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A person shown in the content, as described by the IPTC Extension `PersonInImageWDetails`
+/// structure.
+///
+/// A name or description of `None`, or an empty list of identifiers, leaves that member out of
+/// the person's structure.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PersonShown {
+    /// The name of the person (`PersonName`).
+    pub name: Option<String>,
+    /// URIs identifying the person, such as entries in a knowledge base (`PersonId`).
+    pub identifiers: Vec<String>,
+    /// A description of the person, such as their role in the content (`PersonDescription`).
+    pub description: Option<String>,
+}
+
+impl PersonShown {
+    /// Read every person stored in `Xmp.iptcExt.PersonInImageWDetails`.
+    pub(crate) fn read_all(meta: &Metadata) -> Vec<PersonShown> {
+        let array_tag = tags::xmp::IPTC_EXT_PERSON_IN_IMAGE_DETAILS;
+        (1..=struct_array_len(meta, array_tag))
+            .map(|index| {
+                let field = |name| format!("{array_tag}[{index}]/Iptc4xmpExt:{name}");
+                // The name and description are language alternatives, but are also accepted as
+                // plain text.
+                let get = |name| {
+                    meta.get_tag_string(&field(name))
+                        .ok()
+                        .map(strip_default_lang)
+                };
+                PersonShown {
+                    name: get("PersonName"),
+                    identifiers: get_nested_bag(meta, &field("PersonId")),
+                    description: get("PersonDescription"),
+                }
+            })
+            .filter(|person| person != &PersonShown::default())
+            .collect()
+    }
+
+    /// Replace the contents of `Xmp.iptcExt.PersonInImageWDetails` with the given people.
+    pub(crate) fn write_all(meta: &Metadata, people: &[PersonShown]) -> Result<()> {
+        let array_tag = tags::xmp::IPTC_EXT_PERSON_IN_IMAGE_DETAILS;
+        clear_struct_array(meta, array_tag)?;
+        let people = people
+            .iter()
+            .filter(|person| *person != &PersonShown::default());
+        for (index, person) in (1..).zip(people) {
+            let field = |name| format!("{array_tag}[{index}]/Iptc4xmpExt:{name}");
+            if let Some(name) = &person.name {
+                meta.set_tag_string(&field("PersonName"), name)?;
+            }
+            for (id_index, identifier) in (1..).zip(&person.identifiers) {
+                let tag = format!("{}[{id_index}]", field("PersonId"));
+                meta.set_tag_string(&tag, identifier)?;
+            }
+            if let Some(description) = &person.description {
+                meta.set_tag_string(&field("PersonDescription"), description)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read the items of an XMP array nested inside a structure.
+///
+/// Exiv2 holds such an array as a single tag when it is read from a file, but as one tag per item
+/// when the items were set individually, so both forms are handled.
+pub(crate) fn get_nested_bag(meta: &Metadata, tag: &str) -> Vec<String> {
+    match struct_array_len(meta, tag) {
+        0 => meta.get_tag_multiple_strings(tag).unwrap_or_default(),
+        len => (1..=len)
+            .filter_map(|index| meta.get_tag_string(&format!("{tag}[{index}]")).ok())
+            .collect(),
+    }
+}
+
+/// Count the items of an XMP array of structures, from the highest index used by its fields.
+pub(crate) fn struct_array_len(meta: &Metadata, array_tag: &str) -> usize {
+    let prefix = format!("{array_tag}[");
+    meta.get_xmp_tags()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|tag| tag.strip_prefix(&prefix)?.split(']').next()?.parse().ok())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Remove an XMP array of structures, along with all the fields of its items.
+pub(crate) fn clear_struct_array(meta: &Metadata, array_tag: &str) -> Result<()> {
+    let prefix = format!("{array_tag}[");
+    for tag in meta.get_xmp_tags()? {
+        if tag.starts_with(&prefix) {
+            meta.clear_tag(&tag);
+        }
+    }
+    meta.clear_tag(array_tag);
+    Ok(())
+}
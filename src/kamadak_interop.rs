@@ -0,0 +1,201 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{get_tag_type, AsTagKey, Metadata, Result, Rexiv2Error, TagType};
+
+/// Get the name Exiv2 uses for a `kamadak-exif` tag stored in the given IFD.
+///
+/// The name uses the tag's number rather than its label (e.g. `Exif.Photo.0x829a`), which Exiv2
+/// accepts everywhere a tag name is expected. Returns `None` for tags in contexts that Exiv2 has
+/// no equivalent for.
+///
+/// # Examples
+/// ```
+/// assert_eq!(
+///     rexiv2::exif_tag_name(exif::Tag::ExposureTime, exif::In::PRIMARY),
+///     Some("Exif.Photo.0x829a".to_string())
+/// );
+/// ```
+pub fn exif_tag_name(tag: exif::Tag, ifd: exif::In) -> Option<String> {
+    let group = match (tag.context(), ifd) {
+        (exif::Context::Tiff, exif::In::PRIMARY) => "Image",
+        (exif::Context::Tiff, exif::In::THUMBNAIL) => "Thumbnail",
+        (exif::Context::Exif, _) => "Photo",
+        (exif::Context::Gps, _) => "GPSInfo",
+        (exif::Context::Interop, _) => "Iop",
+        _ => return None,
+    };
+    Some(format!("Exif.{group}.0x{:04x}", tag.number()))
+}
+
+impl Metadata {
+    /// Get the value of an Exif tag as a `kamadak-exif` value, based on the tag's type.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Photo.ExposureTime", "1/250")?;
+    /// assert_eq!(
+    ///     meta.get_tag_exif_value("Exif.Photo.ExposureTime")?,
+    ///     exif::Value::Rational(vec![exif::Rational { num: 1, denom: 250 }])
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_tag_exif_value<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<exif::Value> {
+        let tag_type = get_tag_type(tag)?;
+        let value = self.get_tag_string(tag)?;
+        Ok(match tag_type {
+            TagType::UnsignedByte => exif::Value::Byte(parse_list(&value)?),
+            TagType::AsciiString => exif::Value::Ascii(vec![value.into_bytes()]),
+            TagType::UnsignedShort => exif::Value::Short(parse_list(&value)?),
+            TagType::UnsignedLong | TagType::TiffIfd => exif::Value::Long(parse_list(&value)?),
+            TagType::UnsignedRational => exif::Value::Rational(
+                parse_rationals(&value)?
+                    .into_iter()
+                    .map(|(num, denom)| exif::Rational { num, denom })
+                    .collect(),
+            ),
+            TagType::SignedByte => exif::Value::SByte(parse_list(&value)?),
+            TagType::Undefined => exif::Value::Undefined(parse_list(&value)?, 0),
+            TagType::SignedShort => exif::Value::SShort(parse_list(&value)?),
+            TagType::SignedLong => exif::Value::SLong(parse_list(&value)?),
+            TagType::SignedRational => exif::Value::SRational(
+                parse_rationals(&value)?
+                    .into_iter()
+                    .map(|(num, denom)| exif::SRational { num, denom })
+                    .collect(),
+            ),
+            TagType::TiffFloat => exif::Value::Float(parse_list(&value)?),
+            TagType::TiffDouble => exif::Value::Double(parse_list(&value)?),
+            other => {
+                return Err(Rexiv2Error::Internal(Some(format!(
+                    "Tag type {other:?} has no kamadak-exif equivalent"
+                ))))
+            }
+        })
+    }
+
+    /// Set the value of an Exif tag from a `kamadak-exif` value.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_exif_value("Exif.Photo.ISOSpeedRatings", &exif::Value::Short(vec![400]))?;
+    /// assert_eq!(meta.get_tag_numeric("Exif.Photo.ISOSpeedRatings"), 400);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_tag_exif_value<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        value: &exif::Value,
+    ) -> Result<()> {
+        self.set_tag_string(tag, &value_to_string(value)?)
+    }
+
+    /// Copy a field read by `kamadak-exif` into this metadata.
+    pub fn set_exif_field(&self, field: &exif::Field) -> Result<()> {
+        let name = exif_tag_name(field.tag, field.ifd_num).ok_or_else(|| {
+            Rexiv2Error::Internal(Some(format!("No Exiv2 equivalent for tag {}", field.tag)))
+        })?;
+        self.set_tag_exif_value(&name, &field.value)
+    }
+}
+
+/// Format a `kamadak-exif` value the way Exiv2 parses values from strings.
+fn value_to_string(value: &exif::Value) -> Result<String> {
+    fn join<T: ToString>(values: &[T]) -> String {
+        values
+            .iter()
+            .map(T::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    Ok(match value {
+        exif::Value::Byte(values) | exif::Value::Undefined(values, _) => join(values),
+        exif::Value::Ascii(strings) => match strings.as_slice() {
+            [string] => std::str::from_utf8(string)?.to_string(),
+            _ => {
+                return Err(Rexiv2Error::InvalidValue(
+                    "Only single ASCII strings are supported".to_string(),
+                ))
+            }
+        },
+        exif::Value::Short(values) => join(values),
+        exif::Value::Long(values) => join(values),
+        exif::Value::Rational(values) => {
+            let values: Vec<_> = values
+                .iter()
+                .map(|r| format!("{}/{}", r.num, r.denom))
+                .collect();
+            values.join(" ")
+        }
+        exif::Value::SByte(values) => join(values),
+        exif::Value::SShort(values) => join(values),
+        exif::Value::SLong(values) => join(values),
+        exif::Value::SRational(values) => {
+            let values: Vec<_> = values
+                .iter()
+                .map(|r| format!("{}/{}", r.num, r.denom))
+                .collect();
+            values.join(" ")
+        }
+        exif::Value::Float(values) => join(values),
+        exif::Value::Double(values) => join(values),
+        exif::Value::Unknown(..) => {
+            return Err(Rexiv2Error::InvalidValue(
+                "Unknown values can't be written".to_string(),
+            ))
+        }
+    })
+}
+
+/// Parse a space-separated list of numbers, as produced by Exiv2.
+fn parse_list<T: std::str::FromStr>(value: &str) -> Result<Vec<T>> {
+    value
+        .split_whitespace()
+        .map(|item| {
+            item.parse()
+                .map_err(|_| Rexiv2Error::InvalidValue(format!("Invalid number: {item:?}")))
+        })
+        .collect()
+}
+
+/// Parse a space-separated list of `numerator/denominator` pairs, as produced by Exiv2.
+fn parse_rationals<T: std::str::FromStr>(value: &str) -> Result<Vec<(T, T)>> {
+    value
+        .split_whitespace()
+        .map(|item| {
+            let (num, denom) = item.split_once('/').unwrap_or((item, "1"));
+            match (num.parse(), denom.parse()) {
+                (Ok(num), Ok(denom)) => Ok((num, denom)),
+                _ => Err(Rexiv2Error::InvalidValue(format!(
+                    "Invalid rational: {item:?}"
+                ))),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,160 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{Result, Rexiv2Error};
+
+/// The character separating the levels of a hierarchical keyword, as used by Adobe Lightroom.
+const SEPARATOR: char = '|';
+
+/// A keyword placed within a taxonomy, from its broadest to its most specific level, such as
+/// `Animals|Birds|Owl`.
+///
+/// These are stored in `Xmp.lr.hierarchicalSubject` by Adobe Lightroom and other tools, while
+/// `Xmp.dc.subject` holds the flat list of individual keywords.
+///
+/// # Examples
+/// ```
+/// use rexiv2::HierarchicalKeyword;
+/// let owl: HierarchicalKeyword = "Animals|Birds|Owl".parse()?;
+/// assert_eq!(owl.levels(), ["Animals", "Birds", "Owl"]);
+/// assert_eq!(owl.leaf(), "Owl");
+/// assert_eq!(owl.to_string(), "Animals|Birds|Owl");
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HierarchicalKeyword {
+    levels: Vec<String>,
+}
+
+impl HierarchicalKeyword {
+    /// Create a hierarchical keyword from its levels, broadest first.
+    ///
+    /// Returns an error if there are no levels, or if any of them is empty or contains the `|`
+    /// separator.
+    pub fn new<S: AsRef<str>>(levels: &[S]) -> Result<HierarchicalKeyword> {
+        if levels.is_empty() {
+            return Err(Rexiv2Error::InvalidValue(
+                "A hierarchical keyword needs at least one level".to_string(),
+            ));
+        }
+        let levels: Vec<String> = levels
+            .iter()
+            .map(|level| level.as_ref().to_string())
+            .collect();
+        if let Some(level) = levels
+            .iter()
+            .find(|level| level.is_empty() || level.contains(SEPARATOR))
+        {
+            return Err(Rexiv2Error::InvalidValue(format!(
+                "Invalid hierarchical keyword level: {level:?}"
+            )));
+        }
+        Ok(HierarchicalKeyword { levels })
+    }
+
+    /// The levels of the keyword, broadest first.
+    pub fn levels(&self) -> &[String] {
+        &self.levels
+    }
+
+    /// The most specific level of the keyword.
+    pub fn leaf(&self) -> &str {
+        // There is always at least one level, as checked on creation.
+        &self.levels[self.levels.len() - 1]
+    }
+
+    /// Convert hierarchical keywords into a flat list of keywords, as stored in `Xmp.dc.subject`.
+    ///
+    /// Every level of every keyword is included once, in order of first appearance, matching what
+    /// Adobe Lightroom exports.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::HierarchicalKeyword;
+    /// let keywords = [
+    ///     "Animals|Birds|Owl".parse::<HierarchicalKeyword>()?,
+    ///     "Animals|Mammals|Fox".parse()?,
+    /// ];
+    /// assert_eq!(
+    ///     HierarchicalKeyword::flatten(&keywords),
+    ///     ["Animals", "Birds", "Owl", "Mammals", "Fox"],
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn flatten(keywords: &[HierarchicalKeyword]) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        keywords
+            .iter()
+            .flat_map(|keyword| keyword.levels.iter())
+            .filter(|level| seen.insert(level.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    /// Combine a flat list of keywords, as stored in `Xmp.dc.subject`, with the hierarchical
+    /// keywords describing some of them.
+    ///
+    /// Flat keywords that don't appear at any level of the hierarchy are added as top-level
+    /// keywords, so no keyword is lost.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::HierarchicalKeyword;
+    /// let hierarchy = ["Animals|Birds|Owl".parse::<HierarchicalKeyword>()?];
+    /// let flat = ["Owl".to_string(), "Night".to_string()];
+    /// let keywords = HierarchicalKeyword::from_flat(&flat, &hierarchy);
+    /// assert_eq!(keywords, [hierarchy[0].clone(), "Night".parse()?]);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn from_flat<S: AsRef<str>>(
+        flat: &[S],
+        hierarchy: &[HierarchicalKeyword],
+    ) -> Vec<HierarchicalKeyword> {
+        let known: std::collections::HashSet<&str> = hierarchy
+            .iter()
+            .flat_map(|keyword| keyword.levels.iter().map(String::as_str))
+            .collect();
+        let mut keywords = hierarchy.to_vec();
+        for keyword in flat {
+            let keyword = keyword.as_ref();
+            if !known.contains(keyword) {
+                if let Ok(keyword) = HierarchicalKeyword::new(&[keyword]) {
+                    if !keywords.contains(&keyword) {
+                        keywords.push(keyword);
+                    }
+                }
+            }
+        }
+        keywords
+    }
+}
+
+impl std::str::FromStr for HierarchicalKeyword {
+    type Err = Rexiv2Error;
+
+    /// Parse a hierarchical keyword from its levels separated by `|`, e.g. `Animals|Birds|Owl`.
+    fn from_str(s: &str) -> Result<HierarchicalKeyword> {
+        HierarchicalKeyword::new(&s.split(SEPARATOR).collect::<Vec<_>>())
+    }
+}
+
+impl std::fmt::Display for HierarchicalKeyword {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.levels.join("|"))
+    }
+}
@@ -46,9 +46,111 @@
 extern crate gexiv2_sys as gexiv2;
 pub use gexiv2::GExiv2LogLevel as LogLevel;
 
+mod camera;
+pub use camera::Camera;
+
+mod capture_settings;
+pub use capture_settings::CaptureSettings;
+
+mod collection;
+pub use collection::Collection;
+
+mod datetime;
+pub use datetime::{CaptureDatePolicy, CaptureDateSource, DateTimeSet, ExifDateTime};
+
+mod digital_source;
+pub use digital_source::DigitalSourceType;
+
+mod duplicates;
+pub use duplicates::{group_duplicates, ContentSignature};
+
+mod easy_access;
+
+mod gps_method;
+pub use gps_method::GpsProcessingMethod;
+
+mod heif;
+pub use heif::heif_image_count;
+
+mod image_region;
+pub use image_region::{ImageRegion, RegionBoundary, RegionShape, RegionUnit};
+
+mod iptc_record;
+pub use iptc_record::{CreditInfo, IptcApplicationRecord, JobInfo};
+
+mod iptc_xmp;
+pub use iptc_xmp::{CreatorContactInfo, IptcLocation, PersonShown};
+
+mod keywords;
+pub use keywords::HierarchicalKeyword;
+
+mod license;
+pub use license::License;
+
+mod open_options;
+pub use open_options::OpenOptions;
+
+mod orientation;
+pub use orientation::Orientation;
+
+mod tag_key;
+pub use tag_key::{AsTagKey, TagKey};
+
+mod provenance;
+pub use provenance::Provenance;
+
+mod read_only;
+pub use read_only::MetadataRef;
+
+mod registry;
+pub use registry::{find_tags, get_known_tags};
+
+mod report;
+pub use report::ReportOptions;
+
+mod sensitivity;
+pub use sensitivity::{IsoSensitivity, SensitivityType};
+
+mod sniff;
+pub use sniff::{detect_byte_order, detect_media_type, find_c2pa_manifest};
+
+mod table;
+pub use table::{apply_table, write_table, RowOutcome, TableFormat, TableWriter};
+
+mod temp_file;
+
+mod xmp_document;
+
+pub mod apex;
+
+pub mod stats;
+
+pub mod tags;
+
+#[cfg(feature = "image")]
+mod image_interop;
+#[cfg(feature = "image")]
+pub use image_interop::{apply_orientation, load_oriented_from_buffer, ThumbnailOptions};
+
+#[cfg(feature = "img-parts")]
+mod img_parts_interop;
+#[cfg(feature = "img-parts")]
+pub use img_parts_interop::MetadataSegments;
+
+#[cfg(feature = "kamadak-exif")]
+mod kamadak_interop;
+#[cfg(feature = "kamadak-exif")]
+pub use kamadak_interop::exif_tag_name;
+
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+
+use std::cell::Cell;
 use std::ffi;
+use std::io::{self, Read, Seek, Write};
 use std::ptr;
 use std::str;
+use std::sync::{atomic, Mutex};
 
 /// A wrapper type for the kinds of errors one might encounter when using the library.
 #[derive(Debug, PartialEq, Eq)]
@@ -61,6 +163,30 @@ pub enum Rexiv2Error {
     ///
     /// May or may not contain a description message.
     Internal(Option<String>),
+    /// A file needing BMFF support, such as an AVIF or HEIC image, was loaded before
+    /// `initialize()` was called. Without it, Exiv2 can't read the file's metadata.
+    NotInitialized,
+    /// `initialize()` was called again on the thread already running it, e.g. from a log handler
+    /// invoked during initialization, which can't wait for itself to finish.
+    ReentrantInitialize,
+    /// A file couldn't be read or written because of its permissions, or because it's on a
+    /// read-only file system.
+    PermissionDenied(String),
+    /// A file, or the directory it should be in, doesn't exist.
+    NotFound(String),
+    /// A file couldn't be written because the disk is full.
+    NoSpace(String),
+    /// A value passed in, or read from the metadata, isn't valid, such as a malformed date or an
+    /// out-of-range rating. The message says what was wrong with it.
+    InvalidValue(String),
+    /// The operation needs a capability that the linked gexiv2 or Exiv2 library lacks, usually
+    /// because it's too old.
+    Unsupported {
+        /// The capability or library version that's needed, e.g. `"gexiv2 0.13 or later"`.
+        needed: String,
+        /// The library version that was found at runtime, e.g. `"gexiv2 0.12.3"`.
+        found: String,
+    },
 }
 
 impl std::fmt::Display for Rexiv2Error {
@@ -70,6 +196,22 @@ impl std::fmt::Display for Rexiv2Error {
             Rexiv2Error::Utf8(ref err) => write!(f, "IO error: {err}"),
             Rexiv2Error::Internal(Some(ref msg)) => write!(f, "Internal error: {msg}"),
             Rexiv2Error::Internal(None) => write!(f, "Unknown internal error"),
+            Rexiv2Error::NotInitialized => {
+                write!(
+                    f,
+                    "rexiv2::initialize() must be called before loading this file"
+                )
+            }
+            Rexiv2Error::ReentrantInitialize => {
+                write!(f, "rexiv2::initialize() was called while already running")
+            }
+            Rexiv2Error::PermissionDenied(ref msg) => write!(f, "Permission denied: {msg}"),
+            Rexiv2Error::NotFound(ref msg) => write!(f, "Not found: {msg}"),
+            Rexiv2Error::NoSpace(ref msg) => write!(f, "No space left on device: {msg}"),
+            Rexiv2Error::InvalidValue(ref msg) => write!(f, "{msg}"),
+            Rexiv2Error::Unsupported { ref needed, ref found } => {
+                write!(f, "Unsupported: needs {needed}, but found {found}")
+            }
         }
     }
 }
@@ -80,6 +222,13 @@ impl std::error::Error for Rexiv2Error {
             Rexiv2Error::NoValue => None,
             Rexiv2Error::Utf8(ref err) => Some(err),
             Rexiv2Error::Internal(_) => None,
+            Rexiv2Error::NotInitialized => None,
+            Rexiv2Error::ReentrantInitialize => None,
+            Rexiv2Error::PermissionDenied(_) => None,
+            Rexiv2Error::NotFound(_) => None,
+            Rexiv2Error::NoSpace(_) => None,
+            Rexiv2Error::InvalidValue(_) => None,
+            Rexiv2Error::Unsupported { .. } => None,
         }
     }
 }
@@ -90,6 +239,16 @@ impl From<str::Utf8Error> for Rexiv2Error {
     }
 }
 
+impl From<std::io::Error> for Rexiv2Error {
+    fn from(err: std::io::Error) -> Rexiv2Error {
+        match err.kind() {
+            io::ErrorKind::PermissionDenied => Rexiv2Error::PermissionDenied(err.to_string()),
+            io::ErrorKind::NotFound => Rexiv2Error::NotFound(err.to_string()),
+            _ => classify_io_message(err.to_string()),
+        }
+    }
+}
+
 impl From<std::ffi::NulError> for Rexiv2Error {
     fn from(err: std::ffi::NulError) -> Rexiv2Error {
         Rexiv2Error::Internal(Some(format!(
@@ -104,18 +263,69 @@ impl From<std::ffi::NulError> for Rexiv2Error {
 pub type Result<T> = std::result::Result<T, Rexiv2Error>;
 
 /// An opaque structure that serves as a container for a media file's metadata.
-#[derive(Debug, PartialEq, Eq)]
+///
+/// Two `Metadata` objects are equal if they hold the same Exif, IPTC, and XMP tags with the same
+/// values, no matter where they were loaded from. Comparing them reads every tag, so it isn't
+/// cheap; see `Metadata::fingerprint()` for comparing against earlier states.
+///
+/// # Examples
+/// ```
+/// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+/// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+/// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+/// #                78, 68, 174, 66, 96, 130];
+/// let first = rexiv2::Metadata::new_from_buffer(&minipng)?;
+/// let second = rexiv2::Metadata::new_from_buffer(&minipng)?;
+/// assert_eq!(first, second);
+/// second.set_tag_string("Xmp.dc.subject", "fox")?;
+/// assert_ne!(first, second);
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
 pub struct Metadata {
     raw: *mut gexiv2::GExiv2Metadata,
+    max_preview_size: Option<u32>,
+    // Exiv2 may keep reading from the buffer it was loaded from, so hold on to it if we can.
+    #[cfg(feature = "bytes")]
+    buffer: Option<bytes::Bytes>,
+    #[cfg(feature = "memmap")]
+    mapping: Option<memmap2::Mmap>,
 }
 
 /// An opaque structure that serves as a container for a preview image.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(PartialEq, Eq)]
 pub struct PreviewImage<'a> {
     raw: *mut gexiv2::GExiv2PreviewProperties,
     metadata: &'a Metadata, // Parent metadata to load a PreviewImage from a PreviewProperties.
 }
 
+/// An iterator over the tags present in a `Metadata` and their interpreted values, as returned by
+/// `Metadata::iter_interpreted`.
+///
+/// Only one domain's list of tag names is held at a time, and each value is read when the
+/// iterator reaches it.
+pub struct InterpretedValues<'a> {
+    metadata: &'a Metadata,
+    /// The number of domains whose tag lists have been fetched so far.
+    domains_fetched: usize,
+    c_tags: *mut *mut libc::c_char,
+    cur_offset: usize,
+}
+
+/// A description of a preview image, as returned by `PreviewImage::get_properties`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PreviewProperties {
+    /// The size of the preview image in bytes.
+    pub size: u32,
+    /// The width of the preview image, in pixels.
+    pub width: u32,
+    /// The height of the preview image, in pixels.
+    pub height: u32,
+    /// The media type of the preview image, if Exiv2 reported one.
+    pub media_type: Option<MediaType>,
+    /// The preview image's recommended file extension, such as `.jpg`, if Exiv2 reported one.
+    pub extension: Option<String>,
+}
+
 /// Container for the three GPS coordinates: longitude, latitude, and altitude.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct GpsInfo {
@@ -179,64 +389,440 @@ pub enum TagType {
     Unknown,
 }
 
+/// The metadata domains (standards) that a tag can belong to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TagDomain {
+    /// Exif metadata.
+    Exif,
+    /// IPTC-IIM metadata.
+    Iptc,
+    /// XMP metadata.
+    Xmp,
+}
+
+/// The byte order (endianness) of the TIFF structure that Exif metadata is stored in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    /// Least significant byte first, marked `II` (for "Intel").
+    LittleEndian,
+    /// Most significant byte first, marked `MM` (for "Motorola").
+    BigEndian,
+}
+
+/// The Exif image file directories (IFDs) that a tag can be stored in.
+///
+/// Variants are ordered roughly as the directories appear in a typical file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExifIfd {
+    /// IFD0, describing the main image (`Exif.Image.*` tags).
+    Ifd0,
+    /// The Exif sub-IFD, with photographic details (`Exif.Photo.*` tags).
+    Exif,
+    /// The GPS sub-IFD (`Exif.GPSInfo.*` tags).
+    Gps,
+    /// The interoperability sub-IFD (`Exif.Iop.*` tags).
+    Interop,
+    /// IFD1, describing the embedded thumbnail (`Exif.Thumbnail.*` tags).
+    Ifd1,
+    /// Further image directories, as found in TIFF-based RAW files (e.g. `Exif.Image2.*` or
+    /// `Exif.SubImage1.*` tags).
+    Additional,
+    /// A vendor-specific maker note (e.g. `Exif.Canon.*` or `Exif.Nikon3.*` tags).
+    MakerNote,
+}
+
+impl ExifIfd {
+    /// Determine which directory an Exif tag is stored in, based on its name.
+    ///
+    /// Returns `None` if the tag is not an Exif tag.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::ExifIfd;
+    /// assert_eq!(ExifIfd::of_tag("Exif.Photo.FNumber"), Some(ExifIfd::Exif));
+    /// assert_eq!(ExifIfd::of_tag("Exif.Nikon3.ISOSpeed"), Some(ExifIfd::MakerNote));
+    /// assert_eq!(ExifIfd::of_tag("Xmp.dc.subject"), None);
+    /// ```
+    pub fn of_tag(tag: &str) -> Option<ExifIfd> {
+        let group = tag.strip_prefix("Exif.")?.split('.').next()?;
+        Some(match group {
+            "Image" => ExifIfd::Ifd0,
+            "Photo" => ExifIfd::Exif,
+            "GPSInfo" => ExifIfd::Gps,
+            "Iop" => ExifIfd::Interop,
+            "Thumbnail" => ExifIfd::Ifd1,
+            _ if group.starts_with("Image")
+                || group.starts_with("SubImage")
+                || group.starts_with("SubThumb") =>
+            {
+                ExifIfd::Additional
+            }
+            _ => ExifIfd::MakerNote,
+        })
+    }
+}
+
+/// A tag whose value differs between two sets of metadata, as found by `Metadata::diff()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagDifference {
+    /// The full name of the tag, such as `Exif.Photo.FNumber`.
+    pub tag: String,
+    /// The interpreted value in the metadata being compared, or `None` if the tag isn't there.
+    pub old: Option<String>,
+    /// The interpreted value in the metadata it's compared to, or `None` if the tag isn't there.
+    pub new: Option<String>,
+}
+
+/// Information about a tag, as known to Exiv2.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagInfo {
+    /// The full name of the tag, such as `Exif.Photo.FNumber`.
+    pub name: String,
+    /// The metadata domain the tag belongs to.
+    pub domain: TagDomain,
+    /// A short, human-readable label for the tag.
+    pub label: String,
+    /// The long-form description of the tag, or an empty string if Exiv2 doesn't have one.
+    pub description: String,
+    /// The type of the tag's data.
+    pub tag_type: TagType,
+}
+
 /// The media types that an image might have.
 ///
 /// This can be easily converted to/created from an Internet Media Type string with the `::from()`
 /// method, thanks to the `std::convert::From` trait.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum MediaType {
+    /// image/avif
+    ///
+    /// Like HEIC, this is a BMFF-based format, which can only be read once `initialize()` has
+    /// been called, and only with gexiv2 0.13 or later.
+    Avif,
     /// image/x-ms-bmp
     Bmp,
     /// image/x-canon-cr2
     CanonCr2,
+    /// image/x-canon-cr3
+    CanonCr3,
     /// image/x-canon-crw
     CanonCrw,
+    /// image/x-adobe-dng
+    Dng,
     /// application/postscript
     Eps,
+    /// image/x-exr
+    Exr,
     /// image/x-fuji-raf
     FujiRaf,
     /// image/gif
     Gif,
+    /// image/heic
+    Heic,
+    /// image/heif
+    Heif,
     /// image/jp2
     Jp2,
     /// image/jpeg
     Jpeg,
+    /// image/jxl
+    JpegXl,
     /// image/x-minolta-mrw
     MinoltaMrw,
+    /// image/x-nikon-nef
+    NikonNef,
     /// image/x-olympus-orf
     OlympusOrf,
+    /// image/x-pentax-pef
+    PentaxPef,
     /// image/png
     Png,
     /// image/x-photoshop
     Psd,
     /// image/x-panasonic-rw2
     PanasonicRw2,
+    /// image/x-samsung-srw
+    SamsungSrw,
+    /// image/x-sony-arw
+    SonyArw,
     /// image/targa
     Tga,
     /// image/tiff
     Tiff,
+    /// image/webp
+    ///
+    /// Exif and XMP metadata can be read and written; WebP files can't hold IPTC metadata. Exiv2
+    /// adds the extended-format `VP8X` header itself when saving metadata to a simple WebP file.
+    Webp,
     /// Some other, unrecognized, media type, contained within.
     Other(String),
 }
 
+impl MediaType {
+    /// The file extensions commonly used for this media type, most common first.
+    ///
+    /// The list is empty for unrecognized (`Other`) media types.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(rexiv2::MediaType::Tiff.extensions(), &["tif", "tiff"]);
+    /// ```
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match *self {
+            MediaType::Avif => &["avif"],
+            MediaType::Bmp => &["bmp"],
+            MediaType::CanonCr2 => &["cr2"],
+            MediaType::CanonCr3 => &["cr3"],
+            MediaType::CanonCrw => &["crw"],
+            MediaType::Dng => &["dng"],
+            MediaType::Eps => &["eps", "epsf", "epsi"],
+            MediaType::Exr => &["exr"],
+            MediaType::FujiRaf => &["raf"],
+            MediaType::Gif => &["gif"],
+            MediaType::Heic => &["heic"],
+            MediaType::Heif => &["heif", "hif"],
+            MediaType::Jp2 => &["jp2", "jpx"],
+            MediaType::Jpeg => &["jpg", "jpeg", "jpe", "jfif"],
+            MediaType::JpegXl => &["jxl"],
+            MediaType::MinoltaMrw => &["mrw"],
+            MediaType::NikonNef => &["nef"],
+            MediaType::OlympusOrf => &["orf"],
+            MediaType::PentaxPef => &["pef"],
+            MediaType::Png => &["png"],
+            MediaType::Psd => &["psd"],
+            MediaType::PanasonicRw2 => &["rw2"],
+            MediaType::SamsungSrw => &["srw"],
+            MediaType::SonyArw => &["arw"],
+            MediaType::Tga => &["tga"],
+            MediaType::Tiff => &["tif", "tiff"],
+            MediaType::Webp => &["webp"],
+            MediaType::Other(_) => &[],
+        }
+    }
+
+    /// The most common file extension for this media type, if it is a recognized one.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(rexiv2::MediaType::Jpeg.preferred_extension(), Some("jpg"));
+    /// assert_eq!(rexiv2::MediaType::from("image/x-unknown").preferred_extension(), None);
+    /// ```
+    pub fn preferred_extension(&self) -> Option<&'static str> {
+        self.extensions().first().copied()
+    }
+
+    /// Find the media type that a file extension usually indicates.
+    ///
+    /// The extension is matched case-insensitively, and may optionally start with a dot.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(rexiv2::MediaType::from_extension("heic"), Some(rexiv2::MediaType::Heic));
+    /// assert_eq!(rexiv2::MediaType::from_extension(".JPEG"), Some(rexiv2::MediaType::Jpeg));
+    /// assert_eq!(rexiv2::MediaType::from_extension("txt"), None);
+    /// ```
+    pub fn from_extension(extension: &str) -> Option<MediaType> {
+        let extension = extension.strip_prefix('.').unwrap_or(extension);
+        KNOWN_MEDIA_TYPES
+            .iter()
+            .find(|media_type| {
+                media_type
+                    .extensions()
+                    .iter()
+                    .any(|ext| ext.eq_ignore_ascii_case(extension))
+            })
+            .cloned()
+    }
+
+    /// Indicates whether files of this media type are based on the ISO Base Media File Format
+    /// (BMFF), which Exiv2 can only read once `initialize()` has been called.
+    ///
+    /// # Examples
+    /// ```
+    /// assert!(rexiv2::MediaType::Heic.is_bmff());
+    /// assert!(!rexiv2::MediaType::Jpeg.is_bmff());
+    /// ```
+    pub fn is_bmff(&self) -> bool {
+        matches!(
+            *self,
+            MediaType::Avif | MediaType::CanonCr3 | MediaType::Heic | MediaType::Heif
+        )
+    }
+
+    /// Report to what extent Exiv2 can write metadata back to files of this media type.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::{MediaType, WriteSupport};
+    /// assert_eq!(MediaType::Jpeg.write_support(), WriteSupport::Full);
+    /// assert_eq!(MediaType::Eps.write_support(), WriteSupport::XmpOnly);
+    /// assert_eq!(MediaType::Heic.write_support(), WriteSupport::ReadOnly);
+    /// ```
+    pub fn write_support(&self) -> WriteSupport {
+        match *self {
+            MediaType::CanonCr2
+            | MediaType::CanonCrw
+            | MediaType::Dng
+            | MediaType::Jp2
+            | MediaType::Jpeg
+            | MediaType::NikonNef
+            | MediaType::OlympusOrf
+            | MediaType::PentaxPef
+            | MediaType::Png
+            | MediaType::Psd
+            | MediaType::SamsungSrw
+            | MediaType::SonyArw
+            | MediaType::Tiff
+            | MediaType::Webp => WriteSupport::Full,
+            MediaType::Eps => WriteSupport::XmpOnly,
+            MediaType::Avif
+            | MediaType::Bmp
+            | MediaType::CanonCr3
+            | MediaType::Exr
+            | MediaType::FujiRaf
+            | MediaType::Gif
+            | MediaType::Heic
+            | MediaType::Heif
+            | MediaType::JpegXl
+            | MediaType::MinoltaMrw
+            | MediaType::PanasonicRw2
+            | MediaType::Tga => WriteSupport::ReadOnly,
+            MediaType::Other(ref s) => match s.as_str() {
+                // Exiv2's own metadata-only file format.
+                "image/x-exv" => WriteSupport::Full,
+                // XMP sidecar files.
+                "application/rdf+xml" => WriteSupport::XmpOnly,
+                _ => WriteSupport::Unknown,
+            },
+        }
+    }
+}
+
+/// The extent to which Exiv2 can save metadata to a given type of file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WriteSupport {
+    /// Metadata can be written. Note that not every format can hold every domain of metadata;
+    /// use e.g. `Metadata::supports_iptc()` to check for a specific loaded file.
+    Full,
+    /// Only XMP metadata can be written.
+    XmpOnly,
+    /// Metadata can be read, but saving it back to the file will fail.
+    ReadOnly,
+    /// The media type is not one rexiv2 knows about.
+    Unknown,
+}
+
+/// The kinds of metadata that can be read from a loaded file, and whether it can be saved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    /// Exif metadata can be read.
+    pub exif: bool,
+    /// IPTC metadata can be read.
+    pub iptc: bool,
+    /// XMP metadata can be read.
+    pub xmp: bool,
+    /// The extent to which metadata can be saved back to the file.
+    pub write_support: WriteSupport,
+}
+
+/// The character set declared for the text in a file's IPTC metadata, by the
+/// `Iptc.Envelope.CharacterSet` tag.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IptcCharset {
+    /// UTF-8 is declared.
+    Utf8,
+    /// No character set is declared. Files like this often hold Latin-1 (ISO 8859-1) text.
+    Undeclared,
+    /// Some other character set is declared, by the contained ISO 2022 escape sequence.
+    Other(Vec<u8>),
+}
+
+/// How new values are combined with the existing values of a multi-valued tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WriteMode {
+    /// Discard the existing values.
+    Replace,
+    /// Add the new values after the existing ones.
+    Append,
+    /// Add the new values after the existing ones, then drop any repeated values, keeping the
+    /// first occurrence of each.
+    Unique,
+    /// Like `Unique`, but values differing only in letter case count as repeats.
+    UniqueIgnoreCase,
+}
+
+/// The culling decision made about an image, as used by Adobe Bridge and other photo managers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PickFlag {
+    /// No decision has been made.
+    Unflagged,
+    /// The image has been picked as a keeper.
+    Picked,
+    /// The image has been rejected.
+    Rejected,
+}
+
+/// All the media types with a dedicated `MediaType` variant.
+const KNOWN_MEDIA_TYPES: [MediaType; 27] = [
+    MediaType::Avif,
+    MediaType::Bmp,
+    MediaType::CanonCr2,
+    MediaType::CanonCr3,
+    MediaType::CanonCrw,
+    MediaType::Dng,
+    MediaType::Eps,
+    MediaType::Exr,
+    MediaType::FujiRaf,
+    MediaType::Gif,
+    MediaType::Heic,
+    MediaType::Heif,
+    MediaType::Jp2,
+    MediaType::Jpeg,
+    MediaType::JpegXl,
+    MediaType::MinoltaMrw,
+    MediaType::NikonNef,
+    MediaType::OlympusOrf,
+    MediaType::PentaxPef,
+    MediaType::Png,
+    MediaType::Psd,
+    MediaType::PanasonicRw2,
+    MediaType::SamsungSrw,
+    MediaType::SonyArw,
+    MediaType::Tga,
+    MediaType::Tiff,
+    MediaType::Webp,
+];
+
 impl<'a> std::convert::From<&'a MediaType> for String {
     fn from(t: &MediaType) -> String {
         match *t {
+            MediaType::Avif => "image/avif".to_string(),
             MediaType::Bmp => "image/x-ms-bmp".to_string(),
             MediaType::CanonCr2 => "image/x-canon-cr2".to_string(),
+            MediaType::CanonCr3 => "image/x-canon-cr3".to_string(),
             MediaType::CanonCrw => "image/x-canon-crw".to_string(),
+            MediaType::Dng => "image/x-adobe-dng".to_string(),
             MediaType::Eps => "application/postscript".to_string(),
+            MediaType::Exr => "image/x-exr".to_string(),
             MediaType::FujiRaf => "image/x-fuji-raf".to_string(),
             MediaType::Gif => "image/gif".to_string(),
+            MediaType::Heic => "image/heic".to_string(),
+            MediaType::Heif => "image/heif".to_string(),
             MediaType::Jp2 => "image/jp2".to_string(),
             MediaType::Jpeg => "image/jpeg".to_string(),
+            MediaType::JpegXl => "image/jxl".to_string(),
             MediaType::MinoltaMrw => "image/x-minolta-mrw".to_string(),
+            MediaType::NikonNef => "image/x-nikon-nef".to_string(),
             MediaType::OlympusOrf => "image/x-olympus-orf".to_string(),
+            MediaType::PentaxPef => "image/x-pentax-pef".to_string(),
             MediaType::Png => "image/png".to_string(),
             MediaType::Psd => "image/x-photoshop".to_string(),
             MediaType::PanasonicRw2 => "image/x-panasonic-rw2".to_string(),
+            MediaType::SamsungSrw => "image/x-samsung-srw".to_string(),
+            MediaType::SonyArw => "image/x-sony-arw".to_string(),
             MediaType::Tga => "image/targa".to_string(),
             MediaType::Tiff => "image/tiff".to_string(),
+            MediaType::Webp => "image/webp".to_string(),
             MediaType::Other(ref s) => s.clone(),
         }
     }
@@ -245,21 +831,33 @@ impl<'a> std::convert::From<&'a MediaType> for String {
 impl<'a> std::convert::From<&'a str> for MediaType {
     fn from(t: &str) -> MediaType {
         match t {
+            "image/avif" => MediaType::Avif,
             "image/x-ms-bmp" => MediaType::Bmp,
             "image/x-canon-cr2" => MediaType::CanonCr2,
+            "image/x-canon-cr3" => MediaType::CanonCr3,
             "image/x-canon-crw" => MediaType::CanonCrw,
+            "image/x-adobe-dng" => MediaType::Dng,
             "application/postscript" => MediaType::Eps,
+            "image/x-exr" => MediaType::Exr,
             "image/x-fuji-raf" => MediaType::FujiRaf,
             "image/gif" => MediaType::Gif,
+            "image/heic" => MediaType::Heic,
+            "image/heif" => MediaType::Heif,
             "image/jp2" => MediaType::Jp2,
             "image/jpeg" => MediaType::Jpeg,
+            "image/jxl" => MediaType::JpegXl,
             "image/x-minolta-mrw" => MediaType::MinoltaMrw,
+            "image/x-nikon-nef" => MediaType::NikonNef,
             "image/x-olympus-orf" => MediaType::OlympusOrf,
+            "image/x-pentax-pef" => MediaType::PentaxPef,
             "image/png" => MediaType::Png,
             "image/x-photoshop" => MediaType::Psd,
             "image/x-panasonic-rw2" => MediaType::PanasonicRw2,
+            "image/x-samsung-srw" => MediaType::SamsungSrw,
+            "image/x-sony-arw" => MediaType::SonyArw,
             "image/targa" => MediaType::Tga,
             "image/tiff" => MediaType::Tiff,
+            "image/webp" => MediaType::Webp,
             _ => MediaType::Other(t.to_string()),
         }
     }
@@ -271,8 +869,6 @@ impl std::fmt::Display for MediaType {
     }
 }
 
-pub use gexiv2::Orientation;
-
 impl Metadata {
     /// Load the metadata from the file found at the given path.
     ///
@@ -284,20 +880,24 @@ impl Metadata {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn new_from_path<S: AsRef<ffi::OsStr>>(path: S) -> Result<Metadata> {
-        let c_str_path = os_str_to_c_string(path)?;
+        let c_str_path = os_str_to_c_string(&path)?;
         let mut err: *mut gexiv2::GError = ptr::null_mut();
 
-        unsafe {
-            let metadata = gexiv2::gexiv2_metadata_new();
-            let ok = gexiv2::gexiv2_metadata_open_path(metadata, c_str_path.as_ptr(), &mut err);
+        let result = unsafe {
+            let metadata = Metadata::wrap(gexiv2::gexiv2_metadata_new());
+            let ok = gexiv2::gexiv2_metadata_open_path(metadata.raw, c_str_path.as_ptr(), &mut err);
             if ok != 1 {
-                let err_msg = ffi::CStr::from_ptr((*err).message).to_str();
-                return Err(Rexiv2Error::Internal(
-                    err_msg.ok().map(|msg| msg.to_string()),
-                ));
+                Err(gerror_to_rexiv2_error(err))
+            } else {
+                Ok(metadata)
             }
-            Ok(Metadata { raw: metadata })
-        }
+        };
+        Metadata::check_bmff_support(result, || {
+            let mut data = vec![];
+            let file = std::fs::File::open(std::path::Path::new(path.as_ref())).ok()?;
+            file.take(64).read_to_end(&mut data).ok()?;
+            detect_media_type(&data)
+        })
     }
 
     /// Load the metadata from the given Exif data buffer.
@@ -306,21 +906,45 @@ impl Metadata {
     pub fn new_from_app1_segment(data: &[u8]) -> Result<Metadata> {
         let mut err: *mut gexiv2::GError = ptr::null_mut();
         unsafe {
-            let metadata = gexiv2::gexiv2_metadata_new();
+            let metadata = Metadata::wrap(gexiv2::gexiv2_metadata_new());
             let ok = gexiv2::gexiv2_metadata_from_app1_segment(
-                metadata,
+                metadata.raw,
                 data.as_ptr(),
                 data.len() as libc::c_long,
                 &mut err,
             );
             if ok != 1 {
-                let err_msg = ffi::CStr::from_ptr((*err).message).to_str();
-                return Err(Rexiv2Error::Internal(
-                    err_msg.ok().map(|msg| msg.to_string()),
-                ));
+                return Err(gerror_to_rexiv2_error(err));
             }
-            Ok(Metadata { raw: metadata })
+            Ok(metadata)
+        }
+    }
+
+    /// Load the metadata of one of the images held in a HEIF container, such as a HEIC file
+    /// holding a burst or a live photo.
+    ///
+    /// The images are counted by [`heif_image_count`], and index 0 is always the primary image;
+    /// its metadata is loaded in full, just like with `new_from_buffer`. The other images only
+    /// have their own Exif data loaded, since XMP and IPTC metadata aren't stored per image.
+    ///
+    /// Returns `Rexiv2Error::NoValue` if there is no image at the given index, or if it has no
+    /// Exif data of its own.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let data = std::fs::read("burst.heic")?;
+    /// for index in 0..rexiv2::heif_image_count(&data) {
+    ///     let meta = rexiv2::Metadata::new_from_heif_image(&data, index)?;
+    ///     println!("{index}: {:?}", meta.get_tag_string("Exif.Photo.DateTimeOriginal"));
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new_from_heif_image(data: &[u8], index: usize) -> Result<Metadata> {
+        if index == 0 {
+            return Metadata::new_from_buffer(data);
         }
+        let exif = heif::exif_for_image(data, index).ok_or(Rexiv2Error::NoValue)?;
+        Metadata::new_from_app1_segment(&exif)
     }
 
     /// Load the metadata from the given data buffer.
@@ -337,45 +961,366 @@ impl Metadata {
     /// ```
     pub fn new_from_buffer(data: &[u8]) -> Result<Metadata> {
         let mut err: *mut gexiv2::GError = ptr::null_mut();
-        unsafe {
-            let metadata = gexiv2::gexiv2_metadata_new();
+        let result = unsafe {
+            let metadata = Metadata::wrap(gexiv2::gexiv2_metadata_new());
             let ok = gexiv2::gexiv2_metadata_open_buf(
-                metadata,
+                metadata.raw,
                 data.as_ptr(),
                 data.len() as libc::c_long,
                 &mut err,
             );
             if ok != 1 {
+                Err(gerror_to_rexiv2_error(err))
+            } else {
+                Ok(metadata)
+            }
+        };
+        Metadata::check_bmff_support(result, || detect_media_type(data))
+    }
+
+    /// Load the metadata from the given shared data buffer.
+    ///
+    /// The metadata keeps a reference to the buffer rather than a copy, so this is the cheapest
+    /// way to load metadata from data that is already held in `Bytes`, e.g. an HTTP request body.
+    ///
+    /// # Examples
+    /// ```
+    /// let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+    ///                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+    ///                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+    ///                78, 68, 174, 66, 96, 130];
+    /// let meta = rexiv2::Metadata::new_from_bytes(bytes::Bytes::from(minipng.to_vec()))?;
+    /// assert_eq!(meta.get_media_type()?, rexiv2::MediaType::Png);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    #[cfg(feature = "bytes")]
+    pub fn new_from_bytes(data: bytes::Bytes) -> Result<Metadata> {
+        let mut meta = Metadata::new_from_buffer(&data)?;
+        meta.buffer = Some(data);
+        Ok(meta)
+    }
+
+    /// Load the metadata from the file found at the given path by mapping it into memory, rather
+    /// than reading it.
+    ///
+    /// For large files such as RAW images, only the parts of the file that Exiv2 actually looks
+    /// at are read from disk. The file must not be modified or truncated while the metadata is
+    /// alive; doing so may crash the program.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let meta = rexiv2::Metadata::new_from_path_mmap("huge.dng")?;
+    /// println!("{:?}", meta.get_exif_tags()?);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    #[cfg(feature = "memmap")]
+    pub fn new_from_path_mmap<S: AsRef<ffi::OsStr>>(path: S) -> Result<Metadata> {
+        let file = std::fs::File::open(std::path::Path::new(path.as_ref()))?;
+        let mapping = unsafe { memmap2::Mmap::map(&file)? };
+        let mut meta = Metadata::new_from_buffer(&mapping)?;
+        meta.mapping = Some(mapping);
+        Ok(meta)
+    }
+
+    /// Load the metadata from an already-open file, without needing access to its path.
+    ///
+    /// The whole file is read from the start, regardless of its current position. On Unix,
+    /// a file descriptor can be turned into a `File` using `File::from(OwnedFd)`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let file = std::fs::File::open("myphoto.jpg")?;
+    /// let meta = rexiv2::Metadata::new_from_file(&file)?;
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn new_from_file(mut file: &std::fs::File) -> Result<Metadata> {
+        file.seek(io::SeekFrom::Start(0))?;
+        Metadata::new_from_reader(file)
+    }
+
+    /// Load the metadata from anything that can be read from, such as a network stream or a file
+    /// inside an archive.
+    ///
+    /// The reader is consumed until its end, and its contents are kept in memory while loading.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+    /// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+    /// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+    /// #                78, 68, 174, 66, 96, 130];
+    /// let meta = rexiv2::Metadata::new_from_reader(std::io::Cursor::new(minipng))?;
+    /// assert_eq!(meta.get_media_type()?, rexiv2::MediaType::Png);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn new_from_reader<R: Read>(mut reader: R) -> Result<Metadata> {
+        let mut data = vec![];
+        reader.read_to_end(&mut data)?;
+        Metadata::new_from_buffer(&data)
+    }
+
+    /// Load the metadata from a GIO input stream, by reading the whole stream into memory first.
+    ///
+    /// Exiv2 needs to seek around the data, which a `GInputStream` can't do, so the stream is read
+    /// until its end and its entire contents are buffered while loading, as with
+    /// `new_from_reader`. The stream is left open.
+    ///
+    /// # Safety
+    /// `stream` must be a valid pointer to a `GInputStream` that is not used concurrently.
+    #[cfg(feature = "gio")]
+    pub unsafe fn new_from_gio_stream(stream: *mut gio_sys::GInputStream) -> Result<Metadata> {
+        let mut data = vec![];
+        let mut chunk = [0u8; 64 * 1024];
+        loop {
+            let mut read = 0;
+            let mut err: *mut glib_sys::GError = ptr::null_mut();
+            let ok = gio_sys::g_input_stream_read_all(
+                stream,
+                chunk.as_mut_ptr(),
+                chunk.len(),
+                &mut read,
+                ptr::null_mut(),
+                &mut err,
+            );
+            if ok != 1 {
+                if err.is_null() {
+                    return Err(Rexiv2Error::Internal(None));
+                }
                 let err_msg = ffi::CStr::from_ptr((*err).message).to_str();
-                return Err(Rexiv2Error::Internal(
+                let result = Err(Rexiv2Error::Internal(
                     err_msg.ok().map(|msg| msg.to_string()),
                 ));
+                glib_sys::g_error_free(err);
+                return result;
+            }
+            data.extend_from_slice(&chunk[..read]);
+            if read < chunk.len() {
+                break;
             }
-            Ok(Metadata { raw: metadata })
         }
+        Metadata::new_from_buffer(&data)
+    }
+
+    /// Load the metadata from the file found at the given path, subject to the given options.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let options = rexiv2::OpenOptions::new().max_size(10_000_000).max_preview_size(65_536);
+    /// let meta = rexiv2::Metadata::open_with("myphoto.jpg", &options)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn open_with<S: AsRef<ffi::OsStr>>(path: S, options: &OpenOptions) -> Result<Metadata> {
+        let path = std::path::Path::new(path.as_ref());
+        let size = std::fs::metadata(path)?.len();
+        options.check_size(size)?;
+
+        let mut meta = if options.headers_only {
+            let mut data = vec![];
+            std::fs::File::open(path)?
+                .take(open_options::HEADER_BYTES)
+                .read_to_end(&mut data)?;
+            Metadata::new_from_buffer(&data)?
+        } else {
+            Metadata::new_from_path(path)?
+        };
+        meta.max_preview_size = options.max_preview_size;
+        Ok(meta)
+    }
+
+    /// Load the metadata from the given data buffer, subject to the given options.
+    ///
+    /// # Examples
+    /// ```
+    /// let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+    ///                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+    ///                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+    ///                78, 68, 174, 66, 96, 130];
+    /// let options = rexiv2::OpenOptions::new().max_size(16);
+    /// assert!(rexiv2::Metadata::new_from_buffer_with(&minipng, &options).is_err());
+    /// ```
+    pub fn new_from_buffer_with(data: &[u8], options: &OpenOptions) -> Result<Metadata> {
+        options.check_size(data.len() as u64)?;
+        let data = if options.headers_only {
+            &data[..data.len().min(open_options::HEADER_BYTES as usize)]
+        } else {
+            data
+        };
+        let mut meta = Metadata::new_from_buffer(data)?;
+        meta.max_preview_size = options.max_preview_size;
+        Ok(meta)
     }
 
     /// Save metadata to the file found at the given path, which must already exist.
+    ///
+    /// Common I/O failures are reported as `Rexiv2Error::NotFound`,
+    /// `Rexiv2Error::PermissionDenied`, or `Rexiv2Error::NoSpace`, rather than as an
+    /// `Internal` error, so that callers can decide whether to retry or skip the file.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let meta = rexiv2::Metadata::new_from_path("myphoto.jpg")?;
+    /// match meta.save_to_file("myphoto.jpg") {
+    ///     Err(rexiv2::Rexiv2Error::PermissionDenied(_)) => println!("Skipping read-only file"),
+    ///     result => result?,
+    /// }
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
     pub fn save_to_file<S: AsRef<ffi::OsStr>>(&self, path: S) -> Result<()> {
-        let c_str_path = os_str_to_c_string(path)?;
+        let c_str_path = os_str_to_c_string(&path)?;
         let mut err: *mut gexiv2::GError = ptr::null_mut();
 
         unsafe {
             let ok = gexiv2::gexiv2_metadata_save_file(self.raw, c_str_path.as_ptr(), &mut err);
             if ok != 1 {
-                let err_msg = ffi::CStr::from_ptr((*err).message).to_str();
-                return Err(Rexiv2Error::Internal(
-                    err_msg.ok().map(|msg| msg.to_string()),
-                ));
+                let error = match gerror_to_rexiv2_error(err) {
+                    Rexiv2Error::Internal(Some(msg)) => classify_io_message(msg),
+                    error => error,
+                };
+                // If Exiv2's message gave nothing away, see if the file itself is the problem.
+                if let (Rexiv2Error::Internal(_), Err(io_err)) =
+                    (&error, std::fs::metadata(path.as_ref()))
+                {
+                    return Err(Rexiv2Error::from(io_err));
+                }
+                return Err(error);
             }
             Ok(())
         }
     }
 
+    /// Save metadata to the file found at the given path, which must already exist, without
+    /// risking corruption of the original if the process is interrupted.
+    ///
+    /// The file is copied to a temporary file alongside it, which is updated, flushed to disk,
+    /// and then renamed over the original. The temporary file gets a random name and is created
+    /// exclusively, so concurrent saves of the same path never share one. The original file's
+    /// permissions are preserved.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let meta = rexiv2::Metadata::new_from_path("myphoto.jpg")?;
+    /// meta.set_tag_string("Exif.Image.Artist", "A. Photographer")?;
+    /// meta.save_to_file_atomic("myphoto.jpg")?;
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn save_to_file_atomic<S: AsRef<ffi::OsStr>>(&self, path: S) -> Result<()> {
+        let path = std::path::Path::new(path.as_ref());
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| Rexiv2Error::Internal(Some(format!("Not a file: {path:?}"))))?;
+        let mut prefix = ffi::OsString::from(".");
+        prefix.push(file_name);
+        prefix.push(".rexiv2-");
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+        let (mut temp_file, temp_path) = temp_file::create_temp_file(
+            dir.unwrap_or_else(|| std::path::Path::new(".")),
+            &prefix,
+            ".tmp",
+        )?;
+
+        let copied = std::fs::File::open(path).and_then(|mut original| {
+            io::copy(&mut original, &mut temp_file)?;
+            temp_file.set_permissions(original.metadata()?.permissions())
+        });
+        // Close the temporary file before Exiv2 opens it by name.
+        drop(temp_file);
+        let result = copied
+            .map_err(Rexiv2Error::from)
+            .and_then(|_| self.save_to_file(&temp_path))
+            .and_then(|_| Ok(std::fs::File::open(&temp_path)?.sync_all()?))
+            .and_then(|_| Ok(std::fs::rename(&temp_path, path)?));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+            return result;
+        }
+
+        // Make sure the rename itself is durable.
+        #[cfg(unix)]
+        if let Some(dir) = dir {
+            std::fs::File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// Save metadata to an already-open file, which must be readable and writable, without
+    /// needing access to its path.
+    ///
+    /// Exiv2 can only write to named files, so the image is copied to a new temporary file in
+    /// the system's temporary directory, updated there, and then written back over the contents
+    /// of the given file. The temporary file is created with a random name that can't clash with
+    /// an existing file, and is removed again afterwards.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let file = std::fs::OpenOptions::new().read(true).write(true).open("myphoto.jpg")?;
+    /// let meta = rexiv2::Metadata::new_from_file(&file)?;
+    /// meta.set_tag_string("Exif.Image.Artist", "A. Photographer")?;
+    /// meta.save_to_open_file(&file)?;
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn save_to_open_file(&self, mut file: &std::fs::File) -> Result<()> {
+        let (mut temp_file, temp_path) =
+            temp_file::create_temp_file(&std::env::temp_dir(), ffi::OsStr::new("rexiv2-"), ".tmp")?;
+        let copied = file
+            .seek(io::SeekFrom::Start(0))
+            .and_then(|_| io::copy(&mut file, &mut temp_file));
+        // Close the temporary file before Exiv2 opens it by name.
+        drop(temp_file);
+        let result = copied
+            .map_err(Rexiv2Error::from)
+            .and_then(|_| self.save_to_file(&temp_path))
+            .and_then(|_| Ok(std::fs::read(&temp_path)?));
+        let _ = std::fs::remove_file(&temp_path);
+        let data = result?;
+
+        file.seek(io::SeekFrom::Start(0))?;
+        file.write_all(&data)?;
+        file.set_len(data.len() as u64)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Create a new file at `destination` containing the image from `source`, with this metadata
+    /// applied to it. The source file is left unchanged.
+    ///
+    /// The destination must not already exist. If saving the metadata fails, the partially
+    /// written destination file is removed again.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let meta = rexiv2::Metadata::new_from_path("original.jpg")?;
+    /// meta.set_tag_string("Exif.Image.Copyright", "A. Photographer")?;
+    /// meta.save_as("original.jpg", "export.jpg")?;
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn save_as<S: AsRef<ffi::OsStr>, T: AsRef<ffi::OsStr>>(
+        &self,
+        source: S,
+        destination: T,
+    ) -> Result<()> {
+        let destination = std::path::Path::new(destination.as_ref());
+        let mut input = std::fs::File::open(std::path::Path::new(source.as_ref()))?;
+        let mut output = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(destination)?;
+
+        let result = std::io::copy(&mut input, &mut output)
+            .and_then(|_| output.sync_all())
+            .map_err(Rexiv2Error::from)
+            .and_then(|_| self.save_to_file(destination));
+        if result.is_err() {
+            let _ = std::fs::remove_file(destination);
+        }
+        result
+    }
+
 
     // Image information.
 
     /// Determine whether the type of file loaded supports Exif metadata.
+    ///
+    /// This is what Exiv2 reports, which is wrong for some types of file; see `capabilities()`.
     pub fn supports_exif(&self) -> bool {
         unsafe { gexiv2::gexiv2_metadata_get_supports_exif(self.raw) == 1 }
     }
@@ -390,9 +1335,46 @@ impl Metadata {
         unsafe { gexiv2::gexiv2_metadata_get_supports_xmp(self.raw) == 1 }
     }
 
-    /// Return the media type of the loaded file.
-    pub fn get_media_type(&self) -> Result<MediaType> {
-        unsafe {
+    /// Report which kinds of metadata can actually be read from the loaded file, and whether it
+    /// can be saved.
+    ///
+    /// Unlike `supports_exif()` and friends, which only relay what Exiv2 claims, this accounts
+    /// for BMFF-based files such as HEIC, from which Exiv2 reads Exif and XMP metadata despite
+    /// reporting no support for either.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let capabilities = meta.capabilities();
+    /// assert!(capabilities.exif && capabilities.iptc && capabilities.xmp);
+    /// assert_eq!(capabilities.write_support, rexiv2::WriteSupport::Full);
+    /// ```
+    pub fn capabilities(&self) -> Capabilities {
+        match self.get_media_type() {
+            Ok(media_type) if media_type.is_bmff() => Capabilities {
+                exif: true,
+                iptc: false,
+                xmp: true,
+                write_support: media_type.write_support(),
+            },
+            media_type => Capabilities {
+                exif: self.supports_exif(),
+                iptc: self.supports_iptc(),
+                xmp: self.supports_xmp(),
+                write_support: media_type
+                    .map(|media_type| media_type.write_support())
+                    .unwrap_or(WriteSupport::Unknown),
+            },
+        }
+    }
+
+    /// Return the media type of the loaded file.
+    pub fn get_media_type(&self) -> Result<MediaType> {
+        unsafe {
             let c_str_val = gexiv2::gexiv2_metadata_get_mime_type(self.raw);
             if c_str_val.is_null() {
                 return Err(Rexiv2Error::NoValue);
@@ -401,11 +1383,38 @@ impl Metadata {
         }
     }
 
+    /// Indicates whether Exiv2 is able to save metadata back to files of the loaded type.
+    ///
+    /// A `true` result doesn't guarantee that saving will succeed (the file may not be writable,
+    /// for instance), but a `false` one means `save_to_file` is bound to fail.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// assert!(meta.can_save());
+    /// ```
+    pub fn can_save(&self) -> bool {
+        match self.get_media_type() {
+            Ok(media_type) => matches!(
+                media_type.write_support(),
+                WriteSupport::Full | WriteSupport::XmpOnly
+            ),
+            Err(_) => false,
+        }
+    }
+
     /// Get the actual un-rotated/un-oriented pixel width of the loaded image.
     ///
     /// Note that this may be different from the values reported by some metadata tags
     /// that take into account the intended orientation of the image.
     ///
+    /// A value of zero or less means the width could not be determined; prefer `dimensions`,
+    /// which reports this as an error.
+    ///
     /// # Examples
     /// ```
     /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
@@ -424,6 +1433,9 @@ impl Metadata {
     /// Note that this may be different from the values reported by some metadata tags
     /// that take into account the intended orientation of the image.
     ///
+    /// A value of zero or less means the height could not be determined; prefer `dimensions`,
+    /// which reports this as an error.
+    ///
     /// # Examples
     /// ```
     /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
@@ -437,6 +1449,28 @@ impl Metadata {
         unsafe { gexiv2::gexiv2_metadata_get_pixel_height(self.raw) }
     }
 
+    /// Get the actual un-rotated/un-oriented pixel width and height of the loaded image.
+    ///
+    /// Returns an error if either dimension could not be determined.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// assert_eq!(meta.dimensions(), Ok((1, 1)));
+    /// ```
+    pub fn dimensions(&self) -> Result<(u32, u32)> {
+        match (self.get_pixel_width(), self.get_pixel_height()) {
+            (width, height) if width > 0 && height > 0 => Ok((width as u32, height as u32)),
+            (width, height) => Err(Rexiv2Error::Internal(Some(format!(
+                "Couldn't determine the image's pixel dimensions (got {width}x{height})"
+            )))),
+        }
+    }
+
 
     // Tag management.
 
@@ -453,8 +1487,11 @@ impl Metadata {
     /// meta.set_tag_string("Exif.Image.DateTime", "2022-08-07 11:19:44");
     /// assert!(meta.has_tag("Exif.Image.DateTime"));
     /// ```
-    pub fn has_tag(&self, tag: &str) -> bool {
-        let c_str_tag = ffi::CString::new(tag).unwrap();
+    pub fn has_tag<K: AsTagKey + ?Sized>(&self, tag: &K) -> bool {
+        let c_str_tag = match tag.tag_c_str() {
+            Ok(c_str_tag) => c_str_tag,
+            Err(_) => return false,
+        };
         unsafe { gexiv2::gexiv2_metadata_has_tag(self.raw, c_str_tag.as_ptr()) == 1 }
     }
 
@@ -472,8 +1509,11 @@ impl Metadata {
     /// assert!(meta.clear_tag("Exif.Image.DateTime"));
     /// assert!(!meta.has_tag("Exif.Image.DateTime"));
     /// ```
-    pub fn clear_tag(&self, tag: &str) -> bool {
-        let c_str_tag = ffi::CString::new(tag).unwrap();
+    pub fn clear_tag<K: AsTagKey + ?Sized>(&self, tag: &K) -> bool {
+        let c_str_tag = match tag.tag_c_str() {
+            Ok(c_str_tag) => c_str_tag,
+            Err(_) => return false,
+        };
         unsafe { gexiv2::gexiv2_metadata_clear_tag(self.raw, c_str_tag.as_ptr()) == 1 }
     }
 
@@ -533,7 +1573,7 @@ impl Metadata {
         unsafe { gexiv2::gexiv2_metadata_clear_exif(self.raw) }
     }
 
-    /// List all Exif tags present in the loaded metadata.
+    /// List all Exif tags present in the loaded metadata, sorted by name.
     ///
     /// # Examples
     /// ```
@@ -549,6 +1589,9 @@ impl Metadata {
         let mut tags = vec![];
         unsafe {
             let c_tags = gexiv2::gexiv2_metadata_get_exif_tags(self.raw);
+            if c_tags.is_null() {
+                return Ok(tags);
+            }
             let mut cur_offset = 0;
             while !(*c_tags.offset(cur_offset)).is_null() {
                 let tag = ffi::CStr::from_ptr(*c_tags.offset(cur_offset)).to_str();
@@ -563,6 +1606,8 @@ impl Metadata {
             }
             free_array_of_pointers(c_tags as *mut *mut libc::c_void);
         }
+        // The order Exiv2 reports tags in varies between versions; make it predictable.
+        tags.sort_unstable();
         Ok(tags)
     }
 
@@ -604,7 +1649,7 @@ impl Metadata {
         unsafe { gexiv2::gexiv2_metadata_clear_xmp(self.raw) }
     }
 
-    /// List all XMP tags present in the loaded metadata.
+    /// List all XMP tags present in the loaded metadata, sorted by name.
     ///
     /// # Examples
     /// ```
@@ -620,6 +1665,9 @@ impl Metadata {
         let mut tags = vec![];
         unsafe {
             let c_tags = gexiv2::gexiv2_metadata_get_xmp_tags(self.raw);
+            if c_tags.is_null() {
+                return Ok(tags);
+            }
             let mut cur_offset = 0;
             while !(*c_tags.offset(cur_offset)).is_null() {
                 let tag = ffi::CStr::from_ptr(*c_tags.offset(cur_offset)).to_str();
@@ -634,6 +1682,8 @@ impl Metadata {
             }
             free_array_of_pointers(c_tags as *mut *mut libc::c_void);
         }
+        // The order Exiv2 reports tags in varies between versions; make it predictable.
+        tags.sort_unstable();
         Ok(tags)
     }
 
@@ -675,7 +1725,7 @@ impl Metadata {
         unsafe { gexiv2::gexiv2_metadata_clear_iptc(self.raw) }
     }
 
-    /// List all IPTC tags present in the loaded metadata.
+    /// List all IPTC tags present in the loaded metadata, sorted by name.
     ///
     /// # Examples
     /// ```
@@ -691,6 +1741,9 @@ impl Metadata {
         let mut tags = vec![];
         unsafe {
             let c_tags = gexiv2::gexiv2_metadata_get_iptc_tags(self.raw);
+            if c_tags.is_null() {
+                return Ok(tags);
+            }
             let mut cur_offset = 0;
             while !(*c_tags.offset(cur_offset)).is_null() {
                 let tag = ffi::CStr::from_ptr(*c_tags.offset(cur_offset)).to_str();
@@ -705,9 +1758,257 @@ impl Metadata {
             }
             free_array_of_pointers(c_tags as *mut *mut libc::c_void);
         }
+        // The order Exiv2 reports tags in varies between versions; make it predictable.
+        tags.sort_unstable();
         Ok(tags)
     }
 
+    /// List all Exif tags present in the loaded metadata, grouped by the directory (IFD) they are
+    /// stored in. Tags within each directory are sorted by name.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::ExifIfd;
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Image.Artist", "A. Photographer");
+    /// meta.set_tag_string("Exif.Photo.UserComment", "Hello");
+    /// let grouped = meta.get_exif_tags_grouped()?;
+    /// assert_eq!(grouped[&ExifIfd::Ifd0], vec!["Exif.Image.Artist".to_string()]);
+    /// assert_eq!(grouped[&ExifIfd::Exif], vec!["Exif.Photo.UserComment".to_string()]);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_exif_tags_grouped(
+        &self,
+    ) -> Result<std::collections::BTreeMap<ExifIfd, Vec<String>>> {
+        let mut grouped = std::collections::BTreeMap::new();
+        for tag in self.get_exif_tags()? {
+            let ifd = ExifIfd::of_tag(&tag).unwrap_or(ExifIfd::MakerNote);
+            grouped.entry(ifd).or_insert_with(Vec::new).push(tag);
+        }
+        Ok(grouped)
+    }
+
+    /// Count the Exif tags present in the loaded metadata.
+    ///
+    /// This is cheaper than `get_exif_tags().len()`, as no strings are allocated.
+    pub fn exif_tag_count(&self) -> usize {
+        unsafe { count_and_free_tags(gexiv2::gexiv2_metadata_get_exif_tags(self.raw)) }
+    }
+
+    /// Count the XMP tags present in the loaded metadata.
+    ///
+    /// This is cheaper than `get_xmp_tags().len()`, as no strings are allocated.
+    pub fn xmp_tag_count(&self) -> usize {
+        unsafe { count_and_free_tags(gexiv2::gexiv2_metadata_get_xmp_tags(self.raw)) }
+    }
+
+    /// Count the IPTC tags present in the loaded metadata.
+    ///
+    /// This is cheaper than `get_iptc_tags().len()`, as no strings are allocated.
+    pub fn iptc_tag_count(&self) -> usize {
+        unsafe { count_and_free_tags(gexiv2::gexiv2_metadata_get_iptc_tags(self.raw)) }
+    }
+
+    /// Count the tags present in the loaded metadata, across all domains.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// assert_eq!(meta.tag_count(), 0);
+    /// meta.set_tag_string("Exif.Image.Artist", "A. Photographer");
+    /// meta.set_tag_string("Xmp.dc.subject", "Test");
+    /// assert_eq!(meta.tag_count(), 2);
+    /// assert_eq!(meta.exif_tag_count(), 1);
+    /// ```
+    pub fn tag_count(&self) -> usize {
+        self.exif_tag_count() + self.xmp_tag_count() + self.iptc_tag_count()
+    }
+
+    /// Indicates whether the loaded metadata contains no tags at all.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// assert!(meta.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        !self.has_exif() && !self.has_xmp() && !self.has_iptc()
+    }
+
+    /// Get the human-readable, interpreted value of every tag present in the loaded metadata,
+    /// across all domains, keyed by tag name.
+    ///
+    /// Each value is the same as `get_tag_interpreted_string` returns, and is read with a call of
+    /// its own, so this is a convenience for filling the default view of a metadata browser
+    /// rather than a faster path. Values that aren't valid UTF-8 are converted lossily rather
+    /// than being left out. Use `iter_interpreted` to avoid building the whole map.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_numeric("Exif.Photo.MeteringMode", 5)?;
+    /// meta.set_tag_string("Xmp.dc.subject", "Test")?;
+    /// let values = meta.get_all_interpreted();
+    /// assert_eq!(values.len(), 2);
+    /// assert_eq!(values["Exif.Photo.MeteringMode"], "Multi-segment");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_all_interpreted(&self) -> std::collections::BTreeMap<String, String> {
+        self.iter_interpreted().collect()
+    }
+
+    /// Iterate over every tag present in the loaded metadata, across all domains, along with its
+    /// human-readable, interpreted value.
+    ///
+    /// Unlike `get_all_interpreted`, the values are read one at a time as the iterator advances,
+    /// so very large sets of metadata (such as maker notes with thousands of entries) can be
+    /// displayed incrementally. Tags are listed in the order Exiv2 reports them: Exif first, then
+    /// XMP, then IPTC.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_numeric("Exif.Photo.MeteringMode", 5)?;
+    /// meta.set_tag_string("Xmp.dc.subject", "Test")?;
+    /// let mut values = meta.iter_interpreted();
+    /// assert_eq!(
+    ///     values.next(),
+    ///     Some(("Exif.Photo.MeteringMode".to_string(), "Multi-segment".to_string())),
+    /// );
+    /// assert_eq!(values.next(), Some(("Xmp.dc.subject".to_string(), "Test".to_string())));
+    /// assert_eq!(values.next(), None);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn iter_interpreted(&self) -> InterpretedValues<'_> {
+        InterpretedValues {
+            metadata: self,
+            domains_fetched: 0,
+            c_tags: ptr::null_mut(),
+            cur_offset: 0,
+        }
+    }
+
+    /// Compare the interpreted value of every tag with those in `other`, returning the tags that
+    /// are only in one of them or whose values differ, sorted by name.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// let old = rexiv2::Metadata::new_from_buffer(&minipng)?;
+    /// let new = rexiv2::Metadata::new_from_buffer(&minipng)?;
+    /// old.set_tag_string("Xmp.xmp.Label", "Draft")?;
+    /// new.set_tag_string("Xmp.xmp.Label", "Final")?;
+    /// new.set_tag_string("Xmp.xmp.Rating", "4")?;
+    /// let differences = old.diff(&new);
+    /// assert_eq!(differences.len(), 2);
+    /// assert_eq!(differences[0].tag, "Xmp.xmp.Label");
+    /// assert_eq!(differences[0].old.as_deref(), Some("Draft"));
+    /// assert_eq!(differences[1].old, None);
+    /// assert_eq!(differences[1].new.as_deref(), Some("4"));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn diff(&self, other: &Metadata) -> Vec<TagDifference> {
+        let mut old = self.get_all_interpreted();
+        let mut new = other.get_all_interpreted();
+        let mut tags: Vec<String> = old.keys().chain(new.keys()).cloned().collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags.into_iter()
+            .filter_map(|tag| {
+                let (old, new) = (old.remove(&tag), new.remove(&tag));
+                (old != new).then_some(TagDifference { tag, old, new })
+            })
+            .collect()
+    }
+
+    /// List every tag and its value in an aligned, human-readable table, grouped by domain and
+    /// tag group, much like the output of `exiftool`.
+    ///
+    /// The `Display` implementation of `Metadata` produces the same listing with the default
+    /// options.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng)?;
+    /// meta.set_tag_string("Exif.Image.Artist", "A. Photographer")?;
+    /// meta.set_tag_string("Exif.Image.Make", "Rexiv")?;
+    /// meta.set_tag_string("Xmp.dc.subject", "fox")?;
+    /// assert_eq!(
+    ///     meta.to_report(rexiv2::ReportOptions::new().labels(true))?,
+    ///     "---- Exif.Image ----\n\
+    ///      Artist       : A. Photographer\n\
+    ///      Manufacturer : Rexiv\n\
+    ///      ---- Xmp.dc ----\n\
+    ///      Subject      : fox\n",
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn to_report(&self, options: ReportOptions) -> Result<String> {
+        report::render(self, options)
+    }
+
+    /// Produce a standalone XMP document (such as for a `.xmp` sidecar file) holding the loaded
+    /// XMP properties.
+    ///
+    /// If `include_exif_iptc` is set, Exif and IPTC tags with a standard XMP equivalent are
+    /// converted and added too, e.g. `Exif.Image.Make` as `tiff:Make` and
+    /// `Iptc.Application2.Keywords` as `dc:subject`. Properties already present in the XMP take
+    /// precedence, followed by Exif, then IPTC.
+    ///
+    /// Qualifiers, structures, and nested arrays are kept as they are, with an empty item filling
+    /// any gap in an array's numbering so that later items keep their positions. Properties in
+    /// custom namespaces are included if the namespace was registered with
+    /// `register_xmp_namespace()`; properties using any other namespace whose URI isn't known to
+    /// rexiv2 are left out.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng)?;
+    /// meta.set_tag_string("Exif.Image.Make", "Rexiv & Co")?;
+    /// meta.set_tag_string("Xmp.dc.subject", "fox")?;
+    ///
+    /// let xmp = meta.to_xmp_document(true)?;
+    /// assert!(xmp.contains(r#"xmlns:tiff="http://ns.adobe.com/tiff/1.0/""#));
+    /// assert!(xmp.contains("<tiff:Make>Rexiv &amp; Co</tiff:Make>"));
+    /// assert!(xmp.contains("<rdf:li>fox</rdf:li>"));
+    /// assert!(!meta.to_xmp_document(false)?.contains("tiff:Make"));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn to_xmp_document(&self, include_exif_iptc: bool) -> Result<String> {
+        xmp_document::build(self, include_exif_iptc)
+    }
+
     /// Get the value of a tag as a string.
     ///
     /// Only safe if the tag is really of a string type.
@@ -722,8 +2023,8 @@ impl Metadata {
     /// # meta.set_tag_string("Iptc.Application2.Subject", "Test Image");
     /// assert_eq!(meta.get_tag_string("Iptc.Application2.Subject"), Ok("Test Image".to_string()));
     /// ```
-    pub fn get_tag_string(&self, tag: &str) -> Result<String> {
-        let c_str_tag = ffi::CString::new(tag)?;
+    pub fn get_tag_string<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<String> {
+        let c_str_tag = tag.tag_c_str()?;
         unsafe {
             let c_str_val = gexiv2::gexiv2_metadata_get_tag_string(self.raw, c_str_tag.as_ptr());
             if c_str_val.is_null() {
@@ -735,6 +2036,84 @@ impl Metadata {
         }
     }
 
+    /// Look at the value of a tag as a string without copying it, by passing it to the given
+    /// function.
+    ///
+    /// This avoids allocating a `String` when the value is only needed briefly, e.g. to compare
+    /// or hash it.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Image.Artist", "A. Photographer");
+    /// assert_eq!(meta.with_tag_string("Exif.Image.Artist", |value| value.len()), Ok(15));
+    /// ```
+    pub fn with_tag_string<K: AsTagKey + ?Sized, R, F: FnOnce(&str) -> R>(
+        &self,
+        tag: &K,
+        f: F,
+    ) -> Result<R> {
+        let c_str_tag = tag.tag_c_str()?;
+        unsafe {
+            let c_str_val = gexiv2::gexiv2_metadata_get_tag_string(self.raw, c_str_tag.as_ptr());
+            if c_str_val.is_null() {
+                return Err(Rexiv2Error::NoValue);
+            }
+            let result = ffi::CStr::from_ptr(c_str_val)
+                .to_str()
+                .map(f)
+                .map_err(Rexiv2Error::from);
+            libc::free(c_str_val as *mut libc::c_void);
+            result
+        }
+    }
+
+    /// Get the string values of many tags at once, in the same order as the given tag names.
+    ///
+    /// Tags that are missing, or that can't be read as a string, give `None`. Each tag is still
+    /// read with a call into gexiv2 of its own, but whether each domain (Exif, IPTC, XMP) has any
+    /// metadata is only checked once, and tags from empty domains are answered without a call.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Image.Artist", "A. Photographer");
+    /// assert_eq!(
+    ///     meta.get_tags_bulk(&["Exif.Image.Artist", "Xmp.dc.creator"]),
+    ///     vec![Some("A. Photographer".to_string()), None]
+    /// );
+    /// ```
+    pub fn get_tags_bulk<K: AsTagKey>(&self, tags: &[K]) -> Vec<Option<String>> {
+        let (has_exif, has_iptc, has_xmp) = (self.has_exif(), self.has_iptc(), self.has_xmp());
+        tags.iter()
+            .map(|tag| {
+                let name = tag.tag_name();
+                let domain_has_data = if name.starts_with("Exif.") {
+                    has_exif
+                } else if name.starts_with("Iptc.") {
+                    has_iptc
+                } else if name.starts_with("Xmp.") {
+                    has_xmp
+                } else {
+                    true
+                };
+                if domain_has_data {
+                    self.get_tag_string(tag).ok()
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// Set the value of a tag to the given string.
     ///
     /// Only safe if the tag is really of a string type.
@@ -749,8 +2128,8 @@ impl Metadata {
     /// meta.set_tag_string("Iptc.Application2.Subject", "Test Image");
     /// assert_eq!(meta.get_tag_string("Iptc.Application2.Subject"), Ok("Test Image".to_string()));
     /// ```
-    pub fn set_tag_string(&self, tag: &str, value: &str) -> Result<()> {
-        let c_str_tag = ffi::CString::new(tag)?;
+    pub fn set_tag_string<K: AsTagKey + ?Sized>(&self, tag: &K, value: &str) -> Result<()> {
+        let c_str_tag = tag.tag_c_str()?;
         let c_str_val = ffi::CString::new(value)?;
         unsafe {
             int_bool_to_result(gexiv2::gexiv2_metadata_set_tag_string(
@@ -764,8 +2143,8 @@ impl Metadata {
     /// Get the value of a tag as a string, potentially formatted for user-visible display.
     ///
     /// Only safe if the tag is really of a string type.
-    pub fn get_tag_interpreted_string(&self, tag: &str) -> Result<String> {
-        let c_str_tag = ffi::CString::new(tag)?;
+    pub fn get_tag_interpreted_string<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<String> {
+        let c_str_tag = tag.tag_c_str()?;
         unsafe {
             let c_str_val =
                 gexiv2::gexiv2_metadata_get_tag_interpreted_string(self.raw, c_str_tag.as_ptr());
@@ -781,8 +2160,8 @@ impl Metadata {
     /// Retrieve the list of string values of the given tag.
     ///
     /// Only safe if the tag is in fact of a string type.
-    pub fn get_tag_multiple_strings(&self, tag: &str) -> Result<Vec<String>> {
-        let c_str_tag = ffi::CString::new(tag)?;
+    pub fn get_tag_multiple_strings<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<String>> {
+        let c_str_tag = tag.tag_c_str()?;
         let mut vals = vec![];
         unsafe {
             let c_vals = gexiv2::gexiv2_metadata_get_tag_multiple(self.raw, c_str_tag.as_ptr());
@@ -807,8 +2186,12 @@ impl Metadata {
     }
 
     /// Store the given strings as the values of a tag.
-    pub fn set_tag_multiple_strings(&self, tag: &str, values: &[&str]) -> Result<()> {
-        let c_str_tag = ffi::CString::new(tag)?;
+    pub fn set_tag_multiple_strings<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        values: &[&str],
+    ) -> Result<()> {
+        let c_str_tag = tag.tag_c_str()?;
         let c_strs: std::result::Result<Vec<_>, _> =
             values.iter().map(|&s| ffi::CString::new(s)).collect();
         let c_strs = c_strs?;
@@ -823,28 +2206,55 @@ impl Metadata {
         }
     }
 
-    /// Get the value of a tag as a number.
-    ///
-    /// Only safe if the tag is really of a numeric type.
+    /// Store the given strings as the values of a tag, combining them with any existing values
+    /// as described by `mode`.
     ///
     /// # Examples
     /// ```
+    /// use rexiv2::WriteMode;
     /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
     /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
     /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
     /// #               69, 78, 68, 174, 66, 96, 130];
     /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
-    /// # meta.set_tag_numeric("Exif.Photo.MaxApertureValue", 5);
-    /// assert_eq!(meta.get_tag_numeric("Exif.Photo.MaxApertureValue"), 5);
+    /// meta.set_tag_multiple_strings("Xmp.dc.subject", &["cat", "dog"]);
+    /// let new = ["Dog", "fish"];
+    /// meta.set_tag_multiple_strings_with_mode("Xmp.dc.subject", &new, WriteMode::UniqueIgnoreCase)?;
+    /// assert_eq!(
+    ///     meta.get_tag_multiple_strings("Xmp.dc.subject"),
+    ///     Ok(vec!["cat".to_string(), "dog".to_string(), "fish".to_string()])
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
     /// ```
-    pub fn get_tag_numeric(&self, tag: &str) -> i32 {
-        let c_str_tag = ffi::CString::new(tag).unwrap();
-        unsafe { gexiv2::gexiv2_metadata_get_tag_long(self.raw, c_str_tag.as_ptr()) as i32 }
+    pub fn set_tag_multiple_strings_with_mode<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        values: &[&str],
+        mode: WriteMode,
+    ) -> Result<()> {
+        if mode == WriteMode::Replace {
+            return self.set_tag_multiple_strings(tag, values);
+        }
+        let existing = match self.get_tag_multiple_strings(tag) {
+            Ok(existing) => existing,
+            Err(Rexiv2Error::NoValue) => vec![],
+            Err(err) => return Err(err),
+        };
+        let mut combined: Vec<&str> = existing.iter().map(String::as_str).collect();
+        combined.extend_from_slice(values);
+        if mode != WriteMode::Append {
+            let mut seen = std::collections::HashSet::new();
+            combined.retain(|value| match mode {
+                WriteMode::UniqueIgnoreCase => seen.insert(value.to_lowercase()),
+                _ => seen.insert(value.to_string()),
+            });
+        }
+        self.set_tag_multiple_strings(tag, &combined)
     }
 
-    /// Set the value of a tag to the given number.
+    /// Count the values of a tag that can hold several, such as a repeated IPTC dataset.
     ///
-    /// Only safe if the tag is really of a numeric type.
+    /// Returns zero if the tag is not present.
     ///
     /// # Examples
     /// ```
@@ -853,23 +2263,22 @@ impl Metadata {
     /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
     /// #               69, 78, 68, 174, 66, 96, 130];
     /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
-    /// # meta.set_tag_numeric("Exif.Photo.MaxApertureValue", 5);
-    /// assert_eq!(meta.get_tag_numeric("Exif.Photo.MaxApertureValue"), 5);
+    /// meta.set_tag_multiple_strings("Iptc.Application2.Keywords", &["cat", "dog"]);
+    /// assert_eq!(meta.tag_value_count("Iptc.Application2.Keywords"), Ok(2));
     /// ```
-    pub fn set_tag_numeric(&self, tag: &str, value: i32) -> Result<()> {
-        let c_str_tag = ffi::CString::new(tag)?;
-        unsafe {
-            int_bool_to_result(gexiv2::gexiv2_metadata_set_tag_long(
-                self.raw,
-                c_str_tag.as_ptr(),
-                value as libc::c_long,
-            ))
+    pub fn tag_value_count<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<usize> {
+        match self.get_tag_multiple_strings(tag) {
+            Ok(values) => Ok(values.len()),
+            Err(Rexiv2Error::NoValue) => Ok(0),
+            Err(err) => Err(err),
         }
     }
 
-    /// Get the value of a tag as a Rational.
+    /// Get one of the values of a tag that can hold several, by its position.
     ///
-    /// Only safe if the tag is in fact of a rational type.
+    /// For XMP arrays, positions start at 0 for the item addressed as `[1]` in XMP paths; so
+    /// `Xmp.dc.subject[2]` is at position 1. Arrays whose items were set one by one, as tags like
+    /// `Xmp.dc.subject[2]`, are handled as well as ones held as a single tag.
     ///
     /// # Examples
     /// ```
@@ -878,31 +2287,27 @@ impl Metadata {
     /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
     /// #               69, 78, 68, 174, 66, 96, 130];
     /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
-    /// let ratio = num_rational::Ratio::new_raw(16, 10);
-    /// # meta.set_tag_rational("Exif.Photo.MaxApertureValue", &ratio);
-    /// assert_eq!(meta.get_tag_rational("Exif.Photo.MaxApertureValue"), Some(ratio));
+    /// meta.set_tag_multiple_strings("Iptc.Application2.Keywords", &["cat", "dog"]);
+    /// assert_eq!(meta.get_tag_value_at("Iptc.Application2.Keywords", 1), Ok("dog".to_string()));
+    /// assert_eq!(
+    ///     meta.get_tag_value_at("Iptc.Application2.Keywords", 2),
+    ///     Err(rexiv2::Rexiv2Error::NoValue)
+    /// );
     /// ```
-    pub fn get_tag_rational(&self, tag: &str) -> Option<num_rational::Ratio<i32>> {
-        let c_str_tag = ffi::CString::new(tag).ok()?;
-        let num = &mut 0;
-        let den = &mut 0;
-        match unsafe {
-            gexiv2::gexiv2_metadata_get_exif_tag_rational(self.raw, c_str_tag.as_ptr(), num, den)
-        } {
-            0 => None,
-            _ => {
-                if *den != 0 {
-                    Some(num_rational::Ratio::new_raw(*num, *den))
-                } else {
-                    None
-                }
-            }
+    pub fn get_tag_value_at<K: AsTagKey + ?Sized>(&self, tag: &K, index: usize) -> Result<String> {
+        let mut values = self.get_indexed_values(tag)?;
+        if index < values.len() {
+            Ok(values.swap_remove(index))
+        } else {
+            Err(Rexiv2Error::NoValue)
         }
     }
 
-    /// Set the value of a tag to a Rational.
+    /// Replace one of the values of a tag that can hold several, by its position, leaving the
+    /// others in place.
     ///
-    /// Only safe if the tag is in fact of a rational type.
+    /// Positions are counted as for `get_tag_value_at`. Returns `Rexiv2Error::NoValue` if there
+    /// is no value at the given position.
     ///
     /// # Examples
     /// ```
@@ -911,23 +2316,30 @@ impl Metadata {
     /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
     /// #               69, 78, 68, 174, 66, 96, 130];
     /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
-    /// let ratio = num_rational::Ratio::new_raw(16, 10);
-    /// meta.set_tag_rational("Exif.Photo.MaxApertureValue", &ratio);
-    /// assert_eq!(meta.get_tag_rational("Exif.Photo.MaxApertureValue"), Some(ratio));
+    /// meta.set_tag_multiple_strings("Xmp.dc.subject", &["cat", "dog", "fish"])?;
+    /// meta.set_tag_value_at("Xmp.dc.subject", 1, "wolf")?;
+    /// assert_eq!(
+    ///     meta.get_tag_multiple_strings("Xmp.dc.subject"),
+    ///     Ok(vec!["cat".to_string(), "wolf".to_string(), "fish".to_string()])
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
     /// ```
-    pub fn set_tag_rational(&self, tag: &str, value: &num_rational::Ratio<i32>) -> Result<()> {
-        let c_str_tag = ffi::CString::new(tag)?;
-        unsafe {
-            int_bool_to_result(gexiv2::gexiv2_metadata_set_exif_tag_rational(
-                self.raw,
-                c_str_tag.as_ptr(),
-                *value.numer(),
-                *value.denom(),
-            ))
-        }
+    pub fn set_tag_value_at<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        index: usize,
+        value: &str,
+    ) -> Result<()> {
+        self.edit_indexed_value(tag, index, IndexedEdit::Replace(value))
     }
 
-    /// Get the value of a tag as raw data.
+    /// Insert a value into a tag that can hold several, at the given position, shifting the
+    /// values after it along. Inserting at the position just past the last value appends to it,
+    /// and inserting at 0 into a missing tag creates it. Qualifiers attached to the items of an
+    /// XMP array, such as `xml:lang`, move along with them.
+    ///
+    /// Positions are counted as for `get_tag_value_at`. Returns `Rexiv2Error::NoValue` if the
+    /// position is further past the end.
     ///
     /// # Examples
     /// ```
@@ -936,31 +2348,2368 @@ impl Metadata {
     /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
     /// #               69, 78, 68, 174, 66, 96, 130];
     /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
-    /// # meta.set_tag_rational("Exif.Photo.MaxApertureValue", &num_rational::Ratio::new_raw(16, 10));
-    /// assert_eq!(meta.get_tag_raw("Exif.Photo.MaxApertureValue"), Ok(vec![0, 0, 0, 16, 0, 0, 0, 10]));
+    /// meta.insert_tag_value_at("Xmp.dc.subject", 0, "fish")?;
+    /// meta.insert_tag_value_at("Xmp.dc.subject", 0, "cat")?;
+    /// meta.insert_tag_value_at("Xmp.dc.subject", 1, "dog")?;
+    /// assert_eq!(
+    ///     meta.get_tag_multiple_strings("Xmp.dc.subject"),
+    ///     Ok(vec!["cat".to_string(), "dog".to_string(), "fish".to_string()])
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
     /// ```
-    #[cfg(feature = "raw-tag-access")]
-    pub fn get_tag_raw(&self, tag: &str) -> Result<Vec<u8>> {
-        let c_str_tag = ffi::CString::new(tag)?;
-        unsafe {
-            let raw_tag_value = gexiv2::gexiv2_metadata_get_tag_raw(self.raw, c_str_tag.as_ptr());
-            let size = &mut 0;
+    pub fn insert_tag_value_at<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        index: usize,
+        value: &str,
+    ) -> Result<()> {
+        self.edit_indexed_value(tag, index, IndexedEdit::Insert(value))
+    }
+
+    /// Remove one of the values of a tag that can hold several, by its position, leaving the
+    /// others in place. The tag is removed entirely once its last value is deleted.
+    ///
+    /// Positions are counted as for `get_tag_value_at`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_multiple_strings("Iptc.Application2.Keywords", &["cat", "dog", "fish"]);
+    /// meta.delete_tag_value_at("Iptc.Application2.Keywords", 1)?;
+    /// assert_eq!(
+    ///     meta.get_tag_multiple_strings("Iptc.Application2.Keywords"),
+    ///     Ok(vec!["cat".to_string(), "fish".to_string()])
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn delete_tag_value_at<K: AsTagKey + ?Sized>(&self, tag: &K, index: usize) -> Result<()> {
+        self.edit_indexed_value(tag, index, IndexedEdit::Delete)
+    }
+
+    /// Add a value to the end of a tag that can hold several, such as an XMP bag or sequence or a
+    /// repeated IPTC dataset, keeping its existing values.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.append_tag_value("Xmp.dc.subject", "cat")?;
+    /// meta.append_tag_value("Xmp.dc.subject", "dog")?;
+    /// assert_eq!(
+    ///     meta.get_tag_multiple_strings("Xmp.dc.subject"),
+    ///     Ok(vec!["cat".to_string(), "dog".to_string()])
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn append_tag_value<K: AsTagKey + ?Sized>(&self, tag: &K, value: &str) -> Result<()> {
+        self.set_tag_multiple_strings_with_mode(tag, &[value], WriteMode::Append)
+    }
+
+    /// Get the text of an XMP language alternative tag (such as `Xmp.dc.title`) in every language
+    /// it has, as `(language, text)` pairs. The default, `x-default`, comes first if present.
+    ///
+    /// A tag holding plain text is returned as its default language.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_lang_alt("Xmp.dc.title", "x-default", "Sunset")?;
+    /// meta.set_tag_lang_alt("Xmp.dc.title", "fr-FR", "Coucher de soleil")?;
+    /// assert_eq!(
+    ///     meta.get_tag_lang_alt("Xmp.dc.title")?,
+    ///     [
+    ///         ("x-default".to_string(), "Sunset".to_string()),
+    ///         ("fr-FR".to_string(), "Coucher de soleil".to_string()),
+    ///     ],
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_tag_lang_alt<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<(String, String)>> {
+        Ok(parse_lang_alt(&self.get_tag_string(tag)?))
+    }
+
+    /// Set the text of an XMP language alternative tag in one language, such as `x-default` or
+    /// `de-DE`, keeping its text in other languages. Empty text removes that language.
+    ///
+    /// The tag must be known to Exiv2 as a language alternative, or it will hold plain text.
+    pub fn set_tag_lang_alt<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        lang: &str,
+        text: &str,
+    ) -> Result<()> {
+        let mut entries = match self.get_tag_lang_alt(tag) {
+            Ok(entries) => entries,
+            Err(Rexiv2Error::NoValue) => vec![],
+            Err(err) => return Err(err),
+        };
+        entries.retain(|(entry_lang, _)| entry_lang != lang);
+        if !text.is_empty() {
+            entries.push((lang.to_string(), text.to_string()));
+        }
+        self.clear_tag(tag);
+        for (lang, text) in entries {
+            self.set_tag_string(tag, &format!("lang=\"{lang}\" {text}"))?;
+        }
+        Ok(())
+    }
+
+    /// Get the value of a qualifier attached to an XMP property, such as `xml:lang` on an array
+    /// item, which the plain tag value can't represent.
+    ///
+    /// The qualifier is named with its namespace prefix, such as `xml:lang`. Exiv2 holds it as a
+    /// tag of its own, named after the property followed by `/?` and the qualifier, as in
+    /// `Xmp.dc.creator[1]/?xml:lang`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Xmp.dc.source", "Archive box 12")?;
+    /// meta.set_xmp_qualifier("Xmp.dc.source", "xml:lang", "en")?;
+    /// assert_eq!(meta.get_xmp_qualifier("Xmp.dc.source", "xml:lang"), Ok("en".to_string()));
+    /// assert_eq!(
+    ///     meta.get_xmp_qualifiers("Xmp.dc.source")?,
+    ///     [("xml:lang".to_string(), "en".to_string())],
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_xmp_qualifier<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        qualifier: &str,
+    ) -> Result<String> {
+        self.get_tag_string(&xmp_qualifier_tag(tag, qualifier)?)
+    }
+
+    /// Set the value of a qualifier attached to an XMP property, named as for
+    /// `get_xmp_qualifier`. An empty value removes the qualifier.
+    ///
+    /// The property itself must be present by the time the metadata is saved.
+    pub fn set_xmp_qualifier<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        qualifier: &str,
+        value: &str,
+    ) -> Result<()> {
+        let qualifier_tag = xmp_qualifier_tag(tag, qualifier)?;
+        if value.is_empty() {
+            self.clear_tag(&qualifier_tag);
+            return Ok(());
+        }
+        self.set_tag_string(&qualifier_tag, value)
+    }
+
+    /// List all the qualifiers attached to an XMP property, as `(qualifier, value)` pairs sorted
+    /// by qualifier name.
+    pub fn get_xmp_qualifiers<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+    ) -> Result<Vec<(String, String)>> {
+        let prefix = format!("{}/?", tag.tag_name());
+        let mut qualifiers = vec![];
+        for qualifier_tag in self.get_xmp_tags()? {
+            match qualifier_tag.strip_prefix(&prefix) {
+                Some(qualifier) if !qualifier.contains('/') => {
+                    let value = self.get_tag_string(&qualifier_tag)?;
+                    qualifiers.push((qualifier.to_string(), value));
+                }
+                _ => {}
+            }
+        }
+        Ok(qualifiers)
+    }
+
+    /// Remove every occurrence of a value from a tag that can hold several, keeping its other
+    /// values. The tag is removed entirely once its last value is removed.
+    ///
+    /// Returns whether any value was removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_multiple_strings("Iptc.Application2.Keywords", &["cat", "dog"]);
+    /// assert_eq!(meta.remove_tag_value("Iptc.Application2.Keywords", "cat"), Ok(true));
+    /// assert_eq!(meta.remove_tag_value("Iptc.Application2.Keywords", "cat"), Ok(false));
+    /// assert_eq!(
+    ///     meta.get_tag_multiple_strings("Iptc.Application2.Keywords"),
+    ///     Ok(vec!["dog".to_string()])
+    /// );
+    /// ```
+    pub fn remove_tag_value<K: AsTagKey + ?Sized>(&self, tag: &K, value: &str) -> Result<bool> {
+        let existing = match self.get_tag_multiple_strings(tag) {
+            Ok(values) => values,
+            Err(Rexiv2Error::NoValue) => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        let values: Vec<&str> = existing
+            .iter()
+            .map(String::as_str)
+            .filter(|&existing| existing != value)
+            .collect();
+        if values.len() == existing.len() {
+            return Ok(false);
+        }
+        if values.is_empty() {
+            self.clear_tag(tag);
+        } else {
+            self.set_tag_multiple_strings(tag, &values)?;
+        }
+        Ok(true)
+    }
+
+    /// Get the value of a tag as a number.
+    ///
+    /// Only safe if the tag is really of a numeric type.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// # meta.set_tag_numeric("Exif.Photo.MaxApertureValue", 5);
+    /// assert_eq!(meta.get_tag_numeric("Exif.Photo.MaxApertureValue"), 5);
+    /// ```
+    pub fn get_tag_numeric<K: AsTagKey + ?Sized>(&self, tag: &K) -> i32 {
+        let c_str_tag = match tag.tag_c_str() {
+            Ok(c_str_tag) => c_str_tag,
+            Err(_) => return 0,
+        };
+        unsafe { gexiv2::gexiv2_metadata_get_tag_long(self.raw, c_str_tag.as_ptr()) as i32 }
+    }
+
+    /// Set the value of a tag to the given number.
+    ///
+    /// Only safe if the tag is really of a numeric type.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// # meta.set_tag_numeric("Exif.Photo.MaxApertureValue", 5);
+    /// assert_eq!(meta.get_tag_numeric("Exif.Photo.MaxApertureValue"), 5);
+    /// ```
+    pub fn set_tag_numeric<K: AsTagKey + ?Sized>(&self, tag: &K, value: i32) -> Result<()> {
+        let c_str_tag = tag.tag_c_str()?;
+        unsafe {
+            int_bool_to_result(gexiv2::gexiv2_metadata_set_tag_long(
+                self.raw,
+                c_str_tag.as_ptr(),
+                value as libc::c_long,
+            ))
+        }
+    }
+
+    /// Get the value of a tag as a Rational.
+    ///
+    /// Only safe if the tag is in fact of a rational type.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let ratio = num_rational::Ratio::new_raw(16, 10);
+    /// # meta.set_tag_rational("Exif.Photo.MaxApertureValue", &ratio);
+    /// assert_eq!(meta.get_tag_rational("Exif.Photo.MaxApertureValue"), Some(ratio));
+    /// ```
+    pub fn get_tag_rational<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+    ) -> Option<num_rational::Ratio<i32>> {
+        let c_str_tag = tag.tag_c_str().ok()?;
+        let num = &mut 0;
+        let den = &mut 0;
+        match unsafe {
+            gexiv2::gexiv2_metadata_get_exif_tag_rational(self.raw, c_str_tag.as_ptr(), num, den)
+        } {
+            0 => None,
+            _ => {
+                if *den != 0 {
+                    Some(num_rational::Ratio::new_raw(*num, *den))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Set the value of a tag to a Rational.
+    ///
+    /// Only safe if the tag is in fact of a rational type.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let ratio = num_rational::Ratio::new_raw(16, 10);
+    /// meta.set_tag_rational("Exif.Photo.MaxApertureValue", &ratio);
+    /// assert_eq!(meta.get_tag_rational("Exif.Photo.MaxApertureValue"), Some(ratio));
+    /// ```
+    pub fn set_tag_rational<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        value: &num_rational::Ratio<i32>,
+    ) -> Result<()> {
+        let c_str_tag = tag.tag_c_str()?;
+        unsafe {
+            int_bool_to_result(gexiv2::gexiv2_metadata_set_exif_tag_rational(
+                self.raw,
+                c_str_tag.as_ptr(),
+                *value.numer(),
+                *value.denom(),
+            ))
+        }
+    }
+
+    /// Get the value of an Exif date/time tag, such as `Exif.Photo.DateTimeOriginal`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Image.DateTime", "2022:08:07 11:19:44")?;
+    /// let date = meta.get_tag_datetime("Exif.Image.DateTime")?;
+    /// assert_eq!((date.hour, date.minute, date.second), (11, 19, 44));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_tag_datetime<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<ExifDateTime> {
+        self.get_tag_string(tag)?.parse()
+    }
+
+    /// Set the value of an Exif date/time tag, such as `Exif.Photo.DateTimeOriginal`.
+    ///
+    /// The date is validated first, so malformed values never end up in the file. Only the date
+    /// and time are written; any fractional seconds or offset are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let date = rexiv2::ExifDateTime::new(2022, 8, 7, 11, 19, 44)?;
+    /// meta.set_tag_datetime("Exif.Image.DateTime", &date)?;
+    /// assert_eq!(meta.get_tag_string("Exif.Image.DateTime")?, "2022:08:07 11:19:44");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_tag_datetime<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        value: &ExifDateTime,
+    ) -> Result<()> {
+        value.validate()?;
+        self.set_tag_string(tag, &value.to_exif_string())
+    }
+
+    /// Get the date and time held by a pair of IPTC tags, such as `Iptc.Application2.DateCreated`
+    /// and `Iptc.Application2.TimeCreated`.
+    ///
+    /// If the time tag is missing, the time is taken to be midnight, with no offset.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::tags::iptc;
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string(iptc::DATE_CREATED, "20220807")?;
+    /// meta.set_tag_string(iptc::TIME_CREATED, "111944+0200")?;
+    /// let date = meta.get_iptc_datetime(iptc::DATE_CREATED, iptc::TIME_CREATED)?;
+    /// assert_eq!(date.to_string(), "2022:08:07 11:19:44+02:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_iptc_datetime<K: AsTagKey + ?Sized, L: AsTagKey + ?Sized>(
+        &self,
+        date_tag: &K,
+        time_tag: &L,
+    ) -> Result<ExifDateTime> {
+        let date = self.get_tag_string(date_tag)?;
+        let time = self.get_tag_string(time_tag).ok();
+        datetime::parse_iptc(&date, time.as_deref()).ok_or_else(|| {
+            Rexiv2Error::InvalidValue(format!("Invalid IPTC date/time: {date:?} {time:?}"))
+        })
+    }
+
+    /// Set a pair of IPTC date and time tags, such as `Iptc.Application2.DateCreated` and
+    /// `Iptc.Application2.TimeCreated`, converting to the formats IPTC requires.
+    ///
+    /// IPTC times always include an offset from UTC, so `+00:00` is written if the value
+    /// doesn't have one. Fractions of a second are dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::tags::iptc;
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let date: rexiv2::ExifDateTime = "2022:08:07 11:19:44-05:00".parse()?;
+    /// meta.set_iptc_datetime(iptc::DATE_CREATED, iptc::TIME_CREATED, &date)?;
+    /// assert_eq!(meta.get_tag_string(iptc::DATE_CREATED)?, "2022-08-07");
+    /// assert_eq!(meta.get_tag_string(iptc::TIME_CREATED)?, "11:19:44-05:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_iptc_datetime<K: AsTagKey + ?Sized, L: AsTagKey + ?Sized>(
+        &self,
+        date_tag: &K,
+        time_tag: &L,
+        value: &ExifDateTime,
+    ) -> Result<()> {
+        value.validate()?;
+        self.set_tag_string(date_tag, &value.iptc_date_string())?;
+        self.set_tag_string(time_tag, &value.iptc_time_string())
+    }
+
+    /// Get the value of a tag as raw data.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// # meta.set_tag_rational("Exif.Photo.MaxApertureValue", &num_rational::Ratio::new_raw(16, 10));
+    /// assert_eq!(meta.get_tag_raw("Exif.Photo.MaxApertureValue"), Ok(vec![0, 0, 0, 16, 0, 0, 0, 10]));
+    /// ```
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_raw<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<u8>> {
+        let mut data = vec![];
+        self.get_tag_raw_into(tag, &mut data)?;
+        Ok(data)
+    }
+
+    /// Copy the raw value of a tag into the given buffer, replacing its contents.
+    ///
+    /// This lets the same buffer be reused for many tags or files.
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_raw_into<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        buffer: &mut Vec<u8>,
+    ) -> Result<()> {
+        let c_str_tag = tag.tag_c_str()?;
+        buffer.clear();
+        unsafe {
+            let raw_tag_value = gexiv2::gexiv2_metadata_get_tag_raw(self.raw, c_str_tag.as_ptr());
+            if raw_tag_value.is_null() {
+                return Err(Rexiv2Error::NoValue);
+            }
+            let size = &mut 0;
             let ptr = glib_sys::g_bytes_get_data(raw_tag_value, size) as *const u8;
             let result = if ptr.is_null() {
                 Err(Rexiv2Error::NoValue)
             } else {
-                // Make a copy here
-                // Could be optimized out but need to keep a reference to the returned GByte object
-                Ok(std::slice::from_raw_parts(ptr, *size).to_owned())
+                // The data has to be copied, as it is owned by the GBytes released below.
+                buffer.extend_from_slice(std::slice::from_raw_parts(ptr, *size));
+                Ok(())
             };
             glib_sys::g_bytes_unref(raw_tag_value);
             result
         }
     }
 
-    // Helper & convenience getters/setters.
+    /// Get the size in bytes of the raw value of a tag.
+    ///
+    /// Useful for finding unusually large tags, such as giant maker notes, that take up space.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Image.Artist", "Me")?;
+    /// assert_eq!(meta.get_tag_size("Exif.Image.Artist"), Ok(3));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_size<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<usize> {
+        self.get_tag_raw(tag).map(|raw| raw.len())
+    }
+
+    /// Get the number of components in the value of a tag.
+    ///
+    /// For Exif tags this is the count stored in the file, e.g. the number of characters of an
+    /// ASCII string, including its terminating nul, or the number of numbers in an array. For
+    /// XMP and IPTC tags it is the number of values of the tag.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.GPSInfo.GPSVersionID", "2 2 0 0")?;
+    /// assert_eq!(meta.get_tag_component_count("Exif.GPSInfo.GPSVersionID"), Ok(4));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_component_count<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<usize> {
+        match component_size(get_tag_type(tag)?) {
+            Some(component_size) => self.get_tag_size(tag).map(|size| size / component_size),
+            None => self.tag_value_count(tag),
+        }
+    }
+
+    /// Get the raw value of a tag split into its components, such as the individual numbers of
+    /// an array of SHORTs or RATIONALs, each as its own buffer.
+    ///
+    /// Components are as counted by `get_tag_component_count`, so each byte of an ASCII or
+    /// UNDEFINED tag is a component of its own. Tags whose type has no fixed component size, such
+    /// as XMP and IPTC tags, are returned as a single buffer holding the whole raw value. Any
+    /// trailing bytes that don't make up a whole component are left out.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.GPSInfo.GPSLatitude", "51/1 30/1 2639/100")?;
+    /// let values = meta.get_tag_raw_values("Exif.GPSInfo.GPSLatitude")?;
+    /// assert_eq!(values.len(), 3);
+    /// assert_eq!(values[1], vec![0, 0, 0, 30, 0, 0, 0, 1]);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_raw_values<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<Vec<u8>>> {
+        let raw = self.get_tag_raw(tag)?;
+        Ok(match component_size(get_tag_type(tag)?) {
+            Some(component_size) => raw
+                .chunks_exact(component_size)
+                .map(<[u8]>::to_vec)
+                .collect(),
+            None => vec![raw],
+        })
+    }
+
+    // Helper & convenience getters/setters.
+
+    /// Find out the orientation the image should have, according to the metadata tag.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// assert_eq!(meta.get_orientation(), rexiv2::Orientation::Unspecified);
+    /// ```
+    pub fn get_orientation(&self) -> Orientation {
+        Orientation::from(unsafe { gexiv2::gexiv2_metadata_get_orientation(self.raw) })
+    }
+
+    /// Set the intended orientation for the image.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// assert_eq!(meta.get_orientation(), rexiv2::Orientation::Unspecified);
+    /// meta.set_orientation(rexiv2::Orientation::VerticalFlip);
+    /// assert_eq!(meta.get_orientation(), rexiv2::Orientation::VerticalFlip);
+    /// ```
+    pub fn set_orientation(&self, orientation: Orientation) {
+        unsafe { gexiv2::gexiv2_metadata_set_orientation(self.raw, orientation.into()) }
+    }
+
+    /// Reset the orientation tag to `Normal`, returning the orientation it had before.
+    ///
+    /// This is useful after the pixel data has been rotated to match the old orientation.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_orientation(rexiv2::Orientation::Rotate90);
+    /// assert_eq!(meta.normalize_orientation(), rexiv2::Orientation::Rotate90);
+    /// assert_eq!(meta.get_orientation(), rexiv2::Orientation::Normal);
+    /// ```
+    pub fn normalize_orientation(&self) -> Orientation {
+        let previous = self.get_orientation();
+        self.set_orientation(Orientation::Normal);
+        previous
+    }
+
+    /// Returns the camera exposure time of the photograph.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// # meta.set_tag_rational("Exif.Photo.ExposureTime", &num_rational::Ratio::new_raw(1, 1000));
+    /// assert_eq!(meta.get_exposure_time(), Some(num_rational::Ratio::new_raw(1, 1000)));
+    /// ```
+    pub fn get_exposure_time(&self) -> Option<num_rational::Ratio<i32>> {
+        let num = &mut 0;
+        let den = &mut 0;
+        match unsafe { gexiv2::gexiv2_metadata_get_exposure_time(self.raw, num, den) } {
+            0 => None,
+            _ => {
+                if *den != 0 {
+                    Some(num_rational::Ratio::new_raw(*num, *den))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Returns the f-number used by the camera taking the photograph.
+    ///
+    /// This comes from `Exif.Photo.FNumber` if it's set, and is otherwise converted from the
+    /// APEX value in `Exif.Photo.ApertureValue`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string(rexiv2::tags::exif::APERTURE_VALUE, "4/1")?;
+    /// assert!((meta.get_fnumber().unwrap() - 4.0).abs() < 1e-6);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_fnumber(&self) -> Option<f64> {
+        match unsafe { gexiv2::gexiv2_metadata_get_fnumber(self.raw) } {
+            error_value if error_value < 0.0 => None, // gexiv2 returns -1.0 on error
+            fnumber => Some(fnumber),
+        }
+    }
+
+    /// Returns the exposure time of the photograph in seconds.
+    ///
+    /// This comes from `Exif.Photo.ExposureTime` (see `get_exposure_time`) if it's set, and is
+    /// otherwise converted from the APEX value in `Exif.Photo.ShutterSpeedValue`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string(rexiv2::tags::exif::SHUTTER_SPEED_VALUE, "6/1")?;
+    /// assert_eq!(meta.get_exposure_seconds(), Some(1.0 / 64.0));
+    /// meta.set_tag_string(rexiv2::tags::exif::EXPOSURE_TIME, "1/60")?;
+    /// assert_eq!(meta.get_exposure_seconds(), Some(1.0 / 60.0));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_exposure_seconds(&self) -> Option<f64> {
+        match self.get_exposure_time() {
+            Some(time) if *time.numer() > 0 && *time.denom() > 0 => {
+                Some(f64::from(*time.numer()) / f64::from(*time.denom()))
+            }
+            _ => self
+                .get_shutter_speed_value()
+                .map(apex::tv_to_exposure_time),
+        }
+    }
+
+    /// Returns the widest aperture of the lens as an f-number, converted from the APEX value in
+    /// `Exif.Photo.MaxApertureValue` (see `get_max_aperture_value`).
+    pub fn get_max_fnumber(&self) -> Option<f64> {
+        self.get_max_aperture_value().map(apex::av_to_f_number)
+    }
+
+    /// Returns the focal length used by the camera taking the photograph.
+    pub fn get_focal_length(&self) -> Option<f64> {
+        match unsafe { gexiv2::gexiv2_metadata_get_focal_length(self.raw) } {
+            error_value if error_value < 0.0 => None, // gexiv2 returns -1.0 on error
+            focal => Some(focal),
+        }
+    }
+
+    /// Returns the ISO speed used by the camera taking the photograph.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// # meta.set_tag_numeric("Exif.Photo.ISOSpeedRatings", 600);
+    /// assert_eq!(meta.get_iso_speed(), Some(600));
+    /// ```
+    pub fn get_iso_speed(&self) -> Option<i32> {
+        match unsafe { gexiv2::gexiv2_metadata_get_iso_speed(self.raw) } {
+            0 => None,
+            speed => Some(speed),
+        }
+    }
+
+    /// Returns the effective sensitivity of the camera, along with what it was measured as.
+    ///
+    /// Unlike `get_iso_speed`, this follows Exif 2.3: it takes `Exif.Photo.SensitivityType` into
+    /// account, and finds sensitivities above 65535, which don't fit in
+    /// `Exif.Photo.ISOSpeedRatings`, in `Exif.Photo.ISOSpeed`,
+    /// `Exif.Photo.StandardOutputSensitivity`, or `Exif.Photo.RecommendedExposureIndex`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// use rexiv2::{tags::exif, IsoSensitivity, SensitivityType};
+    /// meta.set_tag_numeric(exif::ISO_SPEED_RATINGS, 65535)?;
+    /// meta.set_tag_numeric(exif::SENSITIVITY_TYPE, 2)?;
+    /// meta.set_tag_numeric(exif::RECOMMENDED_EXPOSURE_INDEX, 102400)?;
+    /// assert_eq!(
+    ///     meta.get_iso_sensitivity(),
+    ///     Some(IsoSensitivity {
+    ///         value: 102400,
+    ///         sensitivity_type: SensitivityType::RecommendedExposureIndex,
+    ///     }),
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_iso_sensitivity(&self) -> Option<IsoSensitivity> {
+        sensitivity::effective_sensitivity(self)
+    }
+
+    /// Returns the shutter speed in APEX units, from `Exif.Photo.ShutterSpeedValue` or
+    /// `Exif.Image.ShutterSpeedValue`. Use `get_exposure_seconds` for the time in seconds.
+    pub fn get_shutter_speed_value(&self) -> Option<f64> {
+        self.get_first_rational_as_f64(&easy_access::SHUTTER_SPEED_VALUE)
+    }
+
+    /// Returns the brightness of the scene in APEX units, from `Exif.Photo.BrightnessValue` or
+    /// `Exif.Image.BrightnessValue`.
+    pub fn get_brightness_value(&self) -> Option<f64> {
+        self.get_first_rational_as_f64(&easy_access::BRIGHTNESS_VALUE)
+    }
+
+    /// Returns the exposure compensation in EV, from `Exif.Photo.ExposureBiasValue` or
+    /// `Exif.Image.ExposureBiasValue`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string(rexiv2::tags::exif::EXPOSURE_BIAS_VALUE, "-2/3")?;
+    /// assert_eq!(meta.get_exposure_bias(), Some(-2.0 / 3.0));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_exposure_bias(&self) -> Option<f64> {
+        self.get_first_rational_as_f64(&easy_access::EXPOSURE_BIAS_VALUE)
+    }
+
+    /// Returns the widest aperture of the lens in APEX units, from `Exif.Photo.MaxApertureValue`
+    /// or `Exif.Image.MaxApertureValue`. Use `get_max_fnumber` for the f-number.
+    pub fn get_max_aperture_value(&self) -> Option<f64> {
+        self.get_first_rational_as_f64(&easy_access::MAX_APERTURE_VALUE)
+    }
+
+    /// Returns the distance to the subject in metres, from `Exif.Photo.SubjectDistance` or
+    /// `Exif.Image.SubjectDistance`.
+    ///
+    /// A distance recorded as unknown (a numerator of 0) is returned as `None`, and one recorded
+    /// as infinite (a numerator of `0xFFFFFFFF`) is returned as `f64::INFINITY`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string(rexiv2::tags::exif::SUBJECT_DISTANCE, "5/2")?;
+    /// assert_eq!(meta.get_subject_distance(), Some(2.5));
+    /// meta.set_tag_string(rexiv2::tags::exif::SUBJECT_DISTANCE, "4294967295/100")?;
+    /// assert_eq!(meta.get_subject_distance(), Some(f64::INFINITY));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_subject_distance(&self) -> Option<f64> {
+        let (numerator, distance) = easy_access::SUBJECT_DISTANCE.iter().find_map(|tag| {
+            let value = self.get_tag_string(*tag).ok()?;
+            let first = value.split_whitespace().next()?;
+            let distance = *parse_rationals_as_f64(first)?.first()?;
+            Some((first.split_once('/')?.0.parse::<u32>().ok(), distance))
+        })?;
+        // Exif marks an infinite distance by the numerator alone, whatever the denominator.
+        match numerator {
+            Some(u32::MAX) => Some(f64::INFINITY),
+            _ if distance == 0.0 => None,
+            _ => Some(distance),
+        }
+    }
+
+    /// Returns the saturation processing applied by the camera, as a human-readable value such as
+    /// `"High"`.
+    ///
+    /// Like the other getters of camera settings, this falls back to the makernote tags of
+    /// several manufacturers when the standard Exif tag is missing, as Exiv2's "easy access"
+    /// functions do.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_numeric(rexiv2::tags::exif::SATURATION, 2)?;
+    /// assert_eq!(meta.get_saturation(), Some("High".to_string()));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_saturation(&self) -> Option<String> {
+        self.get_first_interpreted_string(&easy_access::SATURATION)
+    }
+
+    /// Returns the sharpness processing applied by the camera, as a human-readable value.
+    pub fn get_sharpness(&self) -> Option<String> {
+        self.get_first_interpreted_string(&easy_access::SHARPNESS)
+    }
+
+    /// Returns the contrast processing applied by the camera, as a human-readable value.
+    pub fn get_contrast(&self) -> Option<String> {
+        self.get_first_interpreted_string(&easy_access::CONTRAST)
+    }
+
+    /// Returns the white balance setting of the camera, as a human-readable value.
+    ///
+    /// Makernotes are preferred over `Exif.Photo.WhiteBalance` here, since they usually say
+    /// which preset was used rather than just "Auto" or "Manual".
+    pub fn get_white_balance(&self) -> Option<String> {
+        self.get_first_interpreted_string(&easy_access::WHITE_BALANCE)
+    }
+
+    /// Returns the metering mode of the camera, as a human-readable value.
+    pub fn get_metering_mode(&self) -> Option<String> {
+        self.get_first_interpreted_string(&easy_access::METERING_MODE)
+    }
+
+    /// Returns the program the camera used to set the exposure, as a human-readable value.
+    pub fn get_exposure_program(&self) -> Option<String> {
+        self.get_first_interpreted_string(&easy_access::EXPOSURE_PROGRAM)
+    }
+
+    /// Returns whether and how the flash fired, as a human-readable value.
+    pub fn get_flash(&self) -> Option<String> {
+        self.get_first_interpreted_string(&easy_access::FLASH)
+    }
+
+    /// Returns the name of the lens, as a human-readable value.
+    pub fn get_lens_name(&self) -> Option<String> {
+        self.get_first_interpreted_string(&easy_access::LENS_NAME)
+    }
+
+    /// Returns the serial number of the camera body.
+    pub fn get_serial_number(&self) -> Option<String> {
+        self.get_first_interpreted_string(&easy_access::SERIAL_NUMBER)
+    }
+
+    /// Returns the make and model of the camera, from `Exif.Image.Make` and `Exif.Image.Model`,
+    /// or failing those from `Xmp.tiff.Make` and `Xmp.tiff.Model`.
+    ///
+    /// The whitespace and nul bytes some manufacturers pad these tags with are trimmed away.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// use rexiv2::Camera;
+    /// meta.set_tag_string(rexiv2::tags::exif::MAKE, "OLYMPUS IMAGING CORP.  ")?;
+    /// meta.set_tag_string(rexiv2::tags::exif::MODEL, "E-M5")?;
+    /// assert_eq!(
+    ///     meta.get_camera(),
+    ///     Some(Camera {
+    ///         make: Some("OLYMPUS IMAGING CORP.".to_string()),
+    ///         model: Some("E-M5".to_string()),
+    ///     }),
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_camera(&self) -> Option<Camera> {
+        let camera = Camera {
+            make: self.get_trimmed_string(&[tags::exif::MAKE, tags::xmp::TIFF_MAKE]),
+            model: self.get_trimmed_string(&[tags::exif::MODEL, tags::xmp::TIFF_MODEL]),
+        };
+        (camera != Camera::default()).then_some(camera)
+    }
+
+    /// Set the make and model of the camera in `Exif.Image.Make` and `Exif.Image.Model`, and in
+    /// `Xmp.tiff.Make` and `Xmp.tiff.Model` if the file supports XMP. A make or model of `None`
+    /// removes its tags.
+    pub fn set_camera(&self, camera: &Camera) -> Result<()> {
+        self.set_mirrored_string(&[tags::exif::MAKE, tags::xmp::TIFF_MAKE], &camera.make)?;
+        self.set_mirrored_string(&[tags::exif::MODEL, tags::xmp::TIFF_MODEL], &camera.model)
+    }
+
+    /// Returns the name of the software that created the image, from `Exif.Image.Software`, or
+    /// failing that from `Xmp.xmp.CreatorTool`.
+    pub fn get_software(&self) -> Option<String> {
+        self.get_trimmed_string(&[tags::exif::SOFTWARE, tags::xmp::XMP_CREATOR_TOOL])
+    }
+
+    /// Set the name of the software that created the image in `Exif.Image.Software`, and in
+    /// `Xmp.xmp.CreatorTool` if the file supports XMP. An empty name removes them.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_software("rexiv2 example")?;
+    /// assert_eq!(meta.get_tag_string(rexiv2::tags::xmp::XMP_CREATOR_TOOL)?, "rexiv2 example");
+    /// assert_eq!(meta.get_software(), Some("rexiv2 example".to_string()));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_software(&self, software: &str) -> Result<()> {
+        let software = Some(software.to_string()).filter(|software| !software.is_empty());
+        self.set_mirrored_string(
+            &[tags::exif::SOFTWARE, tags::xmp::XMP_CREATOR_TOOL],
+            &software,
+        )
+    }
+
+    /// Returns the name of the camera's owner, from `Exif.Photo.CameraOwnerName`, its XMP
+    /// mirrors `Xmp.exifEX.CameraOwnerName` and `Xmp.aux.OwnerName`, or the owner name Canon
+    /// cameras record in their makernotes.
+    pub fn get_camera_owner(&self) -> Option<String> {
+        self.get_trimmed_string(&OWNER_NAME_TAGS)
+    }
+
+    /// Set the name of the camera's owner in `Exif.Photo.CameraOwnerName`, and in
+    /// `Xmp.exifEX.CameraOwnerName` if the file supports XMP.
+    ///
+    /// An empty name removes every tag `get_camera_owner` reads, including makernote ones, which
+    /// is useful for stripping personal information before sharing an image.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_camera_owner("Jane Doe")?;
+    /// assert_eq!(meta.get_camera_owner(), Some("Jane Doe".to_string()));
+    /// meta.set_camera_owner("")?;
+    /// assert_eq!(meta.get_camera_owner(), None);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_camera_owner(&self, owner: &str) -> Result<()> {
+        if owner.is_empty() {
+            return self.set_mirrored_string(&OWNER_NAME_TAGS, &None);
+        }
+        self.set_mirrored_string(
+            &[
+                tags::exif::CAMERA_OWNER_NAME,
+                tags::xmp::EXIF_EX_CAMERA_OWNER_NAME,
+            ],
+            &Some(owner.to_string()),
+        )
+    }
+
+    /// Returns the name of the photographer, from `Exif.Image.Artist`, or failing that from
+    /// `Xmp.dc.creator`.
+    pub fn get_artist(&self) -> Option<String> {
+        self.get_trimmed_string(&[tags::exif::ARTIST, tags::xmp::DC_CREATOR])
+    }
+
+    /// Set the name of the photographer in `Exif.Image.Artist`, and in `Xmp.dc.creator` if the
+    /// file supports XMP. An empty name removes them.
+    ///
+    /// Any other creators listed in `Xmp.dc.creator` are replaced.
+    pub fn set_artist(&self, artist: &str) -> Result<()> {
+        let artist = Some(artist.to_string()).filter(|artist| !artist.is_empty());
+        self.set_mirrored_string(&[tags::exif::ARTIST, tags::xmp::DC_CREATOR], &artist)
+    }
+
+    /// Returns the identifiers linking the image back to the original it was derived from.
+    pub fn get_provenance(&self) -> Provenance {
+        Provenance {
+            image_unique_id: self.get_trimmed_string(&[tags::exif::IMAGE_UNIQUE_ID]),
+            original_document_id: self
+                .get_trimmed_string(&[tags::xmp::XMP_MM_ORIGINAL_DOCUMENT_ID]),
+            raw_file_name: self.get_trimmed_string(&[tags::xmp::CRS_RAW_FILE_NAME]),
+        }
+    }
+
+    /// Set the identifiers linking the image back to the original it was derived from. Fields
+    /// that are `None` are removed, and the XMP ones are skipped if the file doesn't support XMP.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let provenance = rexiv2::Provenance {
+    ///     image_unique_id: Some("0123456789abcdef0123456789abcdef".to_string()),
+    ///     original_document_id: Some("xmp.did:5bd8c0a0-3f4e-4b8e-9a2b-7f6d5c4b3a21".to_string()),
+    ///     raw_file_name: Some("IMG_0001.CR3".to_string()),
+    /// };
+    /// meta.set_provenance(&provenance)?;
+    /// assert_eq!(meta.get_provenance(), provenance);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_provenance(&self, provenance: &Provenance) -> Result<()> {
+        self.set_mirrored_string(&[tags::exif::IMAGE_UNIQUE_ID], &provenance.image_unique_id)?;
+        self.set_mirrored_string(
+            &[tags::xmp::XMP_MM_ORIGINAL_DOCUMENT_ID],
+            &provenance.original_document_id,
+        )?;
+        self.set_mirrored_string(&[tags::xmp::CRS_RAW_FILE_NAME], &provenance.raw_file_name)
+    }
+
+    /// Returns the interoperability index from `Exif.Iop.InteroperabilityIndex`, such as `R98`
+    /// for files following the DCF basic rules, or `THM` for DCF thumbnail files.
+    pub fn get_interop_index(&self) -> Option<String> {
+        self.get_trimmed_string(&[tags::exif::INTEROPERABILITY_INDEX])
+    }
+
+    /// Set the interoperability index in `Exif.Iop.InteroperabilityIndex`. An empty index
+    /// removes it.
+    ///
+    /// The index must be three ASCII letters or digits, such as `R98`; Exiv2 creates the pointer
+    /// to the interoperability IFD when the file is saved.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_interop_index("R98")?;
+    /// assert_eq!(meta.get_interop_index(), Some("R98".to_string()));
+    /// assert!(meta.set_interop_index("DCF basic").is_err());
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_interop_index(&self, index: &str) -> Result<()> {
+        if index.is_empty() {
+            self.clear_tag(tags::exif::INTEROPERABILITY_INDEX);
+            return Ok(());
+        }
+        if index.len() != 3 || !index.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(Rexiv2Error::InvalidValue(format!(
+                "Invalid interoperability index: {index:?}"
+            )));
+        }
+        self.set_tag_string(tags::exif::INTEROPERABILITY_INDEX, index)
+    }
+
+    /// Returns the version of the interoperability rules from
+    /// `Exif.Iop.InteroperabilityVersion`, such as `0100`.
+    pub fn get_interop_version(&self) -> Option<String> {
+        self.get_tag_string(tags::exif::INTEROPERABILITY_VERSION)
+            .ok()
+            .and_then(|value| decode_undefined_version(&value))
+    }
+
+    /// Set the version of the interoperability rules in `Exif.Iop.InteroperabilityVersion`. An
+    /// empty version removes it.
+    ///
+    /// The version must be four ASCII digits, such as `0100`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_interop_version("0100")?;
+    /// assert_eq!(meta.get_interop_version(), Some("0100".to_string()));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_interop_version(&self, version: &str) -> Result<()> {
+        if version.is_empty() {
+            self.clear_tag(tags::exif::INTEROPERABILITY_VERSION);
+            return Ok(());
+        }
+        if version.len() != 4 || !version.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Rexiv2Error::InvalidValue(format!(
+                "Invalid interoperability version: {version:?}"
+            )));
+        }
+        let bytes: Vec<String> = version.bytes().map(|b| b.to_string()).collect();
+        self.set_tag_string(tags::exif::INTEROPERABILITY_VERSION, &bytes.join(" "))
+    }
+
+    /// Check that the interoperability IFD is consistent, as strict DCF-compliant readers expect.
+    ///
+    /// This fails if the pointer to the interoperability IFD (`Exif.Photo.InteroperabilityTag`)
+    /// leads nowhere, if the IFD lacks an index, or if its index or version are malformed.
+    /// Indices other than those defined by DCF (`R98`, `THM`, and `R03`) are also rejected.
+    /// Having no interoperability IFD at all is fine.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// assert!(meta.validate_interop().is_ok());
+    /// meta.set_interop_version("0100")?;
+    /// assert!(meta.validate_interop().is_err());
+    /// meta.set_interop_index("R98")?;
+    /// assert!(meta.validate_interop().is_ok());
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn validate_interop(&self) -> Result<()> {
+        let invalid = |msg: String| Err(Rexiv2Error::InvalidValue(msg));
+        let has_interop_ifd = self
+            .get_exif_tags()?
+            .iter()
+            .any(|tag| tag.starts_with("Exif.Iop."));
+        if !has_interop_ifd {
+            if self.has_tag(tags::exif::INTEROPERABILITY_TAG) {
+                return invalid("The interoperability IFD pointer leads to an empty IFD".into());
+            }
+            return Ok(());
+        }
+
+        match self.get_interop_index() {
+            None => return invalid("The interoperability IFD has no index".into()),
+            Some(index) if !DCF_INTEROP_INDICES.contains(&index.as_str()) => {
+                return invalid(format!("Unknown interoperability index: {index:?}"));
+            }
+            Some(_) => {}
+        }
+        if self.has_tag(tags::exif::INTEROPERABILITY_VERSION) {
+            let version = self.get_interop_version().unwrap_or_default();
+            if version.len() != 4 || !version.bytes().all(|b| b.is_ascii_digit()) {
+                return invalid(format!("Invalid interoperability version: {version:?}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Read all the values of a tag for editing them by position, including XMP arrays whose
+    /// items are held as separate tags.
+    fn get_indexed_values<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<String>> {
+        if !is_xmp_tag(tag) {
+            return self.get_tag_multiple_strings(tag);
+        }
+        let name = tag.tag_name();
+        let item_prefix = format!("{name}[");
+        // Item qualifiers, like `[1]/?xml:lang`, are fine, but not structure fields.
+        let holds_structures = self.get_xmp_tags()?.iter().any(|item| {
+            item.strip_prefix(&item_prefix)
+                .and_then(|rest| rest.split_once("]/"))
+                .map_or(false, |(_, path)| !path.starts_with('?'))
+        });
+        if holds_structures {
+            return Err(Rexiv2Error::InvalidValue(format!(
+                "{name} is an array of structures, whose items can't be edited by position"
+            )));
+        }
+        let values = iptc_xmp::get_nested_bag(self, name);
+        if values.is_empty() {
+            return Err(Rexiv2Error::NoValue);
+        }
+        Ok(values)
+    }
+
+    /// Change one of the values of a tag that can hold several, by its position.
+    ///
+    /// XMP arrays held as a tag per item are edited in place, so that only the items after the
+    /// position are touched, and their qualifiers are kept. So are arrays whose type Exiv2 doesn't
+    /// know, since it can only hold known array types as a single tag.
+    fn edit_indexed_value<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        index: usize,
+        edit: IndexedEdit,
+    ) -> Result<()> {
+        let mut values = match self.get_indexed_values(tag) {
+            Ok(values) => values,
+            Err(Rexiv2Error::NoValue) if matches!(edit, IndexedEdit::Insert(_)) => vec![],
+            Err(err) => return Err(err),
+        };
+        let in_range = match edit {
+            IndexedEdit::Insert(_) => index <= values.len(),
+            _ => index < values.len(),
+        };
+        if !in_range {
+            return Err(Rexiv2Error::NoValue);
+        }
+
+        let name = tag.tag_name();
+        let held_per_item = is_xmp_tag(tag) && iptc_xmp::struct_array_len(self, name) > 0;
+        let known_array = matches!(
+            get_tag_type(name),
+            Ok(TagType::XmpBag | TagType::XmpSeq | TagType::XmpAlt)
+        );
+        if !is_xmp_tag(tag) || (known_array && !held_per_item) {
+            match edit {
+                IndexedEdit::Replace(value) => values[index] = value.to_string(),
+                IndexedEdit::Insert(value) => values.insert(index, value.to_string()),
+                IndexedEdit::Delete => {
+                    values.remove(index);
+                }
+            }
+            self.clear_tag(tag);
+            if values.is_empty() {
+                return Ok(());
+            }
+            let values: Vec<&str> = values.iter().map(String::as_str).collect();
+            return self.set_tag_multiple_strings(tag, &values);
+        }
+
+        if !held_per_item {
+            self.clear_tag(tag);
+            for (i, value) in values.iter().enumerate() {
+                self.set_tag_string(&format!("{name}[{}]", i + 1), value)?;
+            }
+        }
+        let item = format!("{name}[{}]", index + 1);
+        match edit {
+            IndexedEdit::Replace(value) => self.set_tag_string(&item, value),
+            IndexedEdit::Insert(value) => {
+                self.shift_xmp_items(name, index + 1, true)?;
+                self.set_tag_string(&item, value)
+            }
+            IndexedEdit::Delete => {
+                let qualifier_prefix = format!("{item}/");
+                for tag in self.get_xmp_tags()? {
+                    if tag == item || tag.starts_with(&qualifier_prefix) {
+                        self.clear_tag(&tag);
+                    }
+                }
+                self.shift_xmp_items(name, index + 2, false)?;
+                if values.len() == 1 {
+                    self.clear_tag(name);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Move the items of an XMP array held as a tag per item, from the given (1-based) position
+    /// onwards, one place up or down, taking their qualifiers with them.
+    fn shift_xmp_items(&self, name: &str, from: usize, up: bool) -> Result<()> {
+        let item_prefix = format!("{name}[");
+        let mut moved = vec![];
+        for tag in self.get_xmp_tags()? {
+            let item = tag
+                .strip_prefix(&item_prefix)
+                .and_then(|rest| rest.split_once(']'))
+                .and_then(|(index, path)| Some((index.parse::<usize>().ok()?, path.to_string())));
+            if let Some((index, path)) = item.filter(|(index, _)| *index >= from) {
+                moved.push((index, path, self.get_tag_string(&tag)?));
+                self.clear_tag(&tag);
+            }
+        }
+        // Add the items back in order, since each can only be added after the one before it.
+        moved.sort_by_key(|(index, _, _)| *index);
+        for (index, path, value) in moved {
+            let index = if up { index + 1 } else { index - 1 };
+            self.set_tag_string(&format!("{name}[{index}]{path}"), &value)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the value of the first of the given text tags that isn't blank, without any
+    /// surrounding whitespace or nul bytes.
+    pub(crate) fn get_trimmed_string(&self, tags: &[&str]) -> Option<String> {
+        tags.iter()
+            .find_map(|tag| trim_text(&self.get_tag_string(*tag).ok()?))
+    }
+
+    /// Set a text tag and its mirrors in other domains to the same value, or remove them all if
+    /// the value is `None`. Mirrors in domains the file doesn't support are skipped.
+    fn set_mirrored_string(&self, tags: &[&str], value: &Option<String>) -> Result<()> {
+        for tag in tags {
+            let supported = if is_xmp_tag(*tag) {
+                self.supports_xmp()
+            } else if is_iptc_tag(*tag) {
+                self.supports_iptc()
+            } else {
+                true
+            };
+            match value {
+                Some(value) if supported => self.set_tag_string(*tag, value)?,
+                Some(_) => {}
+                None => {
+                    self.clear_tag(*tag);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Set each of the given text tags to its value, or remove it if the value is `None`.
+    pub(crate) fn set_optional_strings<K: AsRef<str>>(
+        &self,
+        fields: &[(K, &Option<String>)],
+    ) -> Result<()> {
+        for (tag, value) in fields {
+            match value {
+                Some(value) => self.set_tag_string(tag.as_ref(), value)?,
+                None => {
+                    self.clear_tag(tag.as_ref());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the exposure time, aperture, ISO sensitivity, focal length, flash, metering mode,
+    /// exposure program, and lens the photograph was taken with, all at once.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// use rexiv2::tags::exif;
+    /// meta.set_tag_string(exif::EXPOSURE_TIME, "1/250")?;
+    /// meta.set_tag_string(exif::LENS_MODEL, "EF50mm f/1.8 STM")?;
+    /// let settings = meta.get_capture_settings();
+    /// assert_eq!(settings.exposure_time, Some(num_rational::Ratio::new_raw(1, 250)));
+    /// assert_eq!(settings.lens.as_deref(), Some("EF50mm f/1.8 STM"));
+    /// assert_eq!(settings.iso, None);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_capture_settings(&self) -> CaptureSettings {
+        capture_settings::gather(self)
+    }
+
+    /// Returns the exposure value of the photograph normalized to ISO 100 (`EV100`), which
+    /// describes how bright the scene was, from its f-number, exposure time, and ISO speed.
+    ///
+    /// Returns `None` unless all three are known. See the [`apex`] module to work with
+    /// exposure values more directly.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// use rexiv2::tags::exif;
+    /// meta.set_tag_string(exif::F_NUMBER, "4/1")?;
+    /// meta.set_tag_string(exif::EXPOSURE_TIME, "1/8")?;
+    /// assert_eq!(meta.compute_ev(), None);
+    /// meta.set_tag_numeric(exif::ISO_SPEED_RATINGS, 200)?;
+    /// assert_eq!(meta.compute_ev(), Some(6.0));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn compute_ev(&self) -> Option<f64> {
+        let f_number = self.get_fnumber().filter(|f_number| *f_number > 0.0)?;
+        let seconds = self
+            .get_exposure_seconds()
+            .filter(|seconds| *seconds > 0.0)?;
+        let iso = self.get_iso_speed().filter(|iso| *iso > 0)?;
+        Some(apex::ev_at_iso_100(f_number, seconds, f64::from(iso)))
+    }
+
+    /// Returns the interpreted value of the first of the given tags that is set.
+    fn get_first_interpreted_string(&self, tags: &[&str]) -> Option<String> {
+        tags.iter()
+            .filter(|tag| self.has_tag(**tag))
+            .find_map(|tag| self.get_tag_interpreted_string(*tag).ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Returns the value of the first of the given rational tags that is set.
+    fn get_first_rational_as_f64(&self, tags: &[&str]) -> Option<f64> {
+        tags.iter().find_map(|tag| {
+            let values = parse_rationals_as_f64(&self.get_tag_string(*tag).ok()?)?;
+            values.first().copied()
+        })
+    }
+
+    /// Returns the user-assigned star rating of the image from `Xmp.xmp.Rating`, from 0 to 5
+    /// stars, or -1 if the image was rejected.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_rating(4)?;
+    /// assert_eq!(meta.get_rating(), Some(4));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_rating(&self) -> Option<i8> {
+        let rating: f64 = self
+            .get_tag_string(tags::xmp::XMP_RATING)
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        if (-1.0..=5.0).contains(&rating) {
+            Some(rating.round() as i8)
+        } else {
+            None
+        }
+    }
+
+    /// Set the star rating of the image in `Xmp.xmp.Rating`, from 0 to 5 stars, or -1 to mark it
+    /// as rejected.
+    pub fn set_rating(&self, rating: i8) -> Result<()> {
+        if !(-1..=5).contains(&rating) {
+            return Err(Rexiv2Error::InvalidValue(format!(
+                "Rating must be between -1 and 5, not {rating}"
+            )));
+        }
+        self.set_tag_string(tags::xmp::XMP_RATING, &rating.to_string())
+    }
+
+    /// Returns the colour label of the image from `Xmp.xmp.Label`.
+    ///
+    /// Adobe applications use `Red`, `Yellow`, `Green`, `Blue`, and `Purple` by default, but the
+    /// label can be any text.
+    pub fn get_color_label(&self) -> Option<String> {
+        self.get_tag_string(tags::xmp::XMP_LABEL)
+            .ok()
+            .filter(|label| !label.is_empty())
+    }
+
+    /// Set the colour label of the image in `Xmp.xmp.Label`. An empty label removes it.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_color_label("Green")?;
+    /// assert_eq!(meta.get_color_label(), Some("Green".to_string()));
+    /// meta.set_color_label("")?;
+    /// assert_eq!(meta.get_color_label(), None);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_color_label(&self, label: &str) -> Result<()> {
+        if label.is_empty() {
+            self.clear_tag(tags::xmp::XMP_LABEL);
+            Ok(())
+        } else {
+            self.set_tag_string(tags::xmp::XMP_LABEL, label)
+        }
+    }
+
+    /// Returns whether the image was picked or rejected during culling.
+    ///
+    /// This reads `Xmp.xmpDM.pick` (1 for picked, -1 for rejected), and also treats a rating of -1
+    /// as a rejection, which is how Adobe Bridge records it.
+    pub fn get_pick_flag(&self) -> PickFlag {
+        let pick = self.get_tag_string(tags::xmp::XMP_DM_PICK).ok();
+        match pick.as_deref().map(str::trim) {
+            Some("1") => PickFlag::Picked,
+            Some("-1") => PickFlag::Rejected,
+            _ if self.get_rating() == Some(-1) => PickFlag::Rejected,
+            _ => PickFlag::Unflagged,
+        }
+    }
+
+    /// Record whether the image was picked or rejected during culling.
+    ///
+    /// Along with `Xmp.xmpDM.pick`, rejecting an image sets its rating to -1, and un-rejecting it
+    /// removes that rating, so that Adobe Bridge and Lightroom agree with the flag.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// use rexiv2::PickFlag;
+    /// meta.set_pick_flag(PickFlag::Rejected)?;
+    /// assert_eq!(meta.get_pick_flag(), PickFlag::Rejected);
+    /// assert_eq!(meta.get_rating(), Some(-1));
+    /// meta.set_pick_flag(PickFlag::Picked)?;
+    /// assert_eq!(meta.get_pick_flag(), PickFlag::Picked);
+    /// assert_eq!(meta.get_rating(), None);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_pick_flag(&self, flag: PickFlag) -> Result<()> {
+        if flag != PickFlag::Rejected && self.get_rating() == Some(-1) {
+            self.clear_tag(tags::xmp::XMP_RATING);
+        }
+        match flag {
+            PickFlag::Unflagged => {
+                self.clear_tag(tags::xmp::XMP_DM_PICK);
+                Ok(())
+            }
+            PickFlag::Picked => self.set_tag_string(tags::xmp::XMP_DM_PICK, "1"),
+            PickFlag::Rejected => {
+                self.set_tag_string(tags::xmp::XMP_DM_PICK, "-1")?;
+                self.set_rating(-1)
+            }
+        }
+    }
+
+    /// Returns the details identifying the shot the image came from: its capture time, camera
+    /// serial number, and unique image ID. See `group_duplicates()` for finding duplicates and
+    /// bursts with them.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Photo.DateTimeOriginal", "2024:05:01 10:00:00")?;
+    /// meta.set_tag_string("Exif.Photo.BodySerialNumber", "0123456789")?;
+    /// let signature = meta.content_signature();
+    /// assert_eq!(signature.camera_serial.as_deref(), Some("0123456789"));
+    /// assert_eq!(signature.image_unique_id, None);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn content_signature(&self) -> ContentSignature {
+        ContentSignature::read_from(self)
+    }
+
+    /// Returns a hash of all the Exif, IPTC, and XMP tags and their values, for cheaply checking
+    /// whether the metadata has changed.
+    ///
+    /// The hash is deterministic: it doesn't depend on the order tags were added in, and is the
+    /// same across runs and platforms for the same metadata. Tags that change every time a file is
+    /// saved, `Xmp.xmpMM.InstanceID` and `Xmp.xmp.MetadataDate`, are left out. It is not a
+    /// cryptographic hash, so it mustn't be relied on to detect deliberate tampering.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let before = meta.fingerprint()?;
+    /// meta.set_tag_string("Xmp.xmpMM.InstanceID", "xmp.iid:0001")?;
+    /// assert_eq!(meta.fingerprint()?, before);
+    /// meta.set_tag_string("Xmp.dc.subject", "fox")?;
+    /// assert_ne!(meta.fingerprint()?, before);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn fingerprint(&self) -> Result<u64> {
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut feed = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash = (hash ^ byte as u64).wrapping_mul(FNV_PRIME);
+            }
+        };
+        for (name, values) in self.tag_values()? {
+            if VOLATILE_TAGS.contains(&name.as_str()) {
+                continue;
+            }
+            feed(name.as_bytes());
+            feed(&(values.len() as u64).to_le_bytes());
+            for value in values {
+                feed(&(value.len() as u64).to_le_bytes());
+                feed(&value);
+            }
+        }
+        Ok(hash)
+    }
+
+    /// Returns the names of all the Exif, IPTC, and XMP tags, in order, along with their raw
+    /// values.
+    fn tag_values(&self) -> Result<Vec<(String, Vec<Vec<u8>>)>> {
+        let mut names = self.get_exif_tags()?;
+        names.extend(self.get_iptc_tags()?);
+        names.extend(self.get_xmp_tags()?);
+        names.sort_unstable();
+        names
+            .into_iter()
+            .map(|name| match self.get_tag_multiple_bytes(&name) {
+                Ok(values) => Ok((name, values)),
+                Err(Rexiv2Error::NoValue) => Ok((name, vec![])),
+                Err(err) => Err(err),
+            })
+            .collect()
+    }
+
+    // Keyword related methods.
+
+    /// Returns the hierarchical keywords stored in `Xmp.lr.hierarchicalSubject`. Entries that
+    /// aren't valid hierarchical keywords, such as empty strings, are skipped.
+    ///
+    /// To also include the flat keywords in `Xmp.dc.subject` that aren't part of any hierarchy,
+    /// pass both lists to `HierarchicalKeyword::from_flat()`.
+    pub fn get_hierarchical_keywords(&self) -> Vec<HierarchicalKeyword> {
+        self.get_tag_multiple_strings(tags::xmp::LR_HIERARCHICAL_SUBJECT)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|keyword| keyword.parse().ok())
+            .collect()
+    }
+
+    /// Replace the hierarchical keywords stored in `Xmp.lr.hierarchicalSubject`, and add every
+    /// level of them to the flat keywords in `Xmp.dc.subject` that aren't there yet.
+    ///
+    /// Existing flat keywords are kept, even if they came from hierarchical keywords that have now
+    /// been removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let owl: rexiv2::HierarchicalKeyword = "Animals|Birds|Owl".parse()?;
+    /// meta.set_tag_multiple_strings("Xmp.dc.subject", &["Night"])?;
+    /// meta.set_hierarchical_keywords(&[owl.clone()])?;
+    /// assert_eq!(meta.get_hierarchical_keywords(), [owl]);
+    /// assert_eq!(
+    ///     meta.get_tag_multiple_strings("Xmp.dc.subject")?,
+    ///     ["Night", "Animals", "Birds", "Owl"],
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_hierarchical_keywords(&self, keywords: &[HierarchicalKeyword]) -> Result<()> {
+        if keywords.is_empty() {
+            self.clear_tag(tags::xmp::LR_HIERARCHICAL_SUBJECT);
+            return Ok(());
+        }
+        let paths: Vec<String> = keywords.iter().map(ToString::to_string).collect();
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+        self.set_tag_multiple_strings(tags::xmp::LR_HIERARCHICAL_SUBJECT, &paths)?;
+        let flat = HierarchicalKeyword::flatten(keywords);
+        let flat: Vec<&str> = flat.iter().map(String::as_str).collect();
+        self.set_tag_multiple_strings_with_mode(tags::xmp::DC_SUBJECT, &flat, WriteMode::Unique)
+    }
+
+    // Collection related methods.
+
+    /// Returns the collections, such as albums, that the image belongs to, from
+    /// `Xmp.mwg-coll.Collections`. Collections with neither a name nor a URI are skipped.
+    pub fn get_collections(&self) -> Vec<Collection> {
+        Collection::read_all(self)
+    }
+
+    /// Replace the collections the image belongs to, stored in `Xmp.mwg-coll.Collections`.
+    /// Passing an empty slice removes them all.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let album = rexiv2::Collection {
+    ///     name: Some("Summer 2024".to_string()),
+    ///     uri: Some("urn:uuid:5d3f0c2e-8a52-4f3c-9b1e-0f6a2d7c4e11".to_string()),
+    /// };
+    /// meta.set_collections(&[album.clone()])?;
+    /// assert_eq!(
+    ///     meta.get_tag_string("Xmp.mwg-coll.Collections[1]/mwg-coll:CollectionName")?,
+    ///     "Summer 2024",
+    /// );
+    /// assert_eq!(meta.get_collections(), [album]);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_collections(&self, collections: &[Collection]) -> Result<()> {
+        Collection::write_all(self, collections)
+    }
+
+    // Date and time related methods.
+
+    /// Returns the moment the photograph was taken, combining `Exif.Photo.DateTimeOriginal`
+    /// with the fractional seconds in `SubSecTimeOriginal` and the UTC offset in
+    /// `OffsetTimeOriginal`, where those are present and valid.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Photo.DateTimeOriginal", "2022:08:07 11:19:44")?;
+    /// meta.set_tag_string("Exif.Photo.SubSecTimeOriginal", "25")?;
+    /// meta.set_tag_string("Exif.Photo.OffsetTimeOriginal", "+02:00")?;
+    /// let timestamp = meta.get_capture_timestamp()?;
+    /// assert_eq!(timestamp.to_string(), "2022:08:07 11:19:44.25+02:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_capture_timestamp(&self) -> Result<ExifDateTime> {
+        let mut timestamp = self.get_tag_datetime(tags::exif::DATE_TIME_ORIGINAL)?;
+        self.add_exif_subsec_and_offset(
+            &mut timestamp,
+            tags::exif::SUB_SEC_TIME_ORIGINAL,
+            tags::exif::OFFSET_TIME_ORIGINAL,
+        );
+        Ok(timestamp)
+    }
+
+    /// Set the moment the photograph was taken, splitting it between
+    /// `Exif.Photo.DateTimeOriginal`, `SubSecTimeOriginal`, and `OffsetTimeOriginal`.
+    ///
+    /// If the timestamp has no fractional seconds or offset, the corresponding tag is removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let timestamp: rexiv2::ExifDateTime = "2022:08:07 11:19:44.5-05:00".parse()?;
+    /// meta.set_capture_timestamp(&timestamp)?;
+    /// assert_eq!(meta.get_tag_string("Exif.Photo.SubSecTimeOriginal")?, "5");
+    /// assert_eq!(meta.get_tag_string("Exif.Photo.OffsetTimeOriginal")?, "-05:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_capture_timestamp(&self, timestamp: &ExifDateTime) -> Result<()> {
+        self.set_exif_timestamp(
+            timestamp,
+            tags::exif::DATE_TIME_ORIGINAL,
+            tags::exif::SUB_SEC_TIME_ORIGINAL,
+            tags::exif::OFFSET_TIME_ORIGINAL,
+        )
+    }
+
+    /// Find the date the photo was taken by trying each of the policy's sources in turn, returning
+    /// the first valid date along with the source it came from.
+    ///
+    /// Dates without a time of day, as XMP and IPTC allow, are taken to be at midnight.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::{CaptureDatePolicy, CaptureDateSource};
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Iptc.Application2.DateCreated", "2022-08-07")?;
+    /// let (date, source) = meta.resolve_capture_date(&CaptureDatePolicy::default()).unwrap();
+    /// assert_eq!(date.to_string(), "2022:08:07 00:00:00");
+    /// assert_eq!(source, CaptureDateSource::Iptc);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn resolve_capture_date(
+        &self,
+        policy: &CaptureDatePolicy,
+    ) -> Option<(ExifDateTime, CaptureDateSource)> {
+        policy.sources().iter().find_map(|&source| {
+            let date = match source {
+                CaptureDateSource::ExifOriginal => self.get_capture_timestamp().ok(),
+                CaptureDateSource::ExifDigitized => self.get_exif_timestamp(
+                    tags::exif::DATE_TIME_DIGITIZED,
+                    tags::exif::SUB_SEC_TIME_DIGITIZED,
+                    tags::exif::OFFSET_TIME_DIGITIZED,
+                ),
+                CaptureDateSource::ExifModified => self.get_exif_timestamp(
+                    tags::exif::DATE_TIME,
+                    tags::exif::SUB_SEC_TIME,
+                    tags::exif::OFFSET_TIME,
+                ),
+                CaptureDateSource::XmpOriginal => {
+                    self.get_xmp_date(tags::xmp::EXIF_DATE_TIME_ORIGINAL)
+                }
+                CaptureDateSource::XmpDateCreated => {
+                    self.get_xmp_date(tags::xmp::PHOTOSHOP_DATE_CREATED)
+                }
+                CaptureDateSource::XmpCreateDate => self.get_xmp_date(tags::xmp::XMP_CREATE_DATE),
+                CaptureDateSource::Iptc => self
+                    .get_iptc_datetime(tags::iptc::DATE_CREATED, tags::iptc::TIME_CREATED)
+                    .ok(),
+                CaptureDateSource::Gps => self.get_gps_timestamp().ok().flatten(),
+                CaptureDateSource::Fallback => policy.fallback_date().copied(),
+            };
+            date.map(|date| (date, source))
+        })
+    }
+
+    /// Set every tag recording when the image was created or changed to the same timestamp.
+    ///
+    /// This covers `Exif.Image.DateTime`, `Exif.Photo.DateTimeOriginal`, and
+    /// `Exif.Photo.DateTimeDigitized`, along with their fractional seconds and offset tags;
+    /// `Xmp.xmp.CreateDate` and `Xmp.exif.DateTimeOriginal`; and `Iptc.Application2.DateCreated`
+    /// and `TimeCreated`. The XMP and IPTC tags are only written if the file supports them.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let timestamp: rexiv2::ExifDateTime = "2022:08:07 11:19:44+02:00".parse()?;
+    /// meta.set_all_datetimes(&timestamp)?;
+    /// assert_eq!(meta.get_tag_string("Exif.Photo.DateTimeDigitized")?, "2022:08:07 11:19:44");
+    /// assert_eq!(meta.get_tag_string("Xmp.xmp.CreateDate")?, "2022-08-07T11:19:44+02:00");
+    /// assert_eq!(meta.get_tag_string("Iptc.Application2.TimeCreated")?, "11:19:44+02:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_all_datetimes(&self, timestamp: &ExifDateTime) -> Result<()> {
+        timestamp.validate()?;
+        self.set_exif_timestamp(
+            timestamp,
+            tags::exif::DATE_TIME,
+            tags::exif::SUB_SEC_TIME,
+            tags::exif::OFFSET_TIME,
+        )?;
+        self.set_capture_timestamp(timestamp)?;
+        self.set_exif_timestamp(
+            timestamp,
+            tags::exif::DATE_TIME_DIGITIZED,
+            tags::exif::SUB_SEC_TIME_DIGITIZED,
+            tags::exif::OFFSET_TIME_DIGITIZED,
+        )?;
+        if self.supports_xmp() {
+            let xmp = timestamp.to_xmp_string();
+            self.set_tag_string(tags::xmp::XMP_CREATE_DATE, &xmp)?;
+            self.set_tag_string(tags::xmp::EXIF_DATE_TIME_ORIGINAL, &xmp)?;
+        }
+        if self.supports_iptc() {
+            self.set_iptc_datetime(
+                tags::iptc::DATE_CREATED,
+                tags::iptc::TIME_CREATED,
+                timestamp,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Split a timestamp between an Exif date/time tag and its fractional seconds and offset
+    /// tags, removing those if the timestamp doesn't include them.
+    fn set_exif_timestamp(
+        &self,
+        timestamp: &ExifDateTime,
+        date_tag: &str,
+        subsec_tag: &str,
+        offset_tag: &str,
+    ) -> Result<()> {
+        self.set_tag_datetime(date_tag, timestamp)?;
+        match timestamp.subsec_string() {
+            Some(subsec) => self.set_tag_string(subsec_tag, &subsec)?,
+            None => {
+                self.clear_tag(subsec_tag);
+            }
+        }
+        match timestamp.offset_string() {
+            Some(offset) => self.set_tag_string(offset_tag, &offset)?,
+            None => {
+                self.clear_tag(offset_tag);
+            }
+        }
+        Ok(())
+    }
+
+    /// Move all the selected date/time tags by a number of seconds, which may be negative, such
+    /// as to correct for a camera clock that was set wrong.
+    ///
+    /// Tags that aren't present are skipped, as are blank (unknown) Exif dates and XMP dates
+    /// without a time of day. If any other selected tag can't be parsed or shifted, an error is
+    /// returned and nothing is changed.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Photo.DateTimeOriginal", "2022:08:07 23:19:44")?;
+    /// meta.set_tag_string("Xmp.exif.DateTimeOriginal", "2022-08-07T23:19:44+02:00")?;
+    /// meta.shift_datetimes(2 * 60 * 60, rexiv2::DateTimeSet::CAPTURE)?;
+    /// assert_eq!(meta.get_tag_string("Exif.Photo.DateTimeOriginal")?, "2022:08:08 01:19:44");
+    /// assert_eq!(meta.get_tag_string("Xmp.exif.DateTimeOriginal")?, "2022-08-08T01:19:44+02:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn shift_datetimes(&self, seconds: i64, which: DateTimeSet) -> Result<()> {
+        let mut updates = vec![];
+        let mut xmp_tags = vec![];
+        for tag in which.local_tags() {
+            if tag.starts_with("Xmp.") {
+                xmp_tags.push(tag);
+            } else if self.has_tag(tag) {
+                let value = self.get_tag_string(tag)?;
+                if !datetime::is_blank(&value) {
+                    let date = value.parse::<ExifDateTime>()?.shifted(seconds)?;
+                    updates.push((tag, date.to_exif_string()));
+                }
+            }
+        }
+        if which.gps {
+            xmp_tags.push(tags::xmp::EXIF_GPS_TIME_STAMP);
+            if let Some(timestamp) = self.get_gps_timestamp()? {
+                let timestamp = timestamp.shifted(seconds)?;
+                updates.push((
+                    tags::exif::GPS_DATE_STAMP,
+                    timestamp.to_exif_string()[..10].to_string(),
+                ));
+                updates.push((
+                    tags::exif::GPS_TIME_STAMP,
+                    gps_time_stamp_string(&timestamp),
+                ));
+            }
+        }
+        for tag in xmp_tags {
+            if self.has_tag(tag) {
+                let value = self.get_tag_string(tag)?;
+                if value.contains('T') {
+                    let date = ExifDateTime::from_xmp_str(&value)?.shifted(seconds)?;
+                    updates.push((tag, date.to_xmp_string()));
+                }
+            }
+        }
+
+        for (tag, value) in updates {
+            self.set_tag_string(tag, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Record the offset from UTC of the Exif dates in the `OffsetTime`, `OffsetTimeOriginal`,
+    /// and `OffsetTimeDigitized` tags, optionally converting the stored local times to it.
+    ///
+    /// Without `convert_from`, the stored times are kept and simply marked as being in the given
+    /// offset. With it, each time is converted from the offset in its existing `OffsetTime` tag,
+    /// or from `convert_from` if it has none; this fixes photos taken with the camera clock still
+    /// set to the time at home. Only dates that are present are affected; the GPS tags are
+    /// always in UTC, so are left alone.
+    ///
+    /// Either offset being a whole day or more gives an `InvalidValue` error, and nothing is
+    /// changed.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Exif.Photo.DateTimeOriginal", "2022:08:07 11:19:44")?;
+    /// // The camera was still set to UTC+1, but the photo was taken in UTC+9.
+    /// meta.set_utc_offset(9 * 60, Some(60))?;
+    /// assert_eq!(meta.get_tag_string("Exif.Photo.DateTimeOriginal")?, "2022:08:07 19:19:44");
+    /// assert_eq!(meta.get_tag_string("Exif.Photo.OffsetTimeOriginal")?, "+09:00");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_utc_offset(&self, offset_minutes: i16, convert_from: Option<i16>) -> Result<()> {
+        for offset in [Some(offset_minutes), convert_from].into_iter().flatten() {
+            if offset.unsigned_abs() >= 24 * 60 {
+                return Err(Rexiv2Error::InvalidValue(format!(
+                    "Invalid UTC offset: {offset} minutes"
+                )));
+            }
+        }
+        let offset = datetime::format_offset(offset_minutes);
+
+        let mut updates = vec![];
+        for (date_tag, offset_tag) in [
+            (tags::exif::DATE_TIME, tags::exif::OFFSET_TIME),
+            (
+                tags::exif::DATE_TIME_ORIGINAL,
+                tags::exif::OFFSET_TIME_ORIGINAL,
+            ),
+            (
+                tags::exif::DATE_TIME_DIGITIZED,
+                tags::exif::OFFSET_TIME_DIGITIZED,
+            ),
+        ] {
+            if !self.has_tag(date_tag) || datetime::is_blank(&self.get_tag_string(date_tag)?) {
+                continue;
+            }
+            if let Some(convert_from) = convert_from {
+                let from = self
+                    .get_tag_string(offset_tag)
+                    .ok()
+                    .and_then(|from| datetime::parse_offset(&from))
+                    .unwrap_or(convert_from);
+                let shift = (offset_minutes as i64 - from as i64) * 60;
+                let date = self.get_tag_datetime(date_tag)?.shifted(shift)?;
+                updates.push((date_tag, date.to_exif_string()));
+            }
+            updates.push((offset_tag, offset.clone()));
+        }
+
+        for (tag, value) in updates {
+            self.set_tag_string(tag, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Read an Exif date/time tag along with its fractional seconds and offset tags.
+    fn get_exif_timestamp(
+        &self,
+        date_tag: &str,
+        subsec_tag: &str,
+        offset_tag: &str,
+    ) -> Option<ExifDateTime> {
+        let mut timestamp = self.get_tag_datetime(date_tag).ok()?;
+        self.add_exif_subsec_and_offset(&mut timestamp, subsec_tag, offset_tag);
+        Some(timestamp)
+    }
+
+    /// Fill in the fractional seconds and offset of a timestamp from the given Exif tags, where
+    /// those are present and valid.
+    fn add_exif_subsec_and_offset(
+        &self,
+        timestamp: &mut ExifDateTime,
+        subsec_tag: &str,
+        offset_tag: &str,
+    ) {
+        timestamp.nanosecond = self
+            .get_tag_string(subsec_tag)
+            .ok()
+            .and_then(|subsec| datetime::parse_subsec(&subsec));
+        timestamp.offset_minutes = self
+            .get_tag_string(offset_tag)
+            .ok()
+            .and_then(|offset| datetime::parse_offset(&offset));
+    }
+
+    /// Read an XMP date tag, which may lack a time of day.
+    fn get_xmp_date(&self, tag: &str) -> Option<ExifDateTime> {
+        datetime::parse_xmp_date(&self.get_tag_string(tag).ok()?)
+    }
+
+    /// Combine the Exif GPS date and time stamps, if both are present.
+    fn get_gps_timestamp(&self) -> Result<Option<ExifDateTime>> {
+        if !self.has_tag(tags::exif::GPS_DATE_STAMP) || !self.has_tag(tags::exif::GPS_TIME_STAMP) {
+            return Ok(None);
+        }
+        let invalid = || Rexiv2Error::InvalidValue("Invalid GPS date/time stamp".to_string());
+        let date = self.get_tag_string(tags::exif::GPS_DATE_STAMP)?;
+        let time = parse_rationals_as_f64(&self.get_tag_string(tags::exif::GPS_TIME_STAMP)?)
+            .filter(|time| time.len() == 3)
+            .ok_or_else(invalid)?;
+        let seconds = time[0] * 3600.0 + time[1] * 60.0 + time[2];
+        let mut timestamp: ExifDateTime = format!("{} 00:00:00", date.trim()).parse()?;
+        timestamp = timestamp
+            .with_local_seconds(timestamp.local_seconds() + seconds.trunc() as i64)
+            .ok_or_else(invalid)?;
+        let nanosecond = (seconds.fract() * 1e9).round() as u32;
+        timestamp.nanosecond = Some(nanosecond).filter(|&ns| ns > 0 && ns < 1_000_000_000);
+        Ok(Some(timestamp))
+    }
+
+    // IPTC character set handling.
+
+    /// Find out which character set the IPTC text is declared to be in.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// assert_eq!(meta.get_iptc_charset(), rexiv2::IptcCharset::Undeclared);
+    /// meta.set_tag_string("Iptc.Envelope.CharacterSet", "\x1b%G")?;
+    /// assert_eq!(meta.get_iptc_charset(), rexiv2::IptcCharset::Utf8);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_iptc_charset(&self) -> IptcCharset {
+        match self.get_tag_bytes(tags::iptc::CHARACTER_SET) {
+            Ok(charset) if charset == IPTC_UTF8 => IptcCharset::Utf8,
+            Ok(charset) if !charset.is_empty() => IptcCharset::Other(charset),
+            _ => IptcCharset::Undeclared,
+        }
+    }
+
+    /// Get the value of an IPTC tag as a string, decoding it according to the declared
+    /// character set.
+    ///
+    /// Unlike `get_tag_string()`, this copes with legacy files holding Latin-1 text: if no
+    /// character set is declared, values that aren't valid UTF-8 are decoded as Latin-1. Any other
+    /// declared character set gives an `Unsupported` error, rather than risking garbled text.
+    pub fn get_iptc_tag_string<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<String> {
+        decode_iptc(&self.get_tag_bytes(tag)?, &self.get_iptc_charset())
+    }
+
+    /// Get all the values of a repeatable IPTC tag as strings, decoding them in the same way as
+    /// `get_iptc_tag_string()`.
+    pub fn get_iptc_tag_multiple_strings<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+    ) -> Result<Vec<String>> {
+        let charset = self.get_iptc_charset();
+        self.get_tag_multiple_bytes(tag)?
+            .iter()
+            .map(|value| decode_iptc(value, &charset))
+            .collect()
+    }
+
+    /// Re-encode all IPTC text as UTF-8, and declare it as such.
+    ///
+    /// Text is decoded as described for `get_iptc_tag_string()`. Nothing is done if UTF-8 is
+    /// already declared, and an `Unsupported` error is returned, leaving every tag as it was, if
+    /// a character set other than UTF-8 is declared.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string("Iptc.Application2.Caption", "Café")?;
+    /// meta.convert_iptc_to_utf8()?;
+    /// assert_eq!(meta.get_iptc_charset(), rexiv2::IptcCharset::Utf8);
+    /// assert_eq!(meta.get_iptc_tag_string("Iptc.Application2.Caption")?, "Café");
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn convert_iptc_to_utf8(&self) -> Result<()> {
+        let charset = match self.get_iptc_charset() {
+            IptcCharset::Utf8 => return Ok(()),
+            IptcCharset::Other(escape) => return Err(unsupported_iptc_charset(&escape)),
+            charset => charset,
+        };
+
+        let mut names = self.get_iptc_tags()?;
+        names.dedup();
+        for tag in names {
+            if tag == tags::iptc::CHARACTER_SET || get_tag_type(&tag)? != TagType::String {
+                continue;
+            }
+            let values = self
+                .get_tag_multiple_bytes(&tag)?
+                .iter()
+                .map(|value| decode_iptc(value, &charset))
+                .collect::<Result<Vec<_>>>()?;
+            let values: Vec<&str> = values.iter().map(String::as_str).collect();
+            self.set_tag_multiple_strings(&tag, &values)?;
+        }
+        self.set_tag_string(tags::iptc::CHARACTER_SET, "\x1b%G")
+    }
+
+    /// Get the value of a tag as the bytes gexiv2 returns, without decoding it.
+    fn get_tag_bytes<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<u8>> {
+        let c_str_tag = tag.tag_c_str()?;
+        unsafe {
+            let c_str_val = gexiv2::gexiv2_metadata_get_tag_string(self.raw, c_str_tag.as_ptr());
+            if c_str_val.is_null() {
+                return Err(Rexiv2Error::NoValue);
+            }
+            let value = ffi::CStr::from_ptr(c_str_val).to_bytes().to_vec();
+            libc::free(c_str_val as *mut libc::c_void);
+            Ok(value)
+        }
+    }
+
+    /// Get all the values of a tag as the bytes gexiv2 returns, without decoding them.
+    fn get_tag_multiple_bytes<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<Vec<u8>>> {
+        let c_str_tag = tag.tag_c_str()?;
+        let mut vals = vec![];
+        unsafe {
+            let c_vals = gexiv2::gexiv2_metadata_get_tag_multiple(self.raw, c_str_tag.as_ptr());
+            if c_vals.is_null() {
+                return Err(Rexiv2Error::NoValue);
+            }
+            let mut cur_offset = 0;
+            while !(*c_vals.offset(cur_offset)).is_null() {
+                vals.push(
+                    ffi::CStr::from_ptr(*c_vals.offset(cur_offset))
+                        .to_bytes()
+                        .to_vec(),
+                );
+                cur_offset += 1;
+            }
+            free_array_of_pointers(c_vals as *mut *mut libc::c_void);
+        }
+        Ok(vals)
+    }
+
+    // Accessibility related methods.
+
+    /// Returns the short alternative text describing the image for people who can't see it, from
+    /// the IPTC Core `AltTextAccessibility` tag.
+    ///
+    /// The text in the given language (such as `en-GB`) is preferred, then the default text, then
+    /// the text in any other language.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_alt_text("x-default", "A red fox leaping over a fallen log")?;
+    /// assert_eq!(
+    ///     meta.get_alt_text("de-DE"),
+    ///     Some("A red fox leaping over a fallen log".to_string()),
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_alt_text(&self, lang: &str) -> Option<String> {
+        pick_lang_alt(self.get_tag_lang_alt(tags::xmp::IPTC_ALT_TEXT).ok()?, lang)
+    }
+
+    /// Set the alternative text describing the image in the given language (such as `x-default`),
+    /// in the IPTC Core `AltTextAccessibility` tag. Empty text removes that language.
+    pub fn set_alt_text(&self, lang: &str, text: &str) -> Result<()> {
+        self.set_tag_lang_alt(tags::xmp::IPTC_ALT_TEXT, lang, text)
+    }
+
+    /// Returns the extended description of the image for people who can't see it, from the IPTC
+    /// Core `ExtDescrAccessibility` tag, for when the alternative text isn't enough.
+    ///
+    /// Languages are chosen as for `get_alt_text()`.
+    pub fn get_extended_description(&self, lang: &str) -> Option<String> {
+        pick_lang_alt(self.get_tag_lang_alt(tags::xmp::IPTC_EXT_DESCR).ok()?, lang)
+    }
+
+    /// Set the extended description of the image in the given language, in the IPTC Core
+    /// `ExtDescrAccessibility` tag. Empty text removes that language.
+    pub fn set_extended_description(&self, lang: &str, text: &str) -> Result<()> {
+        self.set_tag_lang_alt(tags::xmp::IPTC_EXT_DESCR, lang, text)
+    }
+
+    // IPTC Extension related methods.
+
+    /// Returns the locations where the content was created, from the IPTC Extension
+    /// `LocationCreated` structures. Locations without any fields set are skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// let location = rexiv2::IptcLocation {
+    ///     city: Some("Halifax".to_string()),
+    ///     country_code: Some("CA".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// meta.set_locations_created(&[location.clone()])?;
+    /// assert_eq!(
+    ///     meta.get_tag_string("Xmp.iptcExt.LocationCreated[1]/Iptc4xmpExt:City")?,
+    ///     "Halifax",
+    /// );
+    /// assert_eq!(meta.get_locations_created(), vec![location]);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_locations_created(&self) -> Vec<IptcLocation> {
+        IptcLocation::read_array(self, tags::xmp::IPTC_EXT_LOCATION_CREATED)
+    }
+
+    /// Replace the locations where the content was created, stored as IPTC Extension
+    /// `LocationCreated` structures. Passing an empty slice removes them all.
+    pub fn set_locations_created(&self, locations: &[IptcLocation]) -> Result<()> {
+        IptcLocation::write_array(self, tags::xmp::IPTC_EXT_LOCATION_CREATED, locations)
+    }
+
+    /// Returns the locations shown in the content, from the IPTC Extension `LocationShown`
+    /// structures. Locations without any fields set are skipped.
+    pub fn get_locations_shown(&self) -> Vec<IptcLocation> {
+        IptcLocation::read_array(self, tags::xmp::IPTC_EXT_LOCATION_SHOWN)
+    }
+
+    /// Replace the locations shown in the content, stored as IPTC Extension `LocationShown`
+    /// structures. Passing an empty slice removes them all.
+    pub fn set_locations_shown(&self, locations: &[IptcLocation]) -> Result<()> {
+        IptcLocation::write_array(self, tags::xmp::IPTC_EXT_LOCATION_SHOWN, locations)
+    }
+
+    /// Returns the names of the people shown in the content, from the IPTC Extension
+    /// `PersonInImage` tag.
+    pub fn get_persons_in_image(&self) -> Vec<String> {
+        self.get_tag_multiple_strings(tags::xmp::IPTC_EXT_PERSON_IN_IMAGE)
+            .unwrap_or_default()
+    }
+
+    /// Replace the names of the people shown in the content, stored in the IPTC Extension
+    /// `PersonInImage` tag. Passing an empty slice removes them all.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_persons_in_image(&["Ada Lovelace", "Charles Babbage"])?;
+    /// assert_eq!(meta.get_persons_in_image(), ["Ada Lovelace", "Charles Babbage"]);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_persons_in_image(&self, names: &[&str]) -> Result<()> {
+        if names.is_empty() {
+            self.clear_tag(tags::xmp::IPTC_EXT_PERSON_IN_IMAGE);
+            Ok(())
+        } else {
+            self.set_tag_multiple_strings(tags::xmp::IPTC_EXT_PERSON_IN_IMAGE, names)
+        }
+    }
+
+    /// Returns the people shown in the content, with their details, from the IPTC Extension
+    /// `PersonInImageWDetails` structures. People without any details set are skipped.
+    pub fn get_persons_shown(&self) -> Vec<PersonShown> {
+        PersonShown::read_all(self)
+    }
 
-    /// Find out the orientation the image should have, according to the metadata tag.
+    /// Replace the people shown in the content, stored as IPTC Extension `PersonInImageWDetails`
+    /// structures. Passing an empty slice removes them all.
+    ///
+    /// The simpler list of names returned by `get_persons_in_image()` is left unchanged.
     ///
     /// # Examples
     /// ```
@@ -969,13 +4718,28 @@ impl Metadata {
     /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
     /// #               69, 78, 68, 174, 66, 96, 130];
     /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
-    /// assert_eq!(meta.get_orientation(), rexiv2::Orientation::Unspecified);
+    /// let person = rexiv2::PersonShown {
+    ///     name: Some("Ada Lovelace".to_string()),
+    ///     identifiers: vec!["https://www.wikidata.org/wiki/Q7259".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// meta.set_persons_shown(&[person.clone()])?;
+    /// assert_eq!(meta.get_persons_shown(), [person]);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
     /// ```
-    pub fn get_orientation(&self) -> Orientation {
-        unsafe { gexiv2::gexiv2_metadata_get_orientation(self.raw) }
+    pub fn set_persons_shown(&self, people: &[PersonShown]) -> Result<()> {
+        PersonShown::write_all(self, people)
     }
 
-    /// Set the intended orientation for the image.
+    /// Returns the regions of the image described by IPTC Extension `ImageRegion` structures,
+    /// such as cropping suggestions or the areas showing its subjects. Regions without any fields
+    /// set are skipped, and so are boundaries with an unknown shape or unit.
+    pub fn get_image_regions(&self) -> Vec<ImageRegion> {
+        ImageRegion::read_all(self)
+    }
+
+    /// Replace the regions of the image, stored as IPTC Extension `ImageRegion` structures.
+    /// Passing an empty slice removes them all.
     ///
     /// # Examples
     /// ```
@@ -984,15 +4748,40 @@ impl Metadata {
     /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
     /// #               69, 78, 68, 174, 66, 96, 130];
     /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
-    /// assert_eq!(meta.get_orientation(), rexiv2::Orientation::Unspecified);
-    /// meta.set_orientation(rexiv2::Orientation::VerticalFlip);
-    /// assert_eq!(meta.get_orientation(), rexiv2::Orientation::VerticalFlip);
+    /// use rexiv2::{ImageRegion, RegionBoundary, RegionShape, RegionUnit};
+    /// let face = ImageRegion {
+    ///     boundary: Some(RegionBoundary {
+    ///         unit: RegionUnit::Pixel,
+    ///         shape: RegionShape::Circle { x: 120.0, y: 80.0, radius: 40.0 },
+    ///     }),
+    ///     content_types: vec![ImageRegion::CONTENT_FACE.to_string()],
+    ///     ..Default::default()
+    /// };
+    /// meta.set_image_regions(&[face.clone()])?;
+    /// assert_eq!(
+    ///     meta.get_tag_string(
+    ///         "Xmp.iptcExt.ImageRegion[1]/Iptc4xmpExt:RegionBoundary/Iptc4xmpExt:rbShape"
+    ///     )?,
+    ///     "circle",
+    /// );
+    /// assert_eq!(meta.get_image_regions(), [face]);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
     /// ```
-    pub fn set_orientation(&self, orientation: Orientation) {
-        unsafe { gexiv2::gexiv2_metadata_set_orientation(self.raw, orientation) }
+    pub fn set_image_regions(&self, regions: &[ImageRegion]) -> Result<()> {
+        ImageRegion::write_all(self, regions)
     }
 
-    /// Returns the camera exposure time of the photograph.
+    /// Returns how the content of the image was created, such as by a camera or by generative
+    /// AI, from the IPTC Extension `DigitalSourceType` tag.
+    pub fn get_digital_source_type(&self) -> Option<DigitalSourceType> {
+        let uri = self
+            .get_tag_string(tags::xmp::IPTC_EXT_DIGITAL_SOURCE_TYPE)
+            .ok()?;
+        Some(DigitalSourceType::from_uri(&uri))
+    }
+
+    /// Record how the content of the image was created, in the IPTC Extension
+    /// `DigitalSourceType` tag.
     ///
     /// # Examples
     /// ```
@@ -1001,41 +4790,46 @@ impl Metadata {
     /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
     /// #               69, 78, 68, 174, 66, 96, 130];
     /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
-    /// # meta.set_tag_rational("Exif.Photo.ExposureTime", &num_rational::Ratio::new_raw(1, 1000));
-    /// assert_eq!(meta.get_exposure_time(), Some(num_rational::Ratio::new_raw(1, 1000)));
+    /// use rexiv2::DigitalSourceType;
+    /// meta.set_digital_source_type(&DigitalSourceType::CompositeWithTrainedAlgorithmicMedia)?;
+    /// assert!(meta.get_digital_source_type().map_or(false, |source| source.is_generative_ai()));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
     /// ```
-    pub fn get_exposure_time(&self) -> Option<num_rational::Ratio<i32>> {
-        let num = &mut 0;
-        let den = &mut 0;
-        match unsafe { gexiv2::gexiv2_metadata_get_exposure_time(self.raw, num, den) } {
-            0 => None,
-            _ => {
-                if *den != 0 {
-                    Some(num_rational::Ratio::new_raw(*num, *den))
-                } else {
-                    None
-                }
-            }
-        }
+    pub fn set_digital_source_type(&self, source_type: &DigitalSourceType) -> Result<()> {
+        self.set_tag_string(tags::xmp::IPTC_EXT_DIGITAL_SOURCE_TYPE, &source_type.uri())
     }
 
-    /// Returns the f-number used by the camera taking the photograph.
-    pub fn get_fnumber(&self) -> Option<f64> {
-        match unsafe { gexiv2::gexiv2_metadata_get_fnumber(self.raw) } {
-            error_value if error_value < 0.0 => None, // gexiv2 returns -1.0 on error
-            fnumber => Some(fnumber),
-        }
+    // DNG related methods.
+
+    /// Returns the version of the DNG specification the file conforms to, e.g. `[1, 4, 0, 0]`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string(rexiv2::tags::exif::DNG_VERSION, "1 4 0 0").unwrap();
+    /// assert_eq!(meta.get_dng_version(), Some([1, 4, 0, 0]));
+    /// ```
+    pub fn get_dng_version(&self) -> Option<[u8; 4]> {
+        let value = self.get_tag_string(tags::exif::DNG_VERSION).ok()?;
+        let parts: Vec<u8> = value
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<std::result::Result<_, _>>()
+            .ok()?;
+        parts.try_into().ok()
     }
 
-    /// Returns the focal length used by the camera taking the photograph.
-    pub fn get_focal_length(&self) -> Option<f64> {
-        match unsafe { gexiv2::gexiv2_metadata_get_focal_length(self.raw) } {
-            error_value if error_value < 0.0 => None, // gexiv2 returns -1.0 on error
-            focal => Some(focal),
-        }
+    /// Returns the unique, non-localized name of the camera model that created the DNG file.
+    pub fn get_unique_camera_model(&self) -> Option<String> {
+        self.get_tag_string(tags::exif::UNIQUE_CAMERA_MODEL).ok()
     }
 
-    /// Returns the ISO speed used by the camera taking the photograph.
+    /// Returns the matrix converting XYZ values to camera color space under the first calibration
+    /// illuminant, in row-major order.
     ///
     /// # Examples
     /// ```
@@ -1044,14 +4838,45 @@ impl Metadata {
     /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
     /// #               69, 78, 68, 174, 66, 96, 130];
     /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
-    /// # meta.set_tag_numeric("Exif.Photo.ISOSpeedRatings", 600);
-    /// assert_eq!(meta.get_iso_speed(), Some(600));
+    /// let matrix = "1/2 0/1 0/1 0/1 1/1 0/1 0/1 0/1 3/2";
+    /// meta.set_tag_string(rexiv2::tags::exif::COLOR_MATRIX_1, matrix).unwrap();
+    /// assert_eq!(
+    ///     meta.get_color_matrix1(),
+    ///     Some(vec![0.5, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.5])
+    /// );
     /// ```
-    pub fn get_iso_speed(&self) -> Option<i32> {
-        match unsafe { gexiv2::gexiv2_metadata_get_iso_speed(self.raw) } {
-            0 => None,
-            speed => Some(speed),
-        }
+    pub fn get_color_matrix1(&self) -> Option<Vec<f64>> {
+        parse_rationals_as_f64(&self.get_tag_string(tags::exif::COLOR_MATRIX_1).ok()?)
+    }
+
+    /// Returns the matrix converting XYZ values to camera color space under the second
+    /// calibration illuminant, in row-major order.
+    pub fn get_color_matrix2(&self) -> Option<Vec<f64>> {
+        parse_rationals_as_f64(&self.get_tag_string(tags::exif::COLOR_MATRIX_2).ok()?)
+    }
+
+    /// Returns the white balance at time of capture, as the camera coordinates of a neutral
+    /// color.
+    pub fn get_as_shot_neutral(&self) -> Option<Vec<f64>> {
+        parse_rationals_as_f64(&self.get_tag_string(tags::exif::AS_SHOT_NEUTRAL).ok()?)
+    }
+
+    /// Returns the amount, in EV units, by which to adjust the zero point of the exposure.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_tag_string(rexiv2::tags::exif::BASELINE_EXPOSURE, "-1/2").unwrap();
+    /// assert_eq!(meta.get_baseline_exposure(), Some(-0.5));
+    /// ```
+    pub fn get_baseline_exposure(&self) -> Option<f64> {
+        let values =
+            parse_rationals_as_f64(&self.get_tag_string(tags::exif::BASELINE_EXPOSURE).ok()?)?;
+        values.first().copied()
     }
 
     // Thumbnail related methods.
@@ -1063,8 +4888,34 @@ impl Metadata {
         unsafe {
             match gexiv2::gexiv2_metadata_get_exif_thumbnail(self.raw, &mut data, &mut size) {
                 0 => None,
-                _ => Some(std::slice::from_raw_parts_mut(data, size as usize)),
+                _ if data.is_null() || size < 0 => None,
+                _ if !self.preview_size_allowed(size as u32) => None,
+                _ => Some(std::slice::from_raw_parts(data, size as usize)),
+            }
+        }
+    }
+
+    /// Copy the thumbnail stored in the EXIF data into the given buffer, replacing its contents.
+    ///
+    /// Unlike `get_thumbnail`, this releases the thumbnail data held by Exiv2 right away, and
+    /// lets the same buffer be reused for many files. Returns whether there was a thumbnail.
+    pub fn get_thumbnail_into(&self, buffer: &mut Vec<u8>) -> bool {
+        let mut data: *mut u8 = ptr::null_mut();
+        let mut size: libc::c_int = 0;
+        buffer.clear();
+        unsafe {
+            if gexiv2::gexiv2_metadata_get_exif_thumbnail(self.raw, &mut data, &mut size) == 0 {
+                return false;
             }
+            if data.is_null() {
+                return false;
+            }
+            let allowed = size >= 0 && self.preview_size_allowed(size as u32);
+            if allowed {
+                buffer.extend_from_slice(std::slice::from_raw_parts(data, size as usize));
+            }
+            libc::free(data as *mut libc::c_void);
+            allowed
         }
     }
 
@@ -1085,10 +4936,7 @@ impl Metadata {
                 &mut err,
             );
             if ok != 1 {
-                let err_msg = ffi::CStr::from_ptr((*err).message).to_str();
-                return Err(Rexiv2Error::Internal(
-                    err_msg.ok().map(|msg| msg.to_string()),
-                ));
+                return Err(gerror_to_rexiv2_error(err));
             }
             Ok(())
         }
@@ -1105,27 +4953,91 @@ impl Metadata {
         }
     }
 
+    /// Returns the orientation the thumbnail should be displayed in.
+    ///
+    /// This is `Exif.Thumbnail.Orientation` if it's set, for thumbnails that were stored rotated
+    /// differently from the main image. Otherwise the thumbnail is taken to be stored the same
+    /// way as the main image, and shares its orientation.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// use rexiv2::Orientation;
+    /// meta.set_orientation(Orientation::Rotate90);
+    /// assert_eq!(meta.get_thumbnail_orientation(), Orientation::Rotate90);
+    /// meta.set_thumbnail_orientation(Some(Orientation::Normal))?;
+    /// assert_eq!(meta.get_thumbnail_orientation(), Orientation::Normal);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn get_thumbnail_orientation(&self) -> Orientation {
+        self.get_tag_string(tags::exif::THUMBNAIL_ORIENTATION)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| self.get_orientation())
+    }
+
+    /// Record the orientation of the thumbnail in `Exif.Thumbnail.Orientation`, or remove it if
+    /// `None`, so that the thumbnail shares the orientation of the main image.
+    ///
+    /// Setting a new thumbnail doesn't change this tag, so it should be updated along with the
+    /// thumbnail whenever the new one is rotated differently from the old.
+    pub fn set_thumbnail_orientation(&self, orientation: Option<Orientation>) -> Result<()> {
+        match orientation {
+            Some(orientation) => self.set_tag_numeric(
+                tags::exif::THUMBNAIL_ORIENTATION,
+                i32::from(u16::from(orientation)),
+            ),
+            None => {
+                self.clear_tag(tags::exif::THUMBNAIL_ORIENTATION);
+                Ok(())
+            }
+        }
+    }
+
     // Preview image related methods.
 
-    /// Return the all the preview images found in this EXIF data.
-    pub fn get_preview_images(&self) -> Option<Vec<PreviewImage>> {
+    /// Return all the preview images found in this EXIF data, which may be none.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// assert!(meta.get_preview_images().is_empty());
+    /// assert_eq!(meta.preview_count(), 0);
+    /// ```
+    pub fn get_preview_images(&self) -> Vec<PreviewImage> {
         unsafe {
             let ptr = gexiv2::gexiv2_metadata_get_preview_properties(self.raw);
             if ptr.is_null() {
-                return None;
+                return vec![];
             }
 
             let mut previews: Vec<PreviewImage> = vec![];
             let mut n = 0;
             while !(*ptr.offset(n)).is_null() {
                 let preview_prop = *ptr.offset(n);
-                previews.push(PreviewImage { raw: preview_prop, metadata: self });
+                let preview = PreviewImage { raw: preview_prop, metadata: self };
+                if self.preview_size_allowed(preview.get_size()) {
+                    previews.push(preview);
+                }
                 n += 1;
             }
-            Some(previews)
+            previews
         }
     }
 
+    /// Return the number of preview images found in this EXIF data.
+    pub fn preview_count(&self) -> usize {
+        self.get_preview_images().len()
+    }
+
     // GPS-related methods.
 
     /// Retrieve the stored GPS information from the loaded file.
@@ -1195,19 +5107,351 @@ impl Metadata {
         }
     }
 
-    /// Remove all saved GPS information from the metadata.
-    pub fn delete_gps_info(&self) {
-        unsafe { gexiv2::gexiv2_metadata_delete_gps_info(self.raw) }
+    /// Remove all saved GPS information from the metadata.
+    pub fn delete_gps_info(&self) {
+        unsafe { gexiv2::gexiv2_metadata_delete_gps_info(self.raw) }
+    }
+
+    /// Returns the method used to find the image's location, from
+    /// `Exif.GPSInfo.GPSProcessingMethod`, or failing that from `Xmp.exif.GPSProcessingMethod`.
+    pub fn get_gps_processing_method(&self) -> Option<GpsProcessingMethod> {
+        self.get_tag_string(tags::exif::GPS_PROCESSING_METHOD)
+            .ok()
+            .map(|value| gps_method::comment_text(&value).to_string())
+            .filter(|name| !name.is_empty())
+            .or_else(|| self.get_trimmed_string(&[tags::xmp::EXIF_GPS_PROCESSING_METHOD]))
+            .map(|name| GpsProcessingMethod::from_name(&name))
+    }
+
+    /// Set the method used to find the image's location in `Exif.GPSInfo.GPSProcessingMethod`,
+    /// and in `Xmp.exif.GPSProcessingMethod` if the file supports XMP. `None` removes them.
+    ///
+    /// The Exif value is prefixed with the ASCII character set marker the standard requires, or
+    /// the Unicode one for names that aren't plain ASCII.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// use rexiv2::GpsProcessingMethod;
+    /// meta.set_gps_processing_method(Some(&GpsProcessingMethod::CellId))?;
+    /// assert_eq!(meta.get_gps_processing_method(), Some(GpsProcessingMethod::CellId));
+    /// meta.set_gps_processing_method(None)?;
+    /// assert_eq!(meta.get_gps_processing_method(), None);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_gps_processing_method(&self, method: Option<&GpsProcessingMethod>) -> Result<()> {
+        let name = method.map(|method| method.name().to_string());
+        match &name {
+            Some(name) => {
+                let charset = if name.is_ascii() { "Ascii" } else { "Unicode" };
+                self.set_tag_string(
+                    tags::exif::GPS_PROCESSING_METHOD,
+                    &format!("charset={charset} {name}"),
+                )?;
+            }
+            None => {
+                self.clear_tag(tags::exif::GPS_PROCESSING_METHOD);
+            }
+        }
+        self.set_mirrored_string(&[tags::xmp::EXIF_GPS_PROCESSING_METHOD], &name)
+    }
+
+    /// Returns the geodetic survey data used by the GPS receiver, such as `WGS-84`, from
+    /// `Exif.GPSInfo.GPSMapDatum`, or failing that from `Xmp.exif.GPSMapDatum`.
+    pub fn get_gps_map_datum(&self) -> Option<String> {
+        self.get_trimmed_string(&[tags::exif::GPS_MAP_DATUM, tags::xmp::EXIF_GPS_MAP_DATUM])
+    }
+
+    /// Set the geodetic survey data used by the GPS receiver in `Exif.GPSInfo.GPSMapDatum`, and
+    /// in `Xmp.exif.GPSMapDatum` if the file supports XMP. An empty datum removes them.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+    /// #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+    /// #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+    /// #               69, 78, 68, 174, 66, 96, 130];
+    /// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+    /// meta.set_gps_map_datum("WGS-84")?;
+    /// assert_eq!(meta.get_gps_map_datum(), Some("WGS-84".to_string()));
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn set_gps_map_datum(&self, datum: &str) -> Result<()> {
+        let datum = Some(datum.to_string()).filter(|datum| !datum.is_empty());
+        self.set_mirrored_string(
+            &[tags::exif::GPS_MAP_DATUM, tags::xmp::EXIF_GPS_MAP_DATUM],
+            &datum,
+        )
+    }
+}
+
+impl Metadata {
+    /// Take ownership of a gexiv2 metadata object.
+    fn wrap(raw: *mut gexiv2::GExiv2Metadata) -> Metadata {
+        Metadata {
+            raw,
+            max_preview_size: None,
+            #[cfg(feature = "bytes")]
+            buffer: None,
+            #[cfg(feature = "memmap")]
+            mapping: None,
+        }
+    }
+
+    /// Explain why a BMFF-based file, such as an AVIF or HEIC image, failed to load or loaded
+    /// without any metadata, if that's down to Exiv2's BMFF support being unavailable.
+    ///
+    /// `detect` is only called when something looks wrong, to find the type of the file.
+    fn check_bmff_support<F: FnOnce() -> Option<MediaType>>(
+        result: Result<Metadata>,
+        detect: F,
+    ) -> Result<Metadata> {
+        if matches!(result, Ok(ref meta) if !meta.is_empty()) {
+            return result;
+        }
+        let media_type = match detect() {
+            Some(media_type) if media_type.is_bmff() => String::from(&media_type),
+            _ => return result,
+        };
+
+        if !INITIALIZED.load(atomic::Ordering::Acquire) {
+            Err(Rexiv2Error::NotInitialized)
+        } else if unsafe { gexiv2::gexiv2_get_version() } < 1300 {
+            Err(Rexiv2Error::Unsupported {
+                needed: format!(
+                    "gexiv2 0.13 or later, for BMFF support to read {media_type} files"
+                ),
+                found: gexiv2_version_string(),
+            })
+        } else {
+            result.map_err(|err| {
+                Rexiv2Error::Internal(Some(format!(
+                    "Reading {media_type} files requires BMFF support, which Exiv2 may have been \
+                     built without ({err})"
+                )))
+            })
+        }
+    }
+
+    /// Get the underlying gexiv2 metadata object, for use with `gexiv2-sys` functions that
+    /// rexiv2 doesn't wrap.
+    ///
+    /// The pointer remains owned by this `Metadata`, and is only valid for as long as it is.
+    pub fn as_raw(&self) -> *mut gexiv2::GExiv2Metadata {
+        self.raw
+    }
+
+    /// Wrap a gexiv2 metadata object created with `gexiv2-sys`, taking ownership of it.
+    ///
+    /// # Safety
+    /// `raw` must be a valid, non-null pointer from `gexiv2_metadata_new()` that isn't owned by
+    /// anything else, as it will be freed when the returned `Metadata` is dropped.
+    pub unsafe fn from_raw(raw: *mut gexiv2::GExiv2Metadata) -> Metadata {
+        Metadata::wrap(raw)
+    }
+
+    /// Check a thumbnail or preview image size against the limit set when loading, if any.
+    fn preview_size_allowed(&self, size: u32) -> bool {
+        self.max_preview_size.map_or(true, |max| size <= max)
+    }
+}
+
+impl TryFrom<&std::path::Path> for Metadata {
+    type Error = Rexiv2Error;
+
+    /// Load the metadata from the file found at the given path; see `Metadata::new_from_path`.
+    fn try_from(path: &std::path::Path) -> Result<Metadata> {
+        Metadata::new_from_path(path)
+    }
+}
+
+#[cfg(feature = "camino")]
+impl TryFrom<&camino::Utf8Path> for Metadata {
+    type Error = Rexiv2Error;
+
+    /// Load the metadata from the file found at the given path; see `Metadata::new_from_path`.
+    fn try_from(path: &camino::Utf8Path) -> Result<Metadata> {
+        Metadata::new_from_path(path)
+    }
+}
+
+impl TryFrom<&str> for Metadata {
+    type Error = Rexiv2Error;
+
+    /// Load the metadata from the file found at the given path; see `Metadata::new_from_path`.
+    fn try_from(path: &str) -> Result<Metadata> {
+        Metadata::new_from_path(path)
+    }
+}
+
+impl TryFrom<&[u8]> for Metadata {
+    type Error = Rexiv2Error;
+
+    /// Load the metadata from the given data buffer; see `Metadata::new_from_buffer`.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+    /// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+    /// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+    /// #                78, 68, 174, 66, 96, 130];
+    /// let meta: rexiv2::Metadata = minipng[..].try_into()?;
+    /// assert_eq!(meta.get_media_type()?, rexiv2::MediaType::Png);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    fn try_from(data: &[u8]) -> Result<Metadata> {
+        Metadata::new_from_buffer(data)
+    }
+}
+
+impl std::fmt::Debug for Metadata {
+    /// Summarise the metadata, rather than listing every tag.
+    ///
+    /// # Examples
+    /// ```
+    /// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+    /// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+    /// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+    /// #                78, 68, 174, 66, 96, 130];
+    /// let meta = rexiv2::Metadata::new_from_buffer(&minipng)?;
+    /// assert_eq!(
+    ///     format!("{meta:?}"),
+    ///     "Metadata { media_type: Some(Png), width: 1, height: 1, exif_tags: 0, iptc_tags: 0, \
+    ///      xmp_tags: 0, orientation: Unspecified }",
+    /// );
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Metadata")
+            .field("media_type", &self.get_media_type().ok())
+            .field("width", &self.get_pixel_width())
+            .field("height", &self.get_pixel_height())
+            .field("exif_tags", &self.exif_tag_count())
+            .field("iptc_tags", &self.iptc_tag_count())
+            .field("xmp_tags", &self.xmp_tag_count())
+            .field("orientation", &self.get_orientation())
+            .finish()
+    }
+}
+
+impl std::fmt::Display for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let report = self
+            .to_report(ReportOptions::default())
+            .map_err(|_| std::fmt::Error)?;
+        f.write_str(&report)
+    }
+}
+
+impl PartialEq for Metadata {
+    fn eq(&self, other: &Metadata) -> bool {
+        if self.raw == other.raw {
+            return true;
+        }
+        match (self.tag_values(), other.tag_values()) {
+            (Ok(ours), Ok(theirs)) => ours == theirs,
+            _ => false,
+        }
     }
 }
 
+impl Eq for Metadata {}
+
 impl Drop for Metadata {
     fn drop(&mut self) {
         unsafe { gexiv2::gexiv2_metadata_free(self.raw) }
     }
 }
 
-impl PreviewImage<'_> {
+impl Iterator for InterpretedValues<'_> {
+    type Item = (String, String);
+
+    fn next(&mut self) -> Option<(String, String)> {
+        loop {
+            if self.c_tags.is_null() {
+                let raw = self.metadata.raw;
+                self.c_tags = unsafe {
+                    match self.domains_fetched {
+                        0 => gexiv2::gexiv2_metadata_get_exif_tags(raw),
+                        1 => gexiv2::gexiv2_metadata_get_xmp_tags(raw),
+                        2 => gexiv2::gexiv2_metadata_get_iptc_tags(raw),
+                        _ => return None,
+                    }
+                };
+                self.domains_fetched += 1;
+                self.cur_offset = 0;
+                continue;
+            }
+            unsafe {
+                let c_tag = *self.c_tags.add(self.cur_offset);
+                if c_tag.is_null() {
+                    free_array_of_pointers(self.c_tags as *mut *mut libc::c_void);
+                    self.c_tags = ptr::null_mut();
+                    continue;
+                }
+                self.cur_offset += 1;
+                let c_value =
+                    gexiv2::gexiv2_metadata_get_tag_interpreted_string(self.metadata.raw, c_tag);
+                if c_value.is_null() {
+                    continue;
+                }
+                let value = ffi::CStr::from_ptr(c_value).to_string_lossy().into_owned();
+                libc::free(c_value as *mut libc::c_void);
+                return Some((
+                    ffi::CStr::from_ptr(c_tag).to_string_lossy().into_owned(),
+                    value,
+                ));
+            }
+        }
+    }
+}
+
+impl Drop for InterpretedValues<'_> {
+    fn drop(&mut self) {
+        if !self.c_tags.is_null() {
+            free_array_of_pointers(self.c_tags as *mut *mut libc::c_void);
+        }
+    }
+}
+
+impl std::fmt::Debug for PreviewImage<'_> {
+    /// Describe the preview image by its properties, rather than its raw pointers.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let properties = self.get_properties();
+        f.debug_struct("PreviewImage")
+            .field("media_type", &properties.media_type)
+            .field("width", &properties.width)
+            .field("height", &properties.height)
+            .field("size", &properties.size)
+            .finish()
+    }
+}
+
+impl<'a> PreviewImage<'a> {
+    /// Get the underlying gexiv2 preview properties, for use with `gexiv2-sys` functions that
+    /// rexiv2 doesn't wrap.
+    ///
+    /// The pointer is owned by the parent `Metadata`, and is only valid for as long as it is.
+    pub fn as_raw(&self) -> *mut gexiv2::GExiv2PreviewProperties {
+        self.raw
+    }
+
+    /// Wrap gexiv2 preview properties belonging to the given metadata.
+    ///
+    /// # Safety
+    /// `raw` must be a valid, non-null pointer to preview properties obtained from
+    /// `gexiv2_metadata_get_preview_properties()` on `metadata`'s underlying object.
+    pub unsafe fn from_raw(
+        raw: *mut gexiv2::GExiv2PreviewProperties,
+        metadata: &'a Metadata,
+    ) -> PreviewImage<'a> {
+        PreviewImage { raw, metadata }
+    }
+
     /// Return the size of the preview image in bytes.
     pub fn get_size(&self) -> u32 {
         unsafe { gexiv2::gexiv2_preview_properties_get_size(self.raw) }
@@ -1245,18 +5489,44 @@ impl PreviewImage<'_> {
         }
     }
 
+    /// Return the size, dimensions, media type, and extension of the preview image together.
+    pub fn get_properties(&self) -> PreviewProperties {
+        PreviewProperties {
+            size: self.get_size(),
+            width: self.get_width(),
+            height: self.get_height(),
+            media_type: self.get_media_type().ok(),
+            extension: self.get_extension().ok(),
+        }
+    }
+
     /// Get the preview image data.
     pub fn get_data(&self) -> Result<Vec<u8>> {
+        let mut data = vec![];
+        self.get_data_into(&mut data)?;
+        Ok(data)
+    }
+
+    /// Copy the preview image data into the given buffer, replacing its contents.
+    ///
+    /// This lets the same buffer be reused for many images.
+    pub fn get_data_into(&self, buffer: &mut Vec<u8>) -> Result<()> {
         let image =
             unsafe { gexiv2::gexiv2_metadata_get_preview_image(self.metadata.raw, self.raw) };
 
+        if image.is_null() {
+            return Err(Rexiv2Error::NoValue);
+        }
+
         let mut size: libc::c_uint = 0;
+        buffer.clear();
         unsafe {
             let data = gexiv2::gexiv2_preview_image_get_data(image, &mut size);
             let result = if data.is_null() {
                 Err(Rexiv2Error::NoValue)
             } else {
-                Ok(std::slice::from_raw_parts_mut(data as *mut u8, size as usize).to_vec())
+                buffer.extend_from_slice(std::slice::from_raw_parts(data, size as usize));
+                Ok(())
             };
             gexiv2::gexiv2_preview_image_free(image);
             result
@@ -1281,6 +5551,19 @@ impl PreviewImage<'_> {
             }
         }
     }
+
+    /// Save the preview image to a file, returning the full path that was written.
+    ///
+    /// Exiv2 appends the preview's extension (see `get_extension`) to the given path, so e.g.
+    /// saving to `thumbs/photo` may produce `thumbs/photo.jpg`.
+    #[cfg(feature = "camino")]
+    pub fn save_to_utf8_path(&self, path: &camino::Utf8Path) -> Result<camino::Utf8PathBuf> {
+        self.save_to_file(path)?;
+        Ok(camino::Utf8PathBuf::from(format!(
+            "{path}{}",
+            self.get_extension()?
+        )))
+    }
 }
 
 
@@ -1293,8 +5576,11 @@ impl PreviewImage<'_> {
 /// assert!(rexiv2::is_exif_tag("Exif.Photo.FocalLength"));
 /// assert!(!rexiv2::is_exif_tag("Iptc.Application2.Subject"));
 /// ```
-pub fn is_exif_tag(tag: &str) -> bool {
-    let c_str_tag = ffi::CString::new(tag).unwrap();
+pub fn is_exif_tag<K: AsTagKey + ?Sized>(tag: &K) -> bool {
+    let c_str_tag = match tag.tag_c_str() {
+        Ok(c_str_tag) => c_str_tag,
+        Err(_) => return false,
+    };
     unsafe { gexiv2::gexiv2_metadata_is_exif_tag(c_str_tag.as_ptr()) == 1 }
 }
 
@@ -1305,8 +5591,11 @@ pub fn is_exif_tag(tag: &str) -> bool {
 /// assert!(rexiv2::is_iptc_tag("Iptc.Application2.Subject"));
 /// assert!(!rexiv2::is_iptc_tag("Xmp.dc.Title"));
 /// ```
-pub fn is_iptc_tag(tag: &str) -> bool {
-    let c_str_tag = ffi::CString::new(tag).unwrap();
+pub fn is_iptc_tag<K: AsTagKey + ?Sized>(tag: &K) -> bool {
+    let c_str_tag = match tag.tag_c_str() {
+        Ok(c_str_tag) => c_str_tag,
+        Err(_) => return false,
+    };
     unsafe { gexiv2::gexiv2_metadata_is_iptc_tag(c_str_tag.as_ptr()) == 1 }
 }
 
@@ -1317,8 +5606,11 @@ pub fn is_iptc_tag(tag: &str) -> bool {
 /// assert!(rexiv2::is_xmp_tag("Xmp.dc.Title"));
 /// assert!(!rexiv2::is_xmp_tag("Exif.Photo.FocalLength"));
 /// ```
-pub fn is_xmp_tag(tag: &str) -> bool {
-    let c_str_tag = ffi::CString::new(tag).unwrap();
+pub fn is_xmp_tag<K: AsTagKey + ?Sized>(tag: &K) -> bool {
+    let c_str_tag = match tag.tag_c_str() {
+        Ok(c_str_tag) => c_str_tag,
+        Err(_) => return false,
+    };
     unsafe { gexiv2::gexiv2_metadata_is_xmp_tag(c_str_tag.as_ptr()) == 1 }
 }
 
@@ -1328,15 +5620,8 @@ pub fn is_xmp_tag(tag: &str) -> bool {
 /// ```
 /// assert_eq!(rexiv2::get_tag_label("Iptc.Application2.Subject"), Ok("Subject".to_string()));
 /// ```
-pub fn get_tag_label(tag: &str) -> Result<String> {
-    let c_str_tag = ffi::CString::new(tag)?;
-    unsafe {
-        let c_str_val = gexiv2::gexiv2_metadata_get_tag_label(c_str_tag.as_ptr());
-        if c_str_val.is_null() {
-            return Err(Rexiv2Error::NoValue);
-        }
-        Ok(ffi::CStr::from_ptr(c_str_val).to_str()?.to_string())
-    }
+pub fn get_tag_label<K: AsTagKey + ?Sized>(tag: &K) -> Result<String> {
+    tag_label(&tag.tag_c_str()?)
 }
 
 /// Get the long-form description of a tag.
@@ -1346,15 +5631,8 @@ pub fn get_tag_label(tag: &str) -> Result<String> {
 /// assert_eq!(rexiv2::get_tag_description("Iptc.Application2.Subject"),
 ///     Ok("The Subject Reference is a structured definition of the subject matter.".to_string()));
 /// ```
-pub fn get_tag_description(tag: &str) -> Result<String> {
-    let c_str_tag = ffi::CString::new(tag)?;
-    unsafe {
-        let c_str_val = gexiv2::gexiv2_metadata_get_tag_description(c_str_tag.as_ptr());
-        if c_str_val.is_null() {
-            return Err(Rexiv2Error::NoValue);
-        }
-        Ok(ffi::CStr::from_ptr(c_str_val).to_str()?.to_string())
-    }
+pub fn get_tag_description<K: AsTagKey + ?Sized>(tag: &K) -> Result<String> {
+    tag_description(&tag.tag_c_str()?)
 }
 
 /// Determine the type of the given tag.
@@ -1364,55 +5642,60 @@ pub fn get_tag_description(tag: &str) -> Result<String> {
 /// assert_eq!(rexiv2::get_tag_type("Iptc.Application2.Subject"), Ok(rexiv2::TagType::String));
 /// assert_eq!(rexiv2::get_tag_type("Iptc.Application2.DateCreated"), Ok(rexiv2::TagType::Date));
 /// ```
-pub fn get_tag_type(tag: &str) -> Result<TagType> {
-    let c_str_tag = ffi::CString::new(tag)?;
-    let tag_type = unsafe {
-        let c_str_val = gexiv2::gexiv2_metadata_get_tag_type(c_str_tag.as_ptr());
-        if c_str_val.is_null() {
-            return Err(Rexiv2Error::NoValue);
-        }
-        ffi::CStr::from_ptr(c_str_val).to_str()?
-    };
-    match tag_type {
-        "Byte" => Ok(TagType::UnsignedByte),
-        "Ascii" => Ok(TagType::AsciiString),
-        "Short" => Ok(TagType::UnsignedShort),
-        "Long" => Ok(TagType::UnsignedLong),
-        "Rational" => Ok(TagType::UnsignedRational),
-        "SByte" => Ok(TagType::SignedByte),
-        "Undefined" => Ok(TagType::Undefined),
-        "SShort" => Ok(TagType::SignedShort),
-        "SLong" => Ok(TagType::SignedLong),
-        "SRational" => Ok(TagType::SignedRational),
-        "Float" => Ok(TagType::TiffFloat),
-        "Double" => Ok(TagType::TiffDouble),
-        "Ifd" => Ok(TagType::TiffIfd),
-        "String" => Ok(TagType::String),
-        "Date" => Ok(TagType::Date),
-        "Time" => Ok(TagType::Time),
-        "Comment" => Ok(TagType::Comment),
-        "Directory" => Ok(TagType::Directory),
-        "XmpText" => Ok(TagType::XmpText),
-        "XmpAlt" => Ok(TagType::XmpAlt),
-        "XmpBag" => Ok(TagType::XmpBag),
-        "XmpSeq" => Ok(TagType::XmpSeq),
-        "LangAlt" => Ok(TagType::LangAlt),
-        "Invalid" => Ok(TagType::Invalid),
-        _ => Ok(TagType::Unknown),
-    }
+pub fn get_tag_type<K: AsTagKey + ?Sized>(tag: &K) -> Result<TagType> {
+    tag_type(&tag.tag_c_str()?)
+}
+
+/// Get the domain, label, description, and type of a tag all at once.
+///
+/// This is equivalent to, but cheaper than, calling each of the individual functions, except
+/// that a tag without a description gets an empty one instead of failing with `NoValue`.
+///
+/// # Examples
+/// ```
+/// let info = rexiv2::tag_info("Iptc.Application2.Subject")?;
+/// assert_eq!(info.domain, rexiv2::TagDomain::Iptc);
+/// assert_eq!(info.label, "Subject");
+/// assert_eq!(info.tag_type, rexiv2::TagType::String);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn tag_info<K: AsTagKey + ?Sized>(tag: &K) -> Result<TagInfo> {
+    let c_str_tag = tag.tag_c_str()?;
+    Ok(TagInfo {
+        name: tag.tag_name().to_string(),
+        domain: tag_domain(&c_str_tag)?,
+        label: tag_label(&c_str_tag)?,
+        // Exiv2 doesn't have a description for every tag it knows about.
+        description: match tag_description(&c_str_tag) {
+            Err(Rexiv2Error::NoValue) => String::new(),
+            description => description?,
+        },
+        tag_type: tag_type(&c_str_tag)?,
+    })
 }
 
 /// Initialize gexiv2.
 ///
-/// This must be called in a thread-safe fashion before using rexiv2.
+/// This should be called before using rexiv2.
 /// The library may appear to work without calling this, but some
-/// features such as HEIC/BMFF will silently fail, and the underlying
+/// features such as HEIC/BMFF will fail (loading such files returns
+/// `Rexiv2Error::NotInitialized`), and the underlying
 /// libraries make the assumption that this will be called so it
 /// is safer to do so.
 ///
-/// Calling it first thing in the main function should ensure that
-/// it is executed on the main thread and is thread safe. Do not call
-/// it in a threaded or async context (such as in a tokio context).
+/// Once a call has succeeded, later calls do nothing and return
+/// `Ok(())`. Calls from other threads while it's running wait for it
+/// to finish. If it fails, a later call tries again. Calling it
+/// re-entrantly, e.g. from a log handler invoked while it runs,
+/// returns `Rexiv2Error::ReentrantInitialize` rather than
+/// deadlocking. Calling it first thing in the main function is still
+/// the simplest way to make sure it happens before anything else
+/// uses the library.
+///
+/// There is no matching teardown function: gexiv2 doesn't expose
+/// Exiv2's `XmpParser::terminate()`, so the XMP toolkit's global
+/// state lives until the process exits. Programs that load rexiv2
+/// from a plugin should therefore avoid unloading that plugin.
 ///
 /// # See also
 ///
@@ -1420,36 +5703,59 @@ pub fn get_tag_type(tag: &str) -> Result<TagType> {
 ///
 /// # Examples
 ///
-/// It is normally sufficient to simply call the function in the usual
-/// obvious way:
-///
 /// ```
 /// fn main() {
 ///     rexiv2::initialize().expect("Unable to initialize rexiv2");
-/// }
-/// ```
-///
-/// However if you have a more complex multi-threaded environment, you
-/// might want to ensure the function only gets set up once:
-///
-/// ```
-/// use std::sync::Once;
-///
-/// fn main() {
-///     static START: Once = Once::new();
-///
-///     START.call_once(|| unsafe {
-///         rexiv2::initialize().expect("Unable to initialize rexiv2");
-///     });
+///     // Further calls are harmless.
+///     assert_eq!(rexiv2::initialize(), Ok(()));
 /// }
 /// ```
 pub fn initialize() -> Result<()> {
-    unsafe { int_bool_to_result(gexiv2::gexiv2_initialize()) }
+    if INITIALIZED.load(atomic::Ordering::Acquire) {
+        return Ok(());
+    }
+    if INITIALIZING.with(Cell::get) {
+        return Err(Rexiv2Error::ReentrantInitialize);
+    }
+
+    // If an earlier attempt panicked, the lock is poisoned; try again rather than panicking too.
+    let _guard = INIT_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    // Another thread may have finished initializing while this one waited for the lock.
+    if !INITIALIZED.load(atomic::Ordering::Acquire) {
+        INITIALIZING.with(|initializing| initializing.set(true));
+        let ok = unsafe { gexiv2::gexiv2_initialize() } != 0;
+        INITIALIZING.with(|initializing| initializing.set(false));
+        INITIALIZED.store(ok, atomic::Ordering::Release);
+    }
+
+    if INITIALIZED.load(atomic::Ordering::Acquire) {
+        Ok(())
+    } else {
+        Err(Rexiv2Error::Internal(Some(
+            "gexiv2 failed to initialize".to_string(),
+        )))
+    }
+}
+
+/// Held while initializing gexiv2, so only one thread does it at a time.
+static INIT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Whether `initialize()` has been called successfully.
+static INITIALIZED: atomic::AtomicBool = atomic::AtomicBool::new(false);
+
+thread_local! {
+    /// Whether this thread is currently running `initialize()`.
+    static INITIALIZING: Cell<bool> = const { Cell::new(false) };
 }
 
 
 // XMP namespace management.
 
+/// The XMP namespaces registered through `register_xmp_namespace()`, as `(name, prefix)` pairs.
+static REGISTERED_XMP_NAMESPACES: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
 /// Add a new XMP namespace for tags to exist under.
 ///
 /// It is an error to register a duplicate namespace.
@@ -1468,8 +5774,30 @@ pub fn register_xmp_namespace(name: &str, prefix: &str) -> Result<()> {
         int_bool_to_result(gexiv2::gexiv2_metadata_register_xmp_namespace(
             c_str_name.as_ptr(),
             c_str_prefix.as_ptr(),
-        ))
+        ))?;
     }
+    let mut registered = REGISTERED_XMP_NAMESPACES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    registered.push((name.to_string(), prefix.to_string()));
+    Ok(())
+}
+
+/// Look up the name of a namespace registered through `register_xmp_namespace()` by its prefix.
+fn registered_xmp_namespace(prefix: &str) -> Option<String> {
+    let registered = REGISTERED_XMP_NAMESPACES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    registered
+        .iter()
+        .find(|(_, registered_prefix)| registered_prefix == prefix)
+        .map(|(name, _)| name.clone())
+}
+
+/// Make sure an XMP namespace is known to Exiv2, registering it if it isn't already.
+fn ensure_xmp_namespace(name: &str, prefix: &str) {
+    // Registering fails if the prefix is already known, which is all we need.
+    let _ = register_xmp_namespace(name, prefix);
 }
 
 /// Remove an XMP namespace from the set of known ones.
@@ -1489,8 +5817,13 @@ pub fn unregister_xmp_namespace(name: &str) -> Result<()> {
     unsafe {
         int_bool_to_result(gexiv2::gexiv2_metadata_unregister_xmp_namespace(
             c_str_name.as_ptr(),
-        ))
+        ))?;
     }
+    let mut registered = REGISTERED_XMP_NAMESPACES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    registered.retain(|(registered_name, _)| registered_name != name);
+    Ok(())
 }
 
 /// Forget all known XMP namespaces.
@@ -1503,6 +5836,10 @@ pub fn unregister_xmp_namespace(name: &str) -> Result<()> {
 /// ```
 pub fn unregister_all_xmp_namespaces() {
     unsafe { gexiv2::gexiv2_metadata_unregister_all_xmp_namespaces() }
+    let mut registered = REGISTERED_XMP_NAMESPACES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    registered.clear();
 }
 
 
@@ -1537,6 +5874,9 @@ pub fn set_log_level(level: LogLevel) {
 
 /// Helper function to free an array of pointers, such as those returned by some gexiv2 functions.
 fn free_array_of_pointers(list: *mut *mut libc::c_void) {
+    if list.is_null() {
+        return;
+    }
     unsafe {
         let mut idx = 0;
         while !(*list.offset(idx)).is_null() {
@@ -1547,6 +5887,313 @@ fn free_array_of_pointers(list: *mut *mut libc::c_void) {
     }
 }
 
+/// The tags that record the name of the camera's owner, in order of preference.
+const OWNER_NAME_TAGS: [&str; 4] = [
+    tags::exif::CAMERA_OWNER_NAME,
+    tags::xmp::EXIF_EX_CAMERA_OWNER_NAME,
+    tags::xmp::AUX_OWNER_NAME,
+    "Exif.Canon.OwnerName",
+];
+
+/// The interoperability indices defined by the Design rule for Camera File system (DCF): basic
+/// files, thumbnail files, and option files (such as ones in Adobe RGB).
+const DCF_INTEROP_INDICES: [&str; 3] = ["R98", "THM", "R03"];
+
+/// Tags that change every time a file is saved, which are left out of `Metadata::fingerprint()`.
+const VOLATILE_TAGS: [&str; 2] = ["Xmp.xmpMM.InstanceID", "Xmp.xmp.MetadataDate"];
+
+/// The parameters of the 64-bit FNV-1a hash, used by `Metadata::fingerprint()`.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A change to one of the values of a tag that can hold several, by its position.
+#[derive(Clone, Copy)]
+enum IndexedEdit<'a> {
+    Replace(&'a str),
+    Insert(&'a str),
+    Delete,
+}
+
+/// The ISO 2022 escape sequence that declares IPTC text to be UTF-8.
+const IPTC_UTF8: &[u8] = b"\x1b%G";
+
+/// Decode IPTC text, falling back to Latin-1 for invalid UTF-8 if no character set was declared.
+fn decode_iptc(value: &[u8], charset: &IptcCharset) -> Result<String> {
+    if let IptcCharset::Other(ref escape) = *charset {
+        return Err(unsupported_iptc_charset(escape));
+    }
+    match str::from_utf8(value) {
+        Ok(value) => Ok(value.to_string()),
+        Err(err) if *charset == IptcCharset::Utf8 => Err(Rexiv2Error::from(err)),
+        // Every byte of Latin-1 maps to the Unicode code point of the same value.
+        Err(_) => Ok(value.iter().map(|&byte| byte as char).collect()),
+    }
+}
+
+/// The error for IPTC text in a character set other than UTF-8 or an undeclared one.
+fn unsupported_iptc_charset(escape: &[u8]) -> Rexiv2Error {
+    Rexiv2Error::Unsupported {
+        needed: "IPTC text that is UTF-8 or has no declared character set".to_string(),
+        found: format!("the character set declared by escape sequence {escape:02x?}"),
+    }
+}
+
+/// Split the value of a language alternative XMP tag, as formatted by Exiv2 (e.g.
+/// `lang="x-default" Hello, lang="fr-FR" Bonjour`), into `(language, text)` pairs.
+fn parse_lang_alt(value: &str) -> Vec<(String, String)> {
+    let rest = match value.strip_prefix("lang=\"") {
+        Some(rest) => rest,
+        None => return vec![("x-default".to_string(), value.to_string())],
+    };
+    rest.split(", lang=\"")
+        .filter_map(|entry| {
+            let (lang, text) = entry.split_once("\" ")?;
+            Some((lang.to_string(), text.to_string()))
+        })
+        .collect()
+}
+
+/// Choose the text for the given language from language alternatives, falling back to the default
+/// text, then to the first available.
+fn pick_lang_alt(entries: Vec<(String, String)>, lang: &str) -> Option<String> {
+    let find = |wanted: &str| entries.iter().find(|(entry_lang, _)| entry_lang == wanted);
+    find(lang)
+        .or_else(|| find("x-default"))
+        .or_else(|| entries.first())
+        .map(|(_, text)| text.clone())
+}
+
+/// Strip the whitespace and nul padding from around a text value, or return `None` if that
+/// leaves it empty.
+fn trim_text(value: &str) -> Option<String> {
+    let value = value.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Remove the default language marker Exiv2 puts in front of the value of language alternative
+/// XMP tags, e.g. `lang="x-default" Some text`.
+fn strip_default_lang(value: String) -> String {
+    match value.strip_prefix("lang=\"x-default\" ") {
+        Some(text) => text.to_string(),
+        None => value,
+    }
+}
+
+/// Format the time of day of a timestamp as the rationals of an Exif `GPSTimeStamp` tag, with
+/// millisecond precision.
+fn gps_time_stamp_string(timestamp: &ExifDateTime) -> String {
+    let millis = timestamp.second as u32 * 1000 + timestamp.nanosecond.unwrap_or(0) / 1_000_000;
+    format!(
+        "{}/1 {}/1 {}/1000",
+        timestamp.hour, timestamp.minute, millis
+    )
+}
+
+/// Parse a space-separated list of `numerator/denominator` pairs, as produced by Exiv2 for
+/// rational tags, into floating point numbers.
+fn parse_rationals_as_f64(value: &str) -> Option<Vec<f64>> {
+    value
+        .split_whitespace()
+        .map(|item| {
+            let (num, den) = item.split_once('/')?;
+            let (num, den): (f64, f64) = (num.parse().ok()?, den.parse().ok()?);
+            if den == 0.0 {
+                None
+            } else {
+                Some(num / den)
+            }
+        })
+        .collect()
+}
+
+/// Decode a version number stored as four bytes of undefined type, such as `0100`, which Exiv2
+/// either formats as the bytes' decimal values (`"48 49 48 48"`) or as the text itself.
+fn decode_undefined_version(value: &str) -> Option<String> {
+    let value = value.trim();
+    let version = if value.contains(' ') {
+        value
+            .split_whitespace()
+            .map(|byte| byte.parse::<u8>().ok().map(char::from))
+            .collect::<Option<String>>()?
+    } else {
+        value.to_string()
+    };
+    Some(version).filter(|version| !version.is_empty())
+}
+
+/// Convert an error reported by gexiv2 into a `Rexiv2Error`, without assuming that gexiv2 set
+/// the error or its message.
+unsafe fn gerror_to_rexiv2_error(err: *mut gexiv2::GError) -> Rexiv2Error {
+    if err.is_null() || (*err).message.is_null() {
+        return Rexiv2Error::Internal(None);
+    }
+    let err_msg = ffi::CStr::from_ptr((*err).message).to_str();
+    Rexiv2Error::Internal(err_msg.ok().map(|msg| msg.to_string()))
+}
+
+/// Describe the version of gexiv2 linked at runtime, e.g. `"gexiv2 0.14.3"`.
+fn gexiv2_version_string() -> String {
+    let version = unsafe { gexiv2::gexiv2_get_version() };
+    format!(
+        "gexiv2 {}.{}.{}",
+        version / 10000,
+        version / 100 % 100,
+        version % 100
+    )
+}
+
+/// Turn an I/O error message into the most specific `Rexiv2Error` it describes.
+///
+/// Exiv2 reports I/O failures with the operating system's description of the problem embedded
+/// in its own message, so that's all there is to go on.
+fn classify_io_message(msg: String) -> Rexiv2Error {
+    let lower = msg.to_lowercase();
+    let mentions = |needles: &[&str]| needles.iter().any(|needle| lower.contains(needle));
+    if mentions(&[
+        "no space left on device",
+        "not enough space on the disk",
+        "disk full",
+    ]) {
+        Rexiv2Error::NoSpace(msg)
+    } else if mentions(&[
+        "permission denied",
+        "read-only file system",
+        "access is denied",
+    ]) {
+        Rexiv2Error::PermissionDenied(msg)
+    } else if mentions(&[
+        "no such file or directory",
+        "cannot find the path",
+        "cannot find the file",
+    ]) {
+        Rexiv2Error::NotFound(msg)
+    } else {
+        Rexiv2Error::Internal(Some(msg))
+    }
+}
+
+/// Count the entries in a list of tags returned by gexiv2, then free it.
+fn count_and_free_tags(c_tags: *mut *mut libc::c_char) -> usize {
+    if c_tags.is_null() {
+        return 0;
+    }
+    let mut count = 0;
+    unsafe {
+        while !(*c_tags.add(count)).is_null() {
+            count += 1;
+        }
+    }
+    free_array_of_pointers(c_tags as *mut *mut libc::c_void);
+    count
+}
+
+/// Name the tag Exiv2 uses for a qualifier of an XMP property, such as `Xmp.dc.source/?xml:lang`.
+fn xmp_qualifier_tag<K: AsTagKey + ?Sized>(tag: &K, qualifier: &str) -> Result<String> {
+    let valid = qualifier.split_once(':').map_or(false, |(prefix, name)| {
+        !prefix.is_empty() && !name.is_empty() && !qualifier.contains(['/', '[', '?'])
+    });
+    if !is_xmp_tag(tag) || !valid {
+        return Err(Rexiv2Error::InvalidValue(format!(
+            "Invalid XMP qualifier {qualifier:?} of {}",
+            tag.tag_name()
+        )));
+    }
+    Ok(format!("{}/?{qualifier}", tag.tag_name()))
+}
+
+/// The size in bytes of each component of a raw value of the given type, if it is fixed.
+#[cfg(feature = "raw-tag-access")]
+fn component_size(tag_type: TagType) -> Option<usize> {
+    match tag_type {
+        TagType::UnsignedByte
+        | TagType::AsciiString
+        | TagType::SignedByte
+        | TagType::Undefined
+        | TagType::Comment => Some(1),
+        TagType::UnsignedShort | TagType::SignedShort => Some(2),
+        TagType::UnsignedLong | TagType::SignedLong | TagType::TiffFloat | TagType::TiffIfd => {
+            Some(4)
+        }
+        TagType::UnsignedRational | TagType::SignedRational | TagType::TiffDouble => Some(8),
+        _ => None,
+    }
+}
+
+/// Determine which domain a tag belongs to.
+fn tag_domain(c_str_tag: &ffi::CStr) -> Result<TagDomain> {
+    unsafe {
+        if gexiv2::gexiv2_metadata_is_exif_tag(c_str_tag.as_ptr()) == 1 {
+            Ok(TagDomain::Exif)
+        } else if gexiv2::gexiv2_metadata_is_iptc_tag(c_str_tag.as_ptr()) == 1 {
+            Ok(TagDomain::Iptc)
+        } else if gexiv2::gexiv2_metadata_is_xmp_tag(c_str_tag.as_ptr()) == 1 {
+            Ok(TagDomain::Xmp)
+        } else {
+            Err(Rexiv2Error::NoValue)
+        }
+    }
+}
+
+/// Look up the label of a tag.
+fn tag_label(c_str_tag: &ffi::CStr) -> Result<String> {
+    unsafe {
+        let c_str_val = gexiv2::gexiv2_metadata_get_tag_label(c_str_tag.as_ptr());
+        if c_str_val.is_null() {
+            return Err(Rexiv2Error::NoValue);
+        }
+        Ok(ffi::CStr::from_ptr(c_str_val).to_str()?.to_string())
+    }
+}
+
+/// Look up the description of a tag.
+fn tag_description(c_str_tag: &ffi::CStr) -> Result<String> {
+    unsafe {
+        let c_str_val = gexiv2::gexiv2_metadata_get_tag_description(c_str_tag.as_ptr());
+        if c_str_val.is_null() {
+            return Err(Rexiv2Error::NoValue);
+        }
+        Ok(ffi::CStr::from_ptr(c_str_val).to_str()?.to_string())
+    }
+}
+
+/// Look up the type of a tag.
+fn tag_type(c_str_tag: &ffi::CStr) -> Result<TagType> {
+    let tag_type = unsafe {
+        let c_str_val = gexiv2::gexiv2_metadata_get_tag_type(c_str_tag.as_ptr());
+        if c_str_val.is_null() {
+            return Err(Rexiv2Error::NoValue);
+        }
+        ffi::CStr::from_ptr(c_str_val).to_str()?
+    };
+    match tag_type {
+        "Byte" => Ok(TagType::UnsignedByte),
+        "Ascii" => Ok(TagType::AsciiString),
+        "Short" => Ok(TagType::UnsignedShort),
+        "Long" => Ok(TagType::UnsignedLong),
+        "Rational" => Ok(TagType::UnsignedRational),
+        "SByte" => Ok(TagType::SignedByte),
+        "Undefined" => Ok(TagType::Undefined),
+        "SShort" => Ok(TagType::SignedShort),
+        "SLong" => Ok(TagType::SignedLong),
+        "SRational" => Ok(TagType::SignedRational),
+        "Float" => Ok(TagType::TiffFloat),
+        "Double" => Ok(TagType::TiffDouble),
+        "Ifd" => Ok(TagType::TiffIfd),
+        "String" => Ok(TagType::String),
+        "Date" => Ok(TagType::Date),
+        "Time" => Ok(TagType::Time),
+        "Comment" => Ok(TagType::Comment),
+        "Directory" => Ok(TagType::Directory),
+        "XmpText" => Ok(TagType::XmpText),
+        "XmpAlt" => Ok(TagType::XmpAlt),
+        "XmpBag" => Ok(TagType::XmpBag),
+        "XmpSeq" => Ok(TagType::XmpSeq),
+        "LangAlt" => Ok(TagType::LangAlt),
+        "Invalid" => Ok(TagType::Invalid),
+        _ => Ok(TagType::Unknown),
+    }
+}
+
 /// Convert a success/failure integer representing a boolean into a Result.
 fn int_bool_to_result(success: libc::c_int) -> Result<()> {
     match success {
@@ -0,0 +1,135 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{ensure_xmp_namespace, strip_default_lang, tags, Metadata, Result};
+
+/// The copyright notice and license terms of an image, kept consistent across its Exif, IPTC,
+/// and XMP rights tags.
+///
+/// Each field is `None` when none of the tags it's kept in are present.
+///
+/// # Examples
+/// ```
+/// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+/// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+/// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+/// #                78, 68, 174, 66, 96, 130];
+/// # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+/// use rexiv2::License;
+/// let license = License::creative_commons(License::CC_BY_4_0, "Copyright 2024 Jane Doe");
+/// license.write_to(&meta)?;
+/// assert_eq!(meta.get_tag_string("Xmp.cc.license")?, License::CC_BY_4_0);
+/// assert_eq!(meta.get_tag_string("Xmp.xmpRights.Marked")?, "True");
+/// assert_eq!(meta.get_tag_string("Exif.Image.Copyright")?, "Copyright 2024 Jane Doe");
+/// assert_eq!(License::read_from(&meta), license);
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct License {
+    /// The copyright notice, such as `© 2024 Jane Doe` (`Exif.Image.Copyright`,
+    /// `Iptc.Application2.Copyright`, and `Xmp.dc.rights`).
+    pub copyright: Option<String>,
+    /// The URL of the license, or of a web page describing the rights to the image
+    /// (`Xmp.cc.license` and `Xmp.xmpRights.WebStatement`).
+    pub url: Option<String>,
+    /// Instructions on how the image can be legally used (`Xmp.xmpRights.UsageTerms`).
+    pub usage_terms: Option<String>,
+}
+
+impl License {
+    /// The Creative Commons CC0 1.0 Universal public domain dedication.
+    pub const CC0_1_0: &'static str = "https://creativecommons.org/publicdomain/zero/1.0/";
+    /// The Creative Commons Public Domain Mark 1.0.
+    pub const PUBLIC_DOMAIN_MARK_1_0: &'static str =
+        "https://creativecommons.org/publicdomain/mark/1.0/";
+    /// The Creative Commons Attribution 4.0 International license.
+    pub const CC_BY_4_0: &'static str = "https://creativecommons.org/licenses/by/4.0/";
+    /// The Creative Commons Attribution-ShareAlike 4.0 International license.
+    pub const CC_BY_SA_4_0: &'static str = "https://creativecommons.org/licenses/by-sa/4.0/";
+    /// The Creative Commons Attribution-NoDerivatives 4.0 International license.
+    pub const CC_BY_ND_4_0: &'static str = "https://creativecommons.org/licenses/by-nd/4.0/";
+    /// The Creative Commons Attribution-NonCommercial 4.0 International license.
+    pub const CC_BY_NC_4_0: &'static str = "https://creativecommons.org/licenses/by-nc/4.0/";
+    /// The Creative Commons Attribution-NonCommercial-ShareAlike 4.0 International license.
+    pub const CC_BY_NC_SA_4_0: &'static str = "https://creativecommons.org/licenses/by-nc-sa/4.0/";
+    /// The Creative Commons Attribution-NonCommercial-NoDerivatives 4.0 International license.
+    pub const CC_BY_NC_ND_4_0: &'static str = "https://creativecommons.org/licenses/by-nc-nd/4.0/";
+
+    /// A Creative Commons license found at the given URL (such as `License::CC_BY_4_0`), with the
+    /// given copyright notice, and usage terms pointing at the license.
+    pub fn creative_commons(url: &str, copyright: &str) -> License {
+        License {
+            copyright: Some(copyright.to_string()),
+            url: Some(url.to_string()),
+            usage_terms: Some(format!("This work is licensed under {url}")),
+        }
+    }
+
+    /// Indicates whether the license places the image in the public domain, such as CC0 or the
+    /// Public Domain Mark.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::License;
+    /// assert!(License::creative_commons(License::CC0_1_0, "").is_public_domain());
+    /// assert!(!License::creative_commons(License::CC_BY_SA_4_0, "").is_public_domain());
+    /// ```
+    pub fn is_public_domain(&self) -> bool {
+        self.url.as_deref().map_or(false, |url| {
+            url.contains("creativecommons.org/publicdomain/")
+        })
+    }
+
+    /// Read the license from the given metadata, falling back to the IPTC and XMP copyright
+    /// notices if there is none in Exif, and to the XMP web statement if there is no Creative
+    /// Commons license URL.
+    pub fn read_from(meta: &Metadata) -> License {
+        let get = |tag| meta.get_tag_string(tag).ok();
+        License {
+            copyright: get(tags::exif::COPYRIGHT)
+                .or_else(|| meta.get_iptc_tag_string(tags::iptc::COPYRIGHT).ok())
+                .or_else(|| get(tags::xmp::DC_RIGHTS).map(strip_default_lang)),
+            url: get(tags::xmp::CC_LICENSE).or_else(|| get(tags::xmp::XMP_RIGHTS_WEB_STATEMENT)),
+            usage_terms: get(tags::xmp::XMP_RIGHTS_USAGE_TERMS).map(strip_default_lang),
+        }
+    }
+
+    /// Write the license to every tag that holds it, removing the tags of fields that are `None`.
+    ///
+    /// `Xmp.xmpRights.Marked` is also set, to `False` for public domain licenses and `True`
+    /// otherwise, or removed if all the fields are `None`.
+    pub fn write_to(&self, meta: &Metadata) -> Result<()> {
+        let marked = if *self == License::default() {
+            None
+        } else if self.is_public_domain() {
+            Some("False".to_string())
+        } else {
+            Some("True".to_string())
+        };
+        ensure_xmp_namespace("http://creativecommons.org/ns#", "cc");
+        meta.set_optional_strings(&[
+            (tags::exif::COPYRIGHT, &self.copyright),
+            (tags::iptc::COPYRIGHT, &self.copyright),
+            (tags::xmp::DC_RIGHTS, &self.copyright),
+            (tags::xmp::CC_LICENSE, &self.url),
+            (tags::xmp::XMP_RIGHTS_WEB_STATEMENT, &self.url),
+            (tags::xmp::XMP_RIGHTS_USAGE_TERMS, &self.usage_terms),
+            (tags::xmp::XMP_RIGHTS_MARKED, &marked),
+        ])
+    }
+}
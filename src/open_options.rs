@@ -0,0 +1,92 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{Result, Rexiv2Error};
+
+/// The number of bytes read from the start of a file when only its headers are wanted.
+pub(crate) const HEADER_BYTES: u64 = 1024 * 1024;
+
+/// Options that control how metadata is loaded, for use with [`Metadata::open_with`] and
+/// [`Metadata::new_from_buffer_with`].
+///
+/// These are primarily useful to bound the memory and time spent processing untrusted files. Only
+/// `max_size` and `headers_only` take effect before Exiv2 sees the data; `max_preview_size`
+/// filters what is returned after it has been parsed.
+///
+/// [`Metadata::open_with`]: struct.Metadata.html#method.open_with
+/// [`Metadata::new_from_buffer_with`]: struct.Metadata.html#method.new_from_buffer_with
+///
+/// # Examples
+/// ```no_run
+/// let options = rexiv2::OpenOptions::new().max_size(50 * 1024 * 1024).headers_only(true);
+/// let meta = rexiv2::Metadata::open_with("upload.jpg", &options)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpenOptions {
+    pub(crate) max_size: Option<u64>,
+    pub(crate) max_preview_size: Option<u32>,
+    pub(crate) headers_only: bool,
+}
+
+impl OpenOptions {
+    /// Create a set of options with no limits, equivalent to the plain constructors.
+    pub fn new() -> OpenOptions {
+        OpenOptions::default()
+    }
+
+    /// Refuse to load files or buffers larger than the given number of bytes.
+    pub fn max_size(mut self, bytes: u64) -> OpenOptions {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Ignore any thumbnail or preview images larger than the given number of bytes.
+    ///
+    /// Such images will not be returned by `Metadata::get_thumbnail` or
+    /// `Metadata::get_preview_images`. This is a filter applied after loading: Exiv2 still parses
+    /// the whole file, including the directories describing oversized previews, but their data is
+    /// never copied out.
+    pub fn max_preview_size(mut self, bytes: u32) -> OpenOptions {
+        self.max_preview_size = Some(bytes);
+        self
+    }
+
+    /// Only read the first megabyte of the file, rather than the whole thing.
+    ///
+    /// This is much cheaper for large files in formats that keep their metadata near the start,
+    /// such as JPEG and most TIFF-based RAW formats. The data is cut off before Exiv2 parses it,
+    /// so anything stored past the first megabyte is silently missing: XMP at the end of a PNG or
+    /// video, IFDs or maker notes that TIFF offsets point beyond the cut, and thumbnails or
+    /// previews. Loading can also fail outright. Don't save metadata loaded this way back to the
+    /// file, as whatever was missed would be lost from it.
+    pub fn headers_only(mut self, headers_only: bool) -> OpenOptions {
+        self.headers_only = headers_only;
+        self
+    }
+
+    /// Check the size of some data against the configured limit.
+    pub(crate) fn check_size(&self, size: u64) -> Result<()> {
+        match self.max_size {
+            Some(max) if size > max => Err(Rexiv2Error::Internal(Some(format!(
+                "Size of {size} bytes exceeds the maximum of {max} bytes"
+            )))),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{Result, Rexiv2Error};
+
+const ALL_ORIENTATIONS: [Orientation; 9] = [
+    Orientation::Unspecified,
+    Orientation::Normal,
+    Orientation::HorizontalFlip,
+    Orientation::Rotate180,
+    Orientation::VerticalFlip,
+    Orientation::Rotate90HorizontalFlip,
+    Orientation::Rotate90,
+    Orientation::Rotate90VerticalFlip,
+    Orientation::Rotate270,
+];
+
+/// The orientation an image should be displayed in, as recorded by the Exif orientation tag.
+///
+/// Each value describes the transformation that needs to be applied to the stored pixels in
+/// order to display the image the right way up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// No orientation is specified; usually treated the same as `Normal`.
+    Unspecified,
+    /// The image is stored the right way up (Exif value 1).
+    Normal,
+    /// The image is mirrored left-to-right (Exif value 2).
+    HorizontalFlip,
+    /// The image is upside-down (Exif value 3).
+    Rotate180,
+    /// The image is mirrored top-to-bottom (Exif value 4).
+    VerticalFlip,
+    /// The image must be rotated 90° clockwise, then mirrored left-to-right (Exif value 5).
+    Rotate90HorizontalFlip,
+    /// The image must be rotated 90° clockwise (Exif value 6).
+    Rotate90,
+    /// The image must be rotated 90° clockwise, then mirrored top-to-bottom (Exif value 7).
+    Rotate90VerticalFlip,
+    /// The image must be rotated 270° clockwise (Exif value 8).
+    Rotate270,
+}
+
+impl Orientation {
+    /// Combine two orientations into one: the result is equivalent to applying the
+    /// transformation described by `self` first, then the one described by `next`.
+    ///
+    /// `Unspecified` is treated as `Normal`, and so is never returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::Orientation;
+    /// assert_eq!(Orientation::Rotate90.compose(Orientation::Rotate90), Orientation::Rotate180);
+    /// assert_eq!(Orientation::Rotate90.compose(Orientation::Rotate270), Orientation::Normal);
+    /// assert_eq!(
+    ///     Orientation::Rotate90.compose(Orientation::HorizontalFlip),
+    ///     Orientation::Rotate90HorizontalFlip,
+    /// );
+    /// ```
+    pub fn compose(self, next: Orientation) -> Orientation {
+        let (first_flip, first_turns) = self.to_flip_and_turns();
+        let (next_flip, next_turns) = next.to_flip_and_turns();
+        // A flip reverses the direction of any rotation that happened before it.
+        let turns = if next_flip {
+            next_turns + 4 - first_turns
+        } else {
+            next_turns + first_turns
+        };
+        Orientation::from_flip_and_turns(first_flip != next_flip, turns % 4)
+    }
+
+    /// Return the orientation that undoes this one, such that composing the two yields `Normal`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::Orientation;
+    /// assert_eq!(Orientation::Rotate90.inverse(), Orientation::Rotate270);
+    /// assert_eq!(Orientation::HorizontalFlip.inverse(), Orientation::HorizontalFlip);
+    /// assert_eq!(
+    ///     Orientation::Rotate90VerticalFlip.compose(Orientation::Rotate90VerticalFlip.inverse()),
+    ///     Orientation::Normal,
+    /// );
+    /// ```
+    pub fn inverse(self) -> Orientation {
+        match self.to_flip_and_turns() {
+            // Every flipped orientation is its own inverse.
+            (true, turns) => Orientation::from_flip_and_turns(true, turns),
+            (false, turns) => Orientation::from_flip_and_turns(false, (4 - turns) % 4),
+        }
+    }
+
+    /// Indicates whether applying this orientation exchanges the width and height of the image.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::Orientation;
+    /// assert!(Orientation::Rotate270.swaps_dimensions());
+    /// assert!(!Orientation::Rotate180.swaps_dimensions());
+    /// ```
+    pub fn swaps_dimensions(self) -> bool {
+        self.to_flip_and_turns().1 % 2 == 1
+    }
+
+    /// The name of the orientation, as used by its `Display` and `FromStr` implementations.
+    fn name(self) -> &'static str {
+        match self {
+            Orientation::Unspecified => "Unspecified",
+            Orientation::Normal => "Normal",
+            Orientation::HorizontalFlip => "HorizontalFlip",
+            Orientation::Rotate180 => "Rotate180",
+            Orientation::VerticalFlip => "VerticalFlip",
+            Orientation::Rotate90HorizontalFlip => "Rotate90HorizontalFlip",
+            Orientation::Rotate90 => "Rotate90",
+            Orientation::Rotate90VerticalFlip => "Rotate90VerticalFlip",
+            Orientation::Rotate270 => "Rotate270",
+        }
+    }
+
+    /// Express the orientation as an optional left-to-right mirroring, followed by a number of
+    /// clockwise quarter turns.
+    fn to_flip_and_turns(self) -> (bool, u8) {
+        match self {
+            Orientation::Unspecified | Orientation::Normal => (false, 0),
+            Orientation::HorizontalFlip => (true, 0),
+            Orientation::Rotate180 => (false, 2),
+            Orientation::VerticalFlip => (true, 2),
+            Orientation::Rotate90HorizontalFlip => (true, 3),
+            Orientation::Rotate90 => (false, 1),
+            Orientation::Rotate90VerticalFlip => (true, 1),
+            Orientation::Rotate270 => (false, 3),
+        }
+    }
+
+    fn from_flip_and_turns(flip: bool, turns: u8) -> Orientation {
+        match (flip, turns) {
+            (false, 0) => Orientation::Normal,
+            (false, 1) => Orientation::Rotate90,
+            (false, 2) => Orientation::Rotate180,
+            (false, 3) => Orientation::Rotate270,
+            (true, 0) => Orientation::HorizontalFlip,
+            (true, 1) => Orientation::Rotate90VerticalFlip,
+            (true, 2) => Orientation::VerticalFlip,
+            (true, 3) => Orientation::Rotate90HorizontalFlip,
+            _ => unreachable!("quarter turns are always reduced modulo 4"),
+        }
+    }
+}
+
+impl TryFrom<u16> for Orientation {
+    type Error = Rexiv2Error;
+
+    /// Convert a raw Exif orientation tag value into an `Orientation`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::Orientation;
+    /// assert_eq!(Orientation::try_from(6), Ok(Orientation::Rotate90));
+    /// assert!(Orientation::try_from(9).is_err());
+    /// ```
+    fn try_from(value: u16) -> Result<Orientation> {
+        match value {
+            0 => Ok(Orientation::Unspecified),
+            1 => Ok(Orientation::Normal),
+            2 => Ok(Orientation::HorizontalFlip),
+            3 => Ok(Orientation::Rotate180),
+            4 => Ok(Orientation::VerticalFlip),
+            5 => Ok(Orientation::Rotate90HorizontalFlip),
+            6 => Ok(Orientation::Rotate90),
+            7 => Ok(Orientation::Rotate90VerticalFlip),
+            8 => Ok(Orientation::Rotate270),
+            _ => Err(Rexiv2Error::InvalidValue(format!(
+                "Invalid orientation value: {value}"
+            ))),
+        }
+    }
+}
+
+impl From<Orientation> for u16 {
+    /// Convert an `Orientation` into its raw Exif orientation tag value.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(u16::from(rexiv2::Orientation::Rotate270), 8);
+    /// ```
+    fn from(orientation: Orientation) -> u16 {
+        match orientation {
+            Orientation::Unspecified => 0,
+            Orientation::Normal => 1,
+            Orientation::HorizontalFlip => 2,
+            Orientation::Rotate180 => 3,
+            Orientation::VerticalFlip => 4,
+            Orientation::Rotate90HorizontalFlip => 5,
+            Orientation::Rotate90 => 6,
+            Orientation::Rotate90VerticalFlip => 7,
+            Orientation::Rotate270 => 8,
+        }
+    }
+}
+
+impl std::str::FromStr for Orientation {
+    type Err = Rexiv2Error;
+
+    /// Parse an orientation from either its raw Exif value (e.g. `"6"`) or its name (e.g.
+    /// `"Rotate90"`, matched case-insensitively).
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::Orientation;
+    /// assert_eq!("6".parse(), Ok(Orientation::Rotate90));
+    /// assert_eq!("rotate90".parse(), Ok(Orientation::Rotate90));
+    /// assert!("sideways".parse::<Orientation>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Orientation> {
+        let s = s.trim();
+        if let Ok(value) = s.parse::<u16>() {
+            return Orientation::try_from(value);
+        }
+        ALL_ORIENTATIONS
+            .iter()
+            .find(|orientation| orientation.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| Rexiv2Error::InvalidValue(format!("Invalid orientation: {s:?}")))
+    }
+}
+
+impl std::fmt::Display for Orientation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl From<gexiv2::Orientation> for Orientation {
+    fn from(orientation: gexiv2::Orientation) -> Orientation {
+        match orientation {
+            gexiv2::Orientation::Unspecified => Orientation::Unspecified,
+            gexiv2::Orientation::Normal => Orientation::Normal,
+            gexiv2::Orientation::HorizontalFlip => Orientation::HorizontalFlip,
+            gexiv2::Orientation::Rotate180 => Orientation::Rotate180,
+            gexiv2::Orientation::VerticalFlip => Orientation::VerticalFlip,
+            gexiv2::Orientation::Rotate90HorizontalFlip => Orientation::Rotate90HorizontalFlip,
+            gexiv2::Orientation::Rotate90 => Orientation::Rotate90,
+            gexiv2::Orientation::Rotate90VerticalFlip => Orientation::Rotate90VerticalFlip,
+            gexiv2::Orientation::Rotate270 => Orientation::Rotate270,
+        }
+    }
+}
+
+impl From<Orientation> for gexiv2::Orientation {
+    fn from(orientation: Orientation) -> gexiv2::Orientation {
+        match orientation {
+            Orientation::Unspecified => gexiv2::Orientation::Unspecified,
+            Orientation::Normal => gexiv2::Orientation::Normal,
+            Orientation::HorizontalFlip => gexiv2::Orientation::HorizontalFlip,
+            Orientation::Rotate180 => gexiv2::Orientation::Rotate180,
+            Orientation::VerticalFlip => gexiv2::Orientation::VerticalFlip,
+            Orientation::Rotate90HorizontalFlip => gexiv2::Orientation::Rotate90HorizontalFlip,
+            Orientation::Rotate90 => gexiv2::Orientation::Rotate90,
+            Orientation::Rotate90VerticalFlip => gexiv2::Orientation::Rotate90VerticalFlip,
+            Orientation::Rotate270 => gexiv2::Orientation::Rotate270,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+/// Identifiers linking an image back to the original it was derived from, as asset managers use
+/// them.
+///
+/// An identifier that isn't recorded is `None`, and setting one to `None` removes its tag.
+///
+/// # Examples
+/// ```
+/// let provenance = rexiv2::Provenance {
+///     raw_file_name: Some("IMG_0001.CR3".to_string()),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Provenance {
+    /// An identifier assigned to this image by the camera, unique to it
+    /// (`Exif.Photo.ImageUniqueID`).
+    pub image_unique_id: Option<String>,
+    /// The identifier of the original document this image was derived from
+    /// (`Xmp.xmpMM.OriginalDocumentID`).
+    pub original_document_id: Option<String>,
+    /// The name of the raw file this image was developed from (`Xmp.crs.RawFileName`).
+    pub raw_file_name: Option<String>,
+}
@@ -0,0 +1,670 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{
+    AsTagKey, Camera, Capabilities, CaptureDatePolicy, CaptureDateSource, CaptureSettings,
+    Collection, ContentSignature, DigitalSourceType, ExifDateTime, ExifIfd, GpsInfo,
+    GpsProcessingMethod, HierarchicalKeyword, ImageRegion, InterpretedValues, IptcCharset,
+    IptcLocation, IsoSensitivity, MediaType, Metadata, Orientation, PersonShown, PickFlag,
+    PreviewImage, Provenance, ReportOptions, Result,
+};
+use std::ffi;
+
+/// A read-only handle to the metadata of a file or buffer.
+///
+/// This exposes all of the getters of [`Metadata`], but none of its setters and no way to save,
+/// so code holding a `MetadataRef` is guaranteed not to modify the original file.
+///
+/// # Examples
+/// ```
+/// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+/// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+/// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+/// #                78, 68, 174, 66, 96, 130];
+/// let meta = rexiv2::MetadataRef::new_from_buffer(&minipng)?;
+/// assert_eq!(meta.get_media_type()?, rexiv2::MediaType::Png);
+/// assert!(!meta.has_exif());
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct MetadataRef {
+    inner: Metadata,
+}
+
+impl MetadataRef {
+    /// Load the metadata from the file found at the given path, without allowing modification.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// let meta = rexiv2::MetadataRef::new_from_path("myphoto.jpg")?;
+    /// println!("{:?}", meta.get_exif_tags()?);
+    /// # Ok::<(), rexiv2::Rexiv2Error>(())
+    /// ```
+    pub fn new_from_path<S: AsRef<ffi::OsStr>>(path: S) -> Result<MetadataRef> {
+        Metadata::new_from_path(path).map(MetadataRef::from)
+    }
+
+    /// Load the metadata from the given data buffer, without allowing modification.
+    pub fn new_from_buffer(data: &[u8]) -> Result<MetadataRef> {
+        Metadata::new_from_buffer(data).map(MetadataRef::from)
+    }
+
+    /// See [`Metadata::supports_exif`].
+    pub fn supports_exif(&self) -> bool {
+        self.inner.supports_exif()
+    }
+
+    /// See [`Metadata::supports_iptc`].
+    pub fn supports_iptc(&self) -> bool {
+        self.inner.supports_iptc()
+    }
+
+    /// See [`Metadata::supports_xmp`].
+    pub fn supports_xmp(&self) -> bool {
+        self.inner.supports_xmp()
+    }
+
+    /// See [`Metadata::capabilities`].
+    pub fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
+    /// See [`Metadata::get_media_type`].
+    pub fn get_media_type(&self) -> Result<MediaType> {
+        self.inner.get_media_type()
+    }
+
+    /// See [`Metadata::get_pixel_width`].
+    pub fn get_pixel_width(&self) -> i32 {
+        self.inner.get_pixel_width()
+    }
+
+    /// See [`Metadata::get_pixel_height`].
+    pub fn get_pixel_height(&self) -> i32 {
+        self.inner.get_pixel_height()
+    }
+
+    /// See [`Metadata::dimensions`].
+    pub fn dimensions(&self) -> Result<(u32, u32)> {
+        self.inner.dimensions()
+    }
+
+    /// See [`Metadata::has_tag`].
+    pub fn has_tag<K: AsTagKey + ?Sized>(&self, tag: &K) -> bool {
+        self.inner.has_tag(tag)
+    }
+
+    /// See [`Metadata::has_exif`].
+    pub fn has_exif(&self) -> bool {
+        self.inner.has_exif()
+    }
+
+    /// See [`Metadata::get_exif_tags`].
+    pub fn get_exif_tags(&self) -> Result<Vec<String>> {
+        self.inner.get_exif_tags()
+    }
+
+    /// See [`Metadata::get_exif_tags_grouped`].
+    pub fn get_exif_tags_grouped(
+        &self,
+    ) -> Result<std::collections::BTreeMap<ExifIfd, Vec<String>>> {
+        self.inner.get_exif_tags_grouped()
+    }
+
+    /// See [`Metadata::has_xmp`].
+    pub fn has_xmp(&self) -> bool {
+        self.inner.has_xmp()
+    }
+
+    /// See [`Metadata::get_xmp_tags`].
+    pub fn get_xmp_tags(&self) -> Result<Vec<String>> {
+        self.inner.get_xmp_tags()
+    }
+
+    /// See [`Metadata::has_iptc`].
+    pub fn has_iptc(&self) -> bool {
+        self.inner.has_iptc()
+    }
+
+    /// See [`Metadata::get_iptc_tags`].
+    pub fn get_iptc_tags(&self) -> Result<Vec<String>> {
+        self.inner.get_iptc_tags()
+    }
+
+    /// See [`Metadata::exif_tag_count`].
+    pub fn exif_tag_count(&self) -> usize {
+        self.inner.exif_tag_count()
+    }
+
+    /// See [`Metadata::xmp_tag_count`].
+    pub fn xmp_tag_count(&self) -> usize {
+        self.inner.xmp_tag_count()
+    }
+
+    /// See [`Metadata::iptc_tag_count`].
+    pub fn iptc_tag_count(&self) -> usize {
+        self.inner.iptc_tag_count()
+    }
+
+    /// See [`Metadata::tag_count`].
+    pub fn tag_count(&self) -> usize {
+        self.inner.tag_count()
+    }
+
+    /// See [`Metadata::is_empty`].
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// See [`Metadata::get_all_interpreted`].
+    pub fn get_all_interpreted(&self) -> std::collections::BTreeMap<String, String> {
+        self.inner.get_all_interpreted()
+    }
+
+    /// See [`Metadata::iter_interpreted`].
+    pub fn iter_interpreted(&self) -> InterpretedValues<'_> {
+        self.inner.iter_interpreted()
+    }
+
+    /// See [`Metadata::to_report`].
+    pub fn to_report(&self, options: ReportOptions) -> Result<String> {
+        self.inner.to_report(options)
+    }
+
+    /// See [`Metadata::to_xmp_document`].
+    pub fn to_xmp_document(&self, include_exif_iptc: bool) -> Result<String> {
+        self.inner.to_xmp_document(include_exif_iptc)
+    }
+
+    /// See [`Metadata::get_tag_string`].
+    pub fn get_tag_string<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<String> {
+        self.inner.get_tag_string(tag)
+    }
+
+    /// See [`Metadata::with_tag_string`].
+    pub fn with_tag_string<K: AsTagKey + ?Sized, R, F: FnOnce(&str) -> R>(
+        &self,
+        tag: &K,
+        f: F,
+    ) -> Result<R> {
+        self.inner.with_tag_string(tag, f)
+    }
+
+    /// See [`Metadata::get_tags_bulk`].
+    pub fn get_tags_bulk<K: AsTagKey>(&self, tags: &[K]) -> Vec<Option<String>> {
+        self.inner.get_tags_bulk(tags)
+    }
+
+    /// See [`Metadata::get_tag_interpreted_string`].
+    pub fn get_tag_interpreted_string<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<String> {
+        self.inner.get_tag_interpreted_string(tag)
+    }
+
+    /// See [`Metadata::get_tag_multiple_strings`].
+    pub fn get_tag_multiple_strings<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<String>> {
+        self.inner.get_tag_multiple_strings(tag)
+    }
+
+    /// See [`Metadata::tag_value_count`].
+    pub fn tag_value_count<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<usize> {
+        self.inner.tag_value_count(tag)
+    }
+
+    /// See [`Metadata::get_tag_value_at`].
+    pub fn get_tag_value_at<K: AsTagKey + ?Sized>(&self, tag: &K, index: usize) -> Result<String> {
+        self.inner.get_tag_value_at(tag, index)
+    }
+
+    /// See [`Metadata::get_tag_lang_alt`].
+    pub fn get_tag_lang_alt<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<(String, String)>> {
+        self.inner.get_tag_lang_alt(tag)
+    }
+
+    /// See [`Metadata::get_xmp_qualifier`].
+    pub fn get_xmp_qualifier<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        qualifier: &str,
+    ) -> Result<String> {
+        self.inner.get_xmp_qualifier(tag, qualifier)
+    }
+
+    /// See [`Metadata::get_xmp_qualifiers`].
+    pub fn get_xmp_qualifiers<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+    ) -> Result<Vec<(String, String)>> {
+        self.inner.get_xmp_qualifiers(tag)
+    }
+
+    /// See [`Metadata::get_tag_numeric`].
+    pub fn get_tag_numeric<K: AsTagKey + ?Sized>(&self, tag: &K) -> i32 {
+        self.inner.get_tag_numeric(tag)
+    }
+
+    /// See [`Metadata::get_tag_rational`].
+    pub fn get_tag_rational<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+    ) -> Option<num_rational::Ratio<i32>> {
+        self.inner.get_tag_rational(tag)
+    }
+
+    /// See [`Metadata::get_tag_datetime`].
+    pub fn get_tag_datetime<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<ExifDateTime> {
+        self.inner.get_tag_datetime(tag)
+    }
+
+    /// See [`Metadata::get_iptc_datetime`].
+    pub fn get_iptc_datetime<K: AsTagKey + ?Sized, L: AsTagKey + ?Sized>(
+        &self,
+        date_tag: &K,
+        time_tag: &L,
+    ) -> Result<ExifDateTime> {
+        self.inner.get_iptc_datetime(date_tag, time_tag)
+    }
+
+    /// See [`Metadata::get_tag_raw`].
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_raw<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<u8>> {
+        self.inner.get_tag_raw(tag)
+    }
+
+    /// See [`Metadata::get_tag_raw_into`].
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_raw_into<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+        buffer: &mut Vec<u8>,
+    ) -> Result<()> {
+        self.inner.get_tag_raw_into(tag, buffer)
+    }
+
+    /// See [`Metadata::get_tag_size`].
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_size<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<usize> {
+        self.inner.get_tag_size(tag)
+    }
+
+    /// See [`Metadata::get_tag_component_count`].
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_component_count<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<usize> {
+        self.inner.get_tag_component_count(tag)
+    }
+
+    /// See [`Metadata::get_tag_raw_values`].
+    #[cfg(feature = "raw-tag-access")]
+    pub fn get_tag_raw_values<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<Vec<Vec<u8>>> {
+        self.inner.get_tag_raw_values(tag)
+    }
+
+    /// See [`Metadata::get_orientation`].
+    pub fn get_orientation(&self) -> Orientation {
+        self.inner.get_orientation()
+    }
+
+    /// See [`Metadata::get_exposure_time`].
+    pub fn get_exposure_time(&self) -> Option<num_rational::Ratio<i32>> {
+        self.inner.get_exposure_time()
+    }
+
+    /// See [`Metadata::get_fnumber`].
+    pub fn get_fnumber(&self) -> Option<f64> {
+        self.inner.get_fnumber()
+    }
+
+    /// See [`Metadata::get_exposure_seconds`].
+    pub fn get_exposure_seconds(&self) -> Option<f64> {
+        self.inner.get_exposure_seconds()
+    }
+
+    /// See [`Metadata::get_max_fnumber`].
+    pub fn get_max_fnumber(&self) -> Option<f64> {
+        self.inner.get_max_fnumber()
+    }
+
+    /// See [`Metadata::get_focal_length`].
+    pub fn get_focal_length(&self) -> Option<f64> {
+        self.inner.get_focal_length()
+    }
+
+    /// See [`Metadata::get_iso_speed`].
+    pub fn get_iso_speed(&self) -> Option<i32> {
+        self.inner.get_iso_speed()
+    }
+
+    /// See [`Metadata::get_iso_sensitivity`].
+    pub fn get_iso_sensitivity(&self) -> Option<IsoSensitivity> {
+        self.inner.get_iso_sensitivity()
+    }
+
+    /// See [`Metadata::get_shutter_speed_value`].
+    pub fn get_shutter_speed_value(&self) -> Option<f64> {
+        self.inner.get_shutter_speed_value()
+    }
+
+    /// See [`Metadata::get_brightness_value`].
+    pub fn get_brightness_value(&self) -> Option<f64> {
+        self.inner.get_brightness_value()
+    }
+
+    /// See [`Metadata::get_exposure_bias`].
+    pub fn get_exposure_bias(&self) -> Option<f64> {
+        self.inner.get_exposure_bias()
+    }
+
+    /// See [`Metadata::get_max_aperture_value`].
+    pub fn get_max_aperture_value(&self) -> Option<f64> {
+        self.inner.get_max_aperture_value()
+    }
+
+    /// See [`Metadata::get_subject_distance`].
+    pub fn get_subject_distance(&self) -> Option<f64> {
+        self.inner.get_subject_distance()
+    }
+
+    /// See [`Metadata::get_saturation`].
+    pub fn get_saturation(&self) -> Option<String> {
+        self.inner.get_saturation()
+    }
+
+    /// See [`Metadata::get_sharpness`].
+    pub fn get_sharpness(&self) -> Option<String> {
+        self.inner.get_sharpness()
+    }
+
+    /// See [`Metadata::get_contrast`].
+    pub fn get_contrast(&self) -> Option<String> {
+        self.inner.get_contrast()
+    }
+
+    /// See [`Metadata::get_white_balance`].
+    pub fn get_white_balance(&self) -> Option<String> {
+        self.inner.get_white_balance()
+    }
+
+    /// See [`Metadata::get_metering_mode`].
+    pub fn get_metering_mode(&self) -> Option<String> {
+        self.inner.get_metering_mode()
+    }
+
+    /// See [`Metadata::get_exposure_program`].
+    pub fn get_exposure_program(&self) -> Option<String> {
+        self.inner.get_exposure_program()
+    }
+
+    /// See [`Metadata::get_flash`].
+    pub fn get_flash(&self) -> Option<String> {
+        self.inner.get_flash()
+    }
+
+    /// See [`Metadata::get_lens_name`].
+    pub fn get_lens_name(&self) -> Option<String> {
+        self.inner.get_lens_name()
+    }
+
+    /// See [`Metadata::get_serial_number`].
+    pub fn get_serial_number(&self) -> Option<String> {
+        self.inner.get_serial_number()
+    }
+
+    /// See [`Metadata::get_camera`].
+    pub fn get_camera(&self) -> Option<Camera> {
+        self.inner.get_camera()
+    }
+
+    /// See [`Metadata::get_software`].
+    pub fn get_software(&self) -> Option<String> {
+        self.inner.get_software()
+    }
+
+    /// See [`Metadata::get_camera_owner`].
+    pub fn get_camera_owner(&self) -> Option<String> {
+        self.inner.get_camera_owner()
+    }
+
+    /// See [`Metadata::get_artist`].
+    pub fn get_artist(&self) -> Option<String> {
+        self.inner.get_artist()
+    }
+
+    /// See [`Metadata::get_provenance`].
+    pub fn get_provenance(&self) -> Provenance {
+        self.inner.get_provenance()
+    }
+
+    /// See [`Metadata::get_interop_index`].
+    pub fn get_interop_index(&self) -> Option<String> {
+        self.inner.get_interop_index()
+    }
+
+    /// See [`Metadata::get_interop_version`].
+    pub fn get_interop_version(&self) -> Option<String> {
+        self.inner.get_interop_version()
+    }
+
+    /// See [`Metadata::validate_interop`].
+    pub fn validate_interop(&self) -> Result<()> {
+        self.inner.validate_interop()
+    }
+
+    /// See [`Metadata::get_capture_settings`].
+    pub fn get_capture_settings(&self) -> CaptureSettings {
+        self.inner.get_capture_settings()
+    }
+
+    /// See [`Metadata::compute_ev`].
+    pub fn compute_ev(&self) -> Option<f64> {
+        self.inner.compute_ev()
+    }
+
+    /// See [`Metadata::get_rating`].
+    pub fn get_rating(&self) -> Option<i8> {
+        self.inner.get_rating()
+    }
+
+    /// See [`Metadata::get_color_label`].
+    pub fn get_color_label(&self) -> Option<String> {
+        self.inner.get_color_label()
+    }
+
+    /// See [`Metadata::get_pick_flag`].
+    pub fn get_pick_flag(&self) -> PickFlag {
+        self.inner.get_pick_flag()
+    }
+
+    /// See [`Metadata::content_signature`].
+    pub fn content_signature(&self) -> ContentSignature {
+        self.inner.content_signature()
+    }
+
+    /// See [`Metadata::fingerprint`].
+    pub fn fingerprint(&self) -> Result<u64> {
+        self.inner.fingerprint()
+    }
+
+    /// See [`Metadata::get_hierarchical_keywords`].
+    pub fn get_hierarchical_keywords(&self) -> Vec<HierarchicalKeyword> {
+        self.inner.get_hierarchical_keywords()
+    }
+
+    /// See [`Metadata::get_collections`].
+    pub fn get_collections(&self) -> Vec<Collection> {
+        self.inner.get_collections()
+    }
+
+    /// See [`Metadata::get_capture_timestamp`].
+    pub fn get_capture_timestamp(&self) -> Result<ExifDateTime> {
+        self.inner.get_capture_timestamp()
+    }
+
+    /// See [`Metadata::resolve_capture_date`].
+    pub fn resolve_capture_date(
+        &self,
+        policy: &CaptureDatePolicy,
+    ) -> Option<(ExifDateTime, CaptureDateSource)> {
+        self.inner.resolve_capture_date(policy)
+    }
+
+    /// See [`Metadata::get_iptc_charset`].
+    pub fn get_iptc_charset(&self) -> IptcCharset {
+        self.inner.get_iptc_charset()
+    }
+
+    /// See [`Metadata::get_iptc_tag_string`].
+    pub fn get_iptc_tag_string<K: AsTagKey + ?Sized>(&self, tag: &K) -> Result<String> {
+        self.inner.get_iptc_tag_string(tag)
+    }
+
+    /// See [`Metadata::get_iptc_tag_multiple_strings`].
+    pub fn get_iptc_tag_multiple_strings<K: AsTagKey + ?Sized>(
+        &self,
+        tag: &K,
+    ) -> Result<Vec<String>> {
+        self.inner.get_iptc_tag_multiple_strings(tag)
+    }
+
+    /// See [`Metadata::get_alt_text`].
+    pub fn get_alt_text(&self, lang: &str) -> Option<String> {
+        self.inner.get_alt_text(lang)
+    }
+
+    /// See [`Metadata::get_extended_description`].
+    pub fn get_extended_description(&self, lang: &str) -> Option<String> {
+        self.inner.get_extended_description(lang)
+    }
+
+    /// See [`Metadata::get_locations_created`].
+    pub fn get_locations_created(&self) -> Vec<IptcLocation> {
+        self.inner.get_locations_created()
+    }
+
+    /// See [`Metadata::get_locations_shown`].
+    pub fn get_locations_shown(&self) -> Vec<IptcLocation> {
+        self.inner.get_locations_shown()
+    }
+
+    /// See [`Metadata::get_persons_in_image`].
+    pub fn get_persons_in_image(&self) -> Vec<String> {
+        self.inner.get_persons_in_image()
+    }
+
+    /// See [`Metadata::get_persons_shown`].
+    pub fn get_persons_shown(&self) -> Vec<PersonShown> {
+        self.inner.get_persons_shown()
+    }
+
+    /// See [`Metadata::get_image_regions`].
+    pub fn get_image_regions(&self) -> Vec<ImageRegion> {
+        self.inner.get_image_regions()
+    }
+
+    /// See [`Metadata::get_digital_source_type`].
+    pub fn get_digital_source_type(&self) -> Option<DigitalSourceType> {
+        self.inner.get_digital_source_type()
+    }
+
+    /// See [`Metadata::get_dng_version`].
+    pub fn get_dng_version(&self) -> Option<[u8; 4]> {
+        self.inner.get_dng_version()
+    }
+
+    /// See [`Metadata::get_unique_camera_model`].
+    pub fn get_unique_camera_model(&self) -> Option<String> {
+        self.inner.get_unique_camera_model()
+    }
+
+    /// See [`Metadata::get_color_matrix1`].
+    pub fn get_color_matrix1(&self) -> Option<Vec<f64>> {
+        self.inner.get_color_matrix1()
+    }
+
+    /// See [`Metadata::get_color_matrix2`].
+    pub fn get_color_matrix2(&self) -> Option<Vec<f64>> {
+        self.inner.get_color_matrix2()
+    }
+
+    /// See [`Metadata::get_as_shot_neutral`].
+    pub fn get_as_shot_neutral(&self) -> Option<Vec<f64>> {
+        self.inner.get_as_shot_neutral()
+    }
+
+    /// See [`Metadata::get_baseline_exposure`].
+    pub fn get_baseline_exposure(&self) -> Option<f64> {
+        self.inner.get_baseline_exposure()
+    }
+
+    /// See [`Metadata::get_thumbnail`].
+    pub fn get_thumbnail(&self) -> Option<&[u8]> {
+        self.inner.get_thumbnail()
+    }
+
+    /// See [`Metadata::get_thumbnail_into`].
+    pub fn get_thumbnail_into(&self, buffer: &mut Vec<u8>) -> bool {
+        self.inner.get_thumbnail_into(buffer)
+    }
+
+    /// See [`Metadata::get_thumbnail_orientation`].
+    pub fn get_thumbnail_orientation(&self) -> Orientation {
+        self.inner.get_thumbnail_orientation()
+    }
+
+    /// See [`Metadata::get_thumbnail_oriented`].
+    #[cfg(feature = "image")]
+    pub fn get_thumbnail_oriented(&self) -> Result<image::DynamicImage> {
+        self.inner.get_thumbnail_oriented()
+    }
+
+    /// See [`Metadata::get_preview_images`].
+    pub fn get_preview_images(&self) -> Vec<PreviewImage> {
+        self.inner.get_preview_images()
+    }
+
+    /// See [`Metadata::preview_count`].
+    pub fn preview_count(&self) -> usize {
+        self.inner.preview_count()
+    }
+
+    /// See [`Metadata::get_gps_info`].
+    pub fn get_gps_info(&self) -> Option<GpsInfo> {
+        self.inner.get_gps_info()
+    }
+
+    /// See [`Metadata::get_gps_processing_method`].
+    pub fn get_gps_processing_method(&self) -> Option<GpsProcessingMethod> {
+        self.inner.get_gps_processing_method()
+    }
+
+    /// See [`Metadata::get_gps_map_datum`].
+    pub fn get_gps_map_datum(&self) -> Option<String> {
+        self.inner.get_gps_map_datum()
+    }
+}
+
+impl From<Metadata> for MetadataRef {
+    /// Give up the ability to modify an existing `Metadata`.
+    fn from(metadata: Metadata) -> MetadataRef {
+        MetadataRef { inner: metadata }
+    }
+}
+
+impl std::fmt::Display for MetadataRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.inner, f)
+    }
+}
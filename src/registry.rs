@@ -0,0 +1,354 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! A registry of the tags known to Exiv2, for building tag pickers and similar tools.
+//!
+//! gexiv2 doesn't expose Exiv2's internal tag tables, so the list of tag names is kept here,
+//! while the labels, descriptions, and types are looked up in the linked Exiv2 library. Names
+//! that the installed version of Exiv2 doesn't recognize are left out. Manufacturer-specific
+//! (maker note) tags are not included.
+
+use super::{tag_info, Result, TagInfo};
+
+/// Names of standard tags, organized by their family and group.
+#[rustfmt::skip]
+const KNOWN_TAGS: &[(&str, &[&str])] = &[
+    (
+        "Exif.Image",
+        &[
+            "ProcessingSoftware", "NewSubfileType", "SubfileType", "ImageWidth", "ImageLength",
+            "BitsPerSample", "Compression", "PhotometricInterpretation", "Thresholding",
+            "CellWidth", "CellLength", "FillOrder", "DocumentName", "ImageDescription", "Make",
+            "Model", "StripOffsets", "Orientation", "SamplesPerPixel", "RowsPerStrip",
+            "StripByteCounts", "XResolution", "YResolution", "PlanarConfiguration",
+            "GrayResponseUnit", "GrayResponseCurve", "T4Options", "T6Options", "ResolutionUnit",
+            "PageName", "XPosition", "YPosition", "PageNumber", "TransferFunction", "Software",
+            "DateTime", "Artist", "HostComputer", "Predictor", "WhitePoint",
+            "PrimaryChromaticities", "ColorMap", "HalftoneHints", "TileWidth", "TileLength",
+            "TileOffsets", "TileByteCounts", "SubIFDs", "InkSet", "InkNames", "NumberOfInks",
+            "DotRange", "TargetPrinter", "ExtraSamples", "SampleFormat", "SMinSampleValue",
+            "SMaxSampleValue", "TransferRange", "ClipPath", "XClipPathUnits", "YClipPathUnits",
+            "Indexed", "JPEGTables", "OPIProxy", "JPEGProc", "JPEGInterchangeFormat",
+            "JPEGInterchangeFormatLength", "JPEGRestartInterval", "JPEGLosslessPredictors",
+            "JPEGPointTransforms", "JPEGQTables", "JPEGDCTables", "JPEGACTables",
+            "YCbCrCoefficients", "YCbCrSubSampling", "YCbCrPositioning", "ReferenceBlackWhite",
+            "XMLPacket", "Rating", "RatingPercent", "ImageID", "CFARepeatPatternDim",
+            "CFAPattern", "BatteryLevel", "Copyright", "ExposureTime", "FNumber", "IPTCNAA",
+            "ImageResources", "ExifTag", "InterColorProfile", "ExposureProgram",
+            "SpectralSensitivity", "GPSTag", "ISOSpeedRatings", "OECF", "Interlace",
+            "TimeZoneOffset", "SelfTimerMode", "DateTimeOriginal", "CompressedBitsPerPixel",
+            "ShutterSpeedValue", "ApertureValue", "BrightnessValue", "ExposureBiasValue",
+            "MaxApertureValue", "SubjectDistance", "MeteringMode", "LightSource", "Flash",
+            "FocalLength", "FlashEnergy", "SpatialFrequencyResponse", "Noise",
+            "FocalPlaneXResolution", "FocalPlaneYResolution", "FocalPlaneResolutionUnit",
+            "ImageNumber", "SecurityClassification", "ImageHistory", "SubjectLocation",
+            "ExposureIndex", "TIFFEPStandardID", "SensingMethod", "XPTitle", "XPComment",
+            "XPAuthor", "XPKeywords", "XPSubject", "PrintImageMatching", "DNGVersion",
+            "DNGBackwardVersion", "UniqueCameraModel", "LocalizedCameraModel", "CFAPlaneColor",
+            "CFALayout", "LinearizationTable", "BlackLevelRepeatDim", "BlackLevel",
+            "BlackLevelDeltaH", "BlackLevelDeltaV", "WhiteLevel", "DefaultScale",
+            "DefaultCropOrigin", "DefaultCropSize", "ColorMatrix1", "ColorMatrix2",
+            "CameraCalibration1", "CameraCalibration2", "ReductionMatrix1", "ReductionMatrix2",
+            "AnalogBalance", "AsShotNeutral", "AsShotWhiteXY", "BaselineExposure",
+            "BaselineNoise", "BaselineSharpness", "BayerGreenSplit", "LinearResponseLimit",
+            "CameraSerialNumber", "LensInfo", "ChromaBlurRadius", "AntiAliasStrength",
+            "ShadowScale", "DNGPrivateData", "MakerNoteSafety", "CalibrationIlluminant1",
+            "CalibrationIlluminant2", "BestQualityScale", "RawDataUniqueID",
+            "OriginalRawFileName", "OriginalRawFileData", "ActiveArea", "MaskedAreas",
+            "AsShotICCProfile", "AsShotPreProfileMatrix", "CurrentICCProfile",
+            "CurrentPreProfileMatrix",
+        ],
+    ),
+    (
+        "Exif.Photo",
+        &[
+            "ExposureTime", "FNumber", "ExposureProgram", "SpectralSensitivity",
+            "ISOSpeedRatings", "OECF", "SensitivityType", "StandardOutputSensitivity",
+            "RecommendedExposureIndex", "ISOSpeed", "ISOSpeedLatitudeyyy",
+            "ISOSpeedLatitudezzz", "ExifVersion", "DateTimeOriginal", "DateTimeDigitized",
+            "OffsetTime", "OffsetTimeOriginal", "OffsetTimeDigitized", "ComponentsConfiguration",
+            "CompressedBitsPerPixel", "ShutterSpeedValue", "ApertureValue", "BrightnessValue",
+            "ExposureBiasValue", "MaxApertureValue", "SubjectDistance", "MeteringMode",
+            "LightSource", "Flash", "FocalLength", "SubjectArea", "MakerNote", "UserComment",
+            "SubSecTime", "SubSecTimeOriginal", "SubSecTimeDigitized", "Temperature", "Humidity",
+            "Pressure", "WaterDepth", "Acceleration", "CameraElevationAngle", "FlashpixVersion",
+            "ColorSpace", "PixelXDimension", "PixelYDimension", "RelatedSoundFile",
+            "InteroperabilityTag", "FlashEnergy", "SpatialFrequencyResponse",
+            "FocalPlaneXResolution", "FocalPlaneYResolution", "FocalPlaneResolutionUnit",
+            "SubjectLocation", "ExposureIndex", "SensingMethod", "FileSource", "SceneType",
+            "CFAPattern", "CustomRendered", "ExposureMode", "WhiteBalance", "DigitalZoomRatio",
+            "FocalLengthIn35mmFilm", "SceneCaptureType", "GainControl", "Contrast", "Saturation",
+            "Sharpness", "DeviceSettingDescription", "SubjectDistanceRange", "ImageUniqueID",
+            "CameraOwnerName", "BodySerialNumber", "LensSpecification", "LensMake", "LensModel",
+            "LensSerialNumber", "CompositeImage", "SourceImageNumberOfCompositeImage",
+            "SourceExposureTimesOfCompositeImage", "Gamma",
+        ],
+    ),
+    (
+        "Exif.Iop",
+        &[
+            "InteroperabilityIndex", "InteroperabilityVersion", "RelatedImageFileFormat",
+            "RelatedImageWidth", "RelatedImageLength",
+        ],
+    ),
+    (
+        "Exif.GPSInfo",
+        &[
+            "GPSVersionID", "GPSLatitudeRef", "GPSLatitude", "GPSLongitudeRef", "GPSLongitude",
+            "GPSAltitudeRef", "GPSAltitude", "GPSTimeStamp", "GPSSatellites", "GPSStatus",
+            "GPSMeasureMode", "GPSDOP", "GPSSpeedRef", "GPSSpeed", "GPSTrackRef", "GPSTrack",
+            "GPSImgDirectionRef", "GPSImgDirection", "GPSMapDatum", "GPSDestLatitudeRef",
+            "GPSDestLatitude", "GPSDestLongitudeRef", "GPSDestLongitude", "GPSDestBearingRef",
+            "GPSDestBearing", "GPSDestDistanceRef", "GPSDestDistance", "GPSProcessingMethod",
+            "GPSAreaInformation", "GPSDateStamp", "GPSDifferential", "GPSHPositioningError",
+        ],
+    ),
+    (
+        "Exif.Thumbnail",
+        &[
+            "NewSubfileType", "ImageWidth", "ImageLength", "BitsPerSample", "Compression",
+            "PhotometricInterpretation", "Orientation", "SamplesPerPixel", "XResolution",
+            "YResolution", "ResolutionUnit", "JPEGInterchangeFormat",
+            "JPEGInterchangeFormatLength", "YCbCrPositioning",
+        ],
+    ),
+    (
+        "Iptc.Envelope",
+        &[
+            "ModelVersion", "Destination", "FileFormat", "FileVersion", "ServiceId",
+            "EnvelopeNumber", "ProductId", "EnvelopePriority", "DateSent", "TimeSent",
+            "CharacterSet", "UNO", "ARMId", "ARMVersion",
+        ],
+    ),
+    (
+        "Iptc.Application2",
+        &[
+            "RecordVersion", "ObjectType", "ObjectAttribute", "ObjectName", "EditStatus",
+            "EditorialUpdate", "Urgency", "Subject", "Category", "SuppCategory", "FixtureId",
+            "Keywords", "LocationCode", "LocationName", "ReleaseDate", "ReleaseTime",
+            "ExpirationDate", "ExpirationTime", "SpecialInstructions", "ActionAdvised",
+            "ReferenceService", "ReferenceDate", "ReferenceNumber", "DateCreated", "TimeCreated",
+            "DigitizationDate", "DigitizationTime", "Program", "ProgramVersion", "ObjectCycle",
+            "Byline", "BylineTitle", "City", "SubLocation", "ProvinceState", "CountryCode",
+            "CountryName", "TransmissionReference", "Headline", "Credit", "Source", "Copyright",
+            "Contact", "Caption", "Writer", "RasterizedCaption", "ImageType", "ImageOrientation",
+            "Language", "AudioType", "AudioRate", "AudioResolution", "AudioDuration",
+            "AudioOutcue", "PreviewFormat", "PreviewVersion", "Preview",
+        ],
+    ),
+    (
+        "Xmp.dc",
+        &[
+            "contributor", "coverage", "creator", "date", "description", "format", "identifier",
+            "language", "publisher", "relation", "rights", "source", "subject", "title", "type",
+        ],
+    ),
+    (
+        "Xmp.xmp",
+        &[
+            "Advisory", "BaseURL", "CreateDate", "CreatorTool", "Identifier", "Label",
+            "MetadataDate", "ModifyDate", "Nickname", "Rating", "Thumbnails",
+        ],
+    ),
+    ("Xmp.xmpRights", &["Certificate", "Marked", "Owner", "UsageTerms", "WebStatement"]),
+    (
+        "Xmp.cc",
+        &[
+            "attributionName", "attributionURL", "deprecatedOn", "jurisdiction", "legalcode",
+            "license", "morePermissions", "permits", "prohibits", "requires",
+        ],
+    ),
+    (
+        "Xmp.xmpMM",
+        &[
+            "DerivedFrom", "DocumentID", "History", "Ingredients", "InstanceID", "ManagedFrom",
+            "Manager", "ManageTo", "ManageUI", "ManagerVariant", "OriginalDocumentID",
+            "Pantry", "RenditionClass", "RenditionParams", "VersionID", "Versions",
+        ],
+    ),
+    (
+        "Xmp.photoshop",
+        &[
+            "AuthorsPosition", "CaptionWriter", "Category", "City", "ColorMode", "Country",
+            "Credit", "DateCreated", "Headline", "ICCProfile", "Instructions", "Source",
+            "State", "SupplementalCategories", "TransmissionReference", "Urgency",
+        ],
+    ),
+    (
+        "Xmp.iptc",
+        &[
+            "AltTextAccessibility", "CountryCode", "CreatorContactInfo", "ExtDescrAccessibility",
+            "IntellectualGenre", "Location", "Scene", "SubjectCode",
+        ],
+    ),
+    (
+        "Xmp.iptcExt",
+        &[
+            "AddlModelInfo", "ArtworkOrObject", "CVterm", "DigImageGUID",
+            "DigitalSourceFileType", "DigitalSourceType", "Event", "ImageRegion",
+            "IptcLastEdited", "LocationCreated", "LocationShown", "MaxAvailHeight",
+            "MaxAvailWidth", "ModelAge", "OrganisationInImageCode", "OrganisationInImageName",
+            "PersonInImage", "PersonInImageWDetails", "RegistryId",
+        ],
+    ),
+    (
+        "Xmp.tiff",
+        &[
+            "ImageWidth", "ImageLength", "BitsPerSample", "Compression",
+            "PhotometricInterpretation", "Orientation", "SamplesPerPixel",
+            "PlanarConfiguration", "YCbCrSubSampling", "YCbCrPositioning", "XResolution",
+            "YResolution", "ResolutionUnit", "TransferFunction", "WhitePoint",
+            "PrimaryChromaticities", "YCbCrCoefficients", "ReferenceBlackWhite", "DateTime",
+            "ImageDescription", "Make", "Model", "Software", "Artist", "Copyright",
+        ],
+    ),
+    (
+        "Xmp.exif",
+        &[
+            "ExifVersion", "FlashpixVersion", "ColorSpace", "ComponentsConfiguration",
+            "CompressedBitsPerPixel", "PixelXDimension", "PixelYDimension", "UserComment",
+            "RelatedSoundFile", "DateTimeOriginal", "DateTimeDigitized", "ExposureTime",
+            "FNumber", "ExposureProgram", "SpectralSensitivity", "ISOSpeedRatings", "OECF",
+            "ShutterSpeedValue", "ApertureValue", "BrightnessValue", "ExposureBiasValue",
+            "MaxApertureValue", "SubjectDistance", "MeteringMode", "LightSource", "Flash",
+            "FocalLength", "SubjectArea", "FlashEnergy", "SpatialFrequencyResponse",
+            "FocalPlaneXResolution", "FocalPlaneYResolution", "FocalPlaneResolutionUnit",
+            "SubjectLocation", "ExposureIndex", "SensingMethod", "FileSource", "SceneType",
+            "CFAPattern", "CustomRendered", "ExposureMode", "WhiteBalance", "DigitalZoomRatio",
+            "FocalLengthIn35mmFilm", "SceneCaptureType", "GainControl", "Contrast", "Saturation",
+            "Sharpness", "DeviceSettingDescription", "SubjectDistanceRange", "ImageUniqueID",
+            "GPSVersionID", "GPSLatitude", "GPSLongitude", "GPSAltitudeRef", "GPSAltitude",
+            "GPSTimeStamp", "GPSSatellites", "GPSStatus", "GPSMeasureMode", "GPSDOP",
+            "GPSSpeedRef", "GPSSpeed", "GPSTrackRef", "GPSTrack", "GPSImgDirectionRef",
+            "GPSImgDirection", "GPSMapDatum", "GPSDestLatitude", "GPSDestLongitude",
+            "GPSDestBearingRef", "GPSDestBearing", "GPSDestDistanceRef", "GPSDestDistance",
+            "GPSProcessingMethod", "GPSAreaInformation", "GPSDifferential",
+        ],
+    ),
+    (
+        "Xmp.exifEX",
+        &[
+            "Gamma", "PhotographicSensitivity", "SensitivityType", "StandardOutputSensitivity",
+            "RecommendedExposureIndex", "ISOSpeed", "ISOSpeedLatitudeyyy",
+            "ISOSpeedLatitudezzz", "CameraOwnerName", "BodySerialNumber", "LensSpecification",
+            "LensMake", "LensModel", "LensSerialNumber",
+        ],
+    ),
+    ("Xmp.aux", &["Lens", "LensID", "LensInfo", "LensSerialNumber", "SerialNumber"]),
+    ("Xmp.lr", &["hierarchicalSubject", "privateRTKInfo"]),
+];
+
+/// List the standard tags known to Exiv2 whose names start with the given family or group.
+///
+/// The prefix must be made up of whole name components: `"Exif"` and `"Exif.Photo"` are valid,
+/// but `"Exif.Ph"` matches nothing. An empty prefix lists every known tag. Tags the installed
+/// version of Exiv2 doesn't recognize are omitted.
+///
+/// # Examples
+/// ```
+/// let tags = rexiv2::get_known_tags("Iptc.Application2")?;
+/// let keywords = tags.iter().find(|t| t.name == "Iptc.Application2.Keywords").unwrap();
+/// assert_eq!(keywords.label, "Keywords");
+/// assert_eq!(keywords.tag_type, rexiv2::TagType::String);
+/// assert!(tags.iter().all(|t| t.name.starts_with("Iptc.Application2.")));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn get_known_tags(prefix: &str) -> Result<Vec<TagInfo>> {
+    let mut tags = vec![];
+    for name in known_tag_names(prefix) {
+        // Not every version of Exiv2 knows about every tag; skip the ones it doesn't.
+        if let Ok(info) = tag_info(&name) {
+            tags.push(info);
+        }
+    }
+    Ok(tags)
+}
+
+/// Search the known tags for ones matching the given text, best matches first.
+///
+/// Each word of the query is matched case-insensitively against the tags' names, labels, and
+/// descriptions; only tags matching every word are returned. Matches in the name or label rank
+/// above matches in the description, and as a last resort a word also matches a name containing
+/// its letters in order, so that abbreviations like `"fnum"` still work.
+///
+/// # Examples
+/// ```
+/// let tags = rexiv2::find_tags("aperture")?;
+/// assert!(tags.iter().any(|t| t.name == "Exif.Photo.ApertureValue"));
+/// assert!(rexiv2::find_tags("no such tag anywhere")?.is_empty());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn find_tags(query: &str) -> Result<Vec<TagInfo>> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if words.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut matches: Vec<(u32, TagInfo)> = get_known_tags("")?
+        .into_iter()
+        .filter_map(|tag| match_score(&tag, query, &words).map(|score| (score, tag)))
+        .collect();
+    matches.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then(a.name.cmp(&b.name)));
+    Ok(matches.into_iter().map(|(_, tag)| tag).collect())
+}
+
+/// Score how well a tag matches the search query, or `None` if some word doesn't match at all.
+fn match_score(tag: &TagInfo, query: &str, words: &[String]) -> Option<u32> {
+    let short_name = tag
+        .name
+        .rsplit('.')
+        .next()
+        .unwrap_or(&tag.name)
+        .to_lowercase();
+    let label = tag.label.to_lowercase();
+    let description = tag.description.to_lowercase();
+
+    let mut score = 0;
+    if short_name == query.to_lowercase().replace(' ', "") || label == query.to_lowercase() {
+        score += 100;
+    }
+    for word in words {
+        score += if short_name.starts_with(word.as_str()) || label.starts_with(word.as_str()) {
+            30
+        } else if short_name.contains(word.as_str()) || label.contains(word.as_str()) {
+            20
+        } else if description.contains(word.as_str()) {
+            5
+        } else if is_subsequence(word, &short_name) {
+            1
+        } else {
+            return None;
+        };
+    }
+    Some(score)
+}
+
+/// Indicates whether all the characters of `needle` appear in `haystack`, in the same order.
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|c| haystack.any(|h| h == c))
+}
+
+/// The names of all the tags in the registry that fall under the given family or group.
+fn known_tag_names(prefix: &str) -> impl Iterator<Item = String> + '_ {
+    let group_prefix = format!("{prefix}.");
+    KNOWN_TAGS
+        .iter()
+        .flat_map(|(group, names)| names.iter().map(move |name| format!("{group}.{name}")))
+        .filter(move |name| prefix.is_empty() || name == prefix || name.starts_with(&group_prefix))
+}
@@ -0,0 +1,132 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{get_tag_label, Metadata, Result, Rexiv2Error};
+
+/// Options that control how [`Metadata::to_report`] lays out its listing.
+///
+/// [`Metadata::to_report`]: struct.Metadata.html#method.to_report
+///
+/// # Examples
+/// ```
+/// let options = rexiv2::ReportOptions::new().labels(true).max_value_len(40);
+/// # let _ = options;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReportOptions {
+    interpreted: bool,
+    labels: bool,
+    max_value_len: Option<usize>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> ReportOptions {
+        ReportOptions {
+            interpreted: true,
+            labels: false,
+            max_value_len: None,
+        }
+    }
+}
+
+impl ReportOptions {
+    /// Create the default set of options: interpreted values listed under their full tag names.
+    pub fn new() -> ReportOptions {
+        ReportOptions::default()
+    }
+
+    /// Show values as formatted for display by Exiv2 (e.g. `1/200 s`), or as stored (`1/200`).
+    pub fn interpreted(mut self, interpreted: bool) -> ReportOptions {
+        self.interpreted = interpreted;
+        self
+    }
+
+    /// List tags under their human-readable labels (e.g. `Exposure Time`), rather than their
+    /// full names (`Exif.Photo.ExposureTime`).
+    pub fn labels(mut self, labels: bool) -> ReportOptions {
+        self.labels = labels;
+        self
+    }
+
+    /// Shorten values longer than the given number of characters, such as embedded binary data.
+    pub fn max_value_len(mut self, chars: usize) -> ReportOptions {
+        self.max_value_len = Some(chars);
+        self
+    }
+}
+
+/// Render every tag of the given metadata, grouped by domain and tag group.
+pub(crate) fn render(meta: &Metadata, options: ReportOptions) -> Result<String> {
+    let mut tags = meta.get_exif_tags()?;
+    tags.extend(meta.get_iptc_tags()?);
+    tags.extend(meta.get_xmp_tags()?);
+
+    // Keep the groups in the order their first tag appears in, which follows the file's layout.
+    let mut groups: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    for tag in tags {
+        let name = if options.labels {
+            get_tag_label(&tag).ok().filter(|label| !label.is_empty())
+        } else {
+            None
+        };
+        let value = if options.interpreted {
+            meta.get_tag_interpreted_string(&tag)
+        } else {
+            meta.get_tag_string(&tag)
+        };
+        let value = match value {
+            Ok(value) => tidy_value(&value, options.max_value_len),
+            Err(Rexiv2Error::NoValue) => String::new(),
+            Err(_) => "(unreadable)".to_string(),
+        };
+        let group = tag.rsplit_once('.').map_or("", |(group, _)| group);
+        let row = (name.unwrap_or_else(|| tag.clone()), value);
+        match groups.iter_mut().find(|(name, _)| name == group) {
+            Some((_, rows)) => rows.push(row),
+            None => groups.push((group.to_string(), vec![row])),
+        }
+    }
+
+    let width = groups
+        .iter()
+        .flat_map(|(_, rows)| rows)
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut report = String::new();
+    for (group, rows) in &groups {
+        report.push_str(&format!("---- {group} ----\n"));
+        for (name, value) in rows {
+            report.push_str(&format!("{name:width$} : {value}\n"));
+        }
+    }
+    Ok(report)
+}
+
+/// Flatten a value onto a single line, shortening it if it's too long.
+fn tidy_value(value: &str, max_len: Option<usize>) -> String {
+    let value = value.trim().replace(char::is_control, " ");
+    match max_len {
+        Some(max_len) if value.chars().count() > max_len => {
+            let mut shortened: String = value.chars().take(max_len).collect();
+            shortened.push_str("...");
+            shortened
+        }
+        _ => value,
+    }
+}
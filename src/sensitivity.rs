@@ -0,0 +1,139 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{tags, Metadata};
+
+/// What the sensitivity of a camera was measured as, as recorded by `Exif.Photo.SensitivityType`.
+///
+/// Exif 2.3 defines three measures of sensitivity: the standard output sensitivity (SOS), the
+/// recommended exposure index (REI), and the ISO speed. A camera may record several of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SensitivityType {
+    /// The measure isn't known, as in files predating Exif 2.3 (value 0).
+    Unknown,
+    /// Standard output sensitivity (value 1).
+    StandardOutputSensitivity,
+    /// Recommended exposure index (value 2).
+    RecommendedExposureIndex,
+    /// ISO speed (value 3).
+    IsoSpeed,
+    /// Standard output sensitivity and recommended exposure index (value 4).
+    SosAndRei,
+    /// Standard output sensitivity and ISO speed (value 5).
+    SosAndIsoSpeed,
+    /// Recommended exposure index and ISO speed (value 6).
+    ReiAndIsoSpeed,
+    /// Standard output sensitivity, recommended exposure index, and ISO speed (value 7).
+    SosReiAndIsoSpeed,
+}
+
+impl SensitivityType {
+    /// Convert a raw `Exif.Photo.SensitivityType` value, treating unknown values as `Unknown`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rexiv2::SensitivityType;
+    /// assert_eq!(SensitivityType::from_exif(2), SensitivityType::RecommendedExposureIndex);
+    /// assert_eq!(SensitivityType::from_exif(42), SensitivityType::Unknown);
+    /// ```
+    pub fn from_exif(value: u16) -> SensitivityType {
+        match value {
+            1 => SensitivityType::StandardOutputSensitivity,
+            2 => SensitivityType::RecommendedExposureIndex,
+            3 => SensitivityType::IsoSpeed,
+            4 => SensitivityType::SosAndRei,
+            5 => SensitivityType::SosAndIsoSpeed,
+            6 => SensitivityType::ReiAndIsoSpeed,
+            7 => SensitivityType::SosReiAndIsoSpeed,
+            _ => SensitivityType::Unknown,
+        }
+    }
+
+    /// The tags holding the full values of the measures this type includes, in order of
+    /// preference.
+    fn value_tags(self) -> &'static [(SensitivityType, &'static str)] {
+        const ISO: (SensitivityType, &str) = (SensitivityType::IsoSpeed, tags::exif::ISO_SPEED);
+        const SOS: (SensitivityType, &str) = (
+            SensitivityType::StandardOutputSensitivity,
+            tags::exif::STANDARD_OUTPUT_SENSITIVITY,
+        );
+        const REI: (SensitivityType, &str) = (
+            SensitivityType::RecommendedExposureIndex,
+            tags::exif::RECOMMENDED_EXPOSURE_INDEX,
+        );
+        match self {
+            SensitivityType::StandardOutputSensitivity => &[SOS],
+            SensitivityType::RecommendedExposureIndex => &[REI],
+            SensitivityType::IsoSpeed => &[ISO],
+            SensitivityType::SosAndRei => &[SOS, REI],
+            SensitivityType::SosAndIsoSpeed => &[ISO, SOS],
+            SensitivityType::ReiAndIsoSpeed => &[ISO, REI],
+            SensitivityType::Unknown | SensitivityType::SosReiAndIsoSpeed => &[ISO, SOS, REI],
+        }
+    }
+}
+
+/// The effective sensitivity of the camera when a photograph was taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IsoSensitivity {
+    /// The sensitivity, e.g. `400`.
+    pub value: u32,
+    /// What the value was measured as.
+    pub sensitivity_type: SensitivityType,
+}
+
+/// `Exif.Photo.ISOSpeedRatings` is a 16-bit value, so this is recorded when the sensitivity is
+/// too high to fit, and the actual value must be looked up elsewhere.
+const ISO_SPEED_RATINGS_OVERFLOW: u32 = 65535;
+
+/// Work out the effective sensitivity recorded in the given metadata, per Exif 2.3.
+pub(crate) fn effective_sensitivity(meta: &Metadata) -> Option<IsoSensitivity> {
+    let sensitivity_type = read_u32(meta, tags::exif::SENSITIVITY_TYPE)
+        .and_then(|value| u16::try_from(value).ok())
+        .map_or(SensitivityType::Unknown, SensitivityType::from_exif);
+
+    match read_u32(meta, tags::exif::ISO_SPEED_RATINGS) {
+        Some(value) if value > 0 && value < ISO_SPEED_RATINGS_OVERFLOW => {
+            return Some(IsoSensitivity { value, sensitivity_type });
+        }
+        _ => {}
+    }
+
+    let extended = sensitivity_type
+        .value_tags()
+        .iter()
+        .find_map(|(measure, tag)| {
+            let value = read_u32(meta, tag).filter(|value| *value > 0)?;
+            Some(IsoSensitivity { value, sensitivity_type: *measure })
+        });
+    extended.or_else(|| {
+        // Fall back on the makernotes, which Exiv2 consults when the Exif tags are missing.
+        let value = u32::try_from(meta.get_iso_speed()?).ok()?;
+        Some(IsoSensitivity { value, sensitivity_type: SensitivityType::Unknown })
+    })
+}
+
+/// Read the first value of a numeric tag.
+fn read_u32(meta: &Metadata, tag: &str) -> Option<u32> {
+    meta.get_tag_string(tag)
+        .ok()?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
@@ -0,0 +1,337 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Cheap identification of media types and other properties from the first few bytes of a file.
+
+use super::{ByteOrder, MediaType};
+use std::ops::Range;
+
+/// Guess the media type of a file from the "magic" bytes at the start of its contents.
+///
+/// This only inspects a handful of bytes and doesn't involve gexiv2 at all, so it's much cheaper
+/// than loading the metadata; it's useful for skipping files that aren't worth opening. Passing
+/// the first few kilobytes of a file is enough. Many RAW formats (such as DNG, NEF, and ARW) are
+/// structured as TIFF files and will be reported as `MediaType::Tiff`. TGA files have no magic
+/// bytes, so they are recognized by the values in their header instead (or by their footer, if the
+/// whole file is passed).
+///
+/// Returns `None` if the data isn't recognized as any of the supported media types.
+///
+/// # Examples
+/// ```
+/// let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+///                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+///                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+///                78, 68, 174, 66, 96, 130];
+/// assert_eq!(rexiv2::detect_media_type(&minipng), Some(rexiv2::MediaType::Png));
+/// assert_eq!(rexiv2::detect_media_type(b"Hello, world!"), None);
+/// ```
+pub fn detect_media_type(data: &[u8]) -> Option<MediaType> {
+    if data.starts_with(b"\xFF\xD8\xFF") {
+        Some(MediaType::Jpeg)
+    } else if data.starts_with(b"\x89PNG\r\n\x1A\n") {
+        Some(MediaType::Png)
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some(MediaType::Gif)
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        Some(MediaType::Webp)
+    } else if data.get(4..8) == Some(b"ftyp") {
+        detect_bmff(data)
+    } else if data.starts_with(b"II*\0") || data.starts_with(b"MM\0*") {
+        if data.get(8..11) == Some(b"CR\x02") {
+            Some(MediaType::CanonCr2)
+        } else {
+            Some(MediaType::Tiff)
+        }
+    } else if data.starts_with(b"IIRO") || data.starts_with(b"IIRS") || data.starts_with(b"MMOR") {
+        Some(MediaType::OlympusOrf)
+    } else if data.starts_with(b"IIU\0") {
+        Some(MediaType::PanasonicRw2)
+    } else if data.starts_with(b"II\x1A\0") && data.get(6..14) == Some(b"HEAPCCDR") {
+        Some(MediaType::CanonCrw)
+    } else if data.starts_with(b"FUJIFILMCCD-RAW") {
+        Some(MediaType::FujiRaf)
+    } else if data.starts_with(b"\0MRM") {
+        Some(MediaType::MinoltaMrw)
+    } else if data.starts_with(b"8BPS") {
+        Some(MediaType::Psd)
+    } else if data.starts_with(b"\0\0\0\x0CjP  \r\n\x87\n") {
+        Some(MediaType::Jp2)
+    } else if data.starts_with(b"\xFF\x0A") || data.starts_with(b"\0\0\0\x0CJXL \r\n\x87\n") {
+        Some(MediaType::JpegXl)
+    } else if data.starts_with(b"%!PS-Adobe") || data.starts_with(b"\xC5\xD0\xD3\xC6") {
+        Some(MediaType::Eps)
+    } else if data.starts_with(b"\x76\x2F\x31\x01") {
+        Some(MediaType::Exr)
+    } else if data.starts_with(b"BM") && data.len() >= 14 {
+        Some(MediaType::Bmp)
+    } else if data.ends_with(b"TRUEVISION-XFILE.\0") || is_tga_header(data) {
+        Some(MediaType::Tga)
+    } else {
+        None
+    }
+}
+
+/// Indicates whether the data starts with a plausible TGA header. TGA files have no magic bytes at
+/// the start (only newer ones have a signature, in a footer), so this checks that each field of
+/// the 18 byte header holds a value the format allows.
+fn is_tga_header(data: &[u8]) -> bool {
+    let header = match data.get(..18) {
+        Some(header) => header,
+        None => return false,
+    };
+    let (color_map_type, image_type) = (header[1], header[2]);
+    let valid_color_map = match color_map_type {
+        0 => header[3..8].iter().all(|&byte| byte == 0),
+        1 => matches!(header[7], 15 | 16 | 24 | 32),
+        _ => false,
+    };
+    // Image types 1 and 9 are color-mapped, so need a color map.
+    let valid_image_type = match image_type {
+        1 | 9 => color_map_type == 1,
+        2 | 3 | 10 | 11 => true,
+        _ => false,
+    };
+    let width = u16::from_le_bytes([header[12], header[13]]);
+    let height = u16::from_le_bytes([header[14], header[15]]);
+    valid_color_map
+        && valid_image_type
+        && width > 0
+        && height > 0
+        && matches!(header[16], 8 | 15 | 16 | 24 | 32)
+        // The top two bits of the image descriptor are reserved.
+        && header[17] & 0xC0 == 0
+}
+
+/// Work out which kind of ISO Base Media File Format (BMFF) file the data is, based on the
+/// brands listed in its leading `ftyp` box.
+fn detect_bmff(data: &[u8]) -> Option<MediaType> {
+    let box_size = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?) as usize;
+    let box_data = data.get(8..box_size.min(data.len()).max(8))?;
+
+    // The major brand comes first, followed by a minor version and the compatible brands.
+    let major_brand = box_data.get(0..4)?;
+    let compatible_brands = box_data.get(8..).unwrap_or_default().chunks_exact(4);
+    std::iter::once(major_brand)
+        .chain(compatible_brands)
+        .find_map(|brand| match brand {
+            b"avif" | b"avis" => Some(MediaType::Avif),
+            b"heic" | b"heix" | b"heim" | b"heis" | b"hevc" | b"hevx" => Some(MediaType::Heic),
+            b"crx " => Some(MediaType::CanonCr3),
+            b"jxl " => Some(MediaType::JpegXl),
+            _ => None,
+        })
+        .or(match major_brand {
+            b"mif1" | b"msf1" | b"heif" => Some(MediaType::Heif),
+            _ => None,
+        })
+}
+
+/// Find the byte order of the TIFF structure holding a file's Exif metadata, from the start of its
+/// contents.
+///
+/// This understands TIFF-based files (including most RAW formats), JPEG files with an Exif APP1
+/// segment, and bare Exif blobs starting with `Exif\0\0`.
+///
+/// It works on the file's contents because a loaded `Metadata` doesn't record the byte order, and
+/// there is no way to choose it when writing: Exiv2 keeps the existing byte order when saving
+/// metadata back to a file.
+///
+/// Returns `None` if no Exif TIFF header was found.
+///
+/// # Examples
+/// ```
+/// use rexiv2::ByteOrder;
+/// assert_eq!(rexiv2::detect_byte_order(b"MM\0*\0\0\0\x08"), Some(ByteOrder::BigEndian));
+/// assert_eq!(rexiv2::detect_byte_order(b"Exif\0\0II*\0"), Some(ByteOrder::LittleEndian));
+/// ```
+pub fn detect_byte_order(data: &[u8]) -> Option<ByteOrder> {
+    if let Some(tiff) = data.strip_prefix(b"Exif\0\0") {
+        return tiff_byte_order(tiff);
+    }
+    if !data.starts_with(b"\xFF\xD8") {
+        return tiff_byte_order(data);
+    }
+
+    // Walk the JPEG segments up to the start of the image data, looking for Exif.
+    let mut pos = 2;
+    while let Some(&[0xFF, marker, len_hi, len_lo]) = data.get(pos..pos + 4) {
+        if marker == 0xDA {
+            break;
+        }
+        let segment_len = u16::from_be_bytes([len_hi, len_lo]) as usize;
+        if marker == 0xE1 {
+            if let Some(tiff) = data
+                .get(pos + 4..)
+                .and_then(|d| d.strip_prefix(b"Exif\0\0"))
+            {
+                return tiff_byte_order(tiff);
+            }
+        }
+        pos += 2 + segment_len;
+    }
+    None
+}
+
+/// Find the C2PA manifest store (also known as Content Credentials) embedded in a file, returning
+/// its byte range within the given data.
+///
+/// This only looks for the container holding the manifest, without parsing or validating it; use
+/// a full C2PA implementation for that. The manifest is found in APP11 segments in JPEG files,
+/// `caBX` chunks in PNG files, `C2PA` chunks in WebP files, and top-level `uuid` boxes in ISO BMFF
+/// files (such as HEIC, AVIF, and MP4), as well as in standalone `.c2pa` files. For JPEG files
+/// the range covers all the segments making up the manifest, including their headers.
+///
+/// Returns `None` if no manifest was found, including when the data ends before the manifest.
+///
+/// # Examples
+/// ```
+/// let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+///                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+///                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+///                78, 68, 174, 66, 96, 130];
+/// assert_eq!(rexiv2::find_c2pa_manifest(&minipng), None);
+/// ```
+pub fn find_c2pa_manifest(data: &[u8]) -> Option<Range<usize>> {
+    if data.starts_with(b"\xFF\xD8") {
+        find_c2pa_in_jpeg(data)
+    } else if data.starts_with(b"\x89PNG\r\n\x1A\n") {
+        find_c2pa_in_png(data)
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        find_c2pa_in_webp(data)
+    } else if data.get(4..8) == Some(b"jumb") {
+        is_c2pa_jumbf(data).then_some(0..data.len())
+    } else if data.get(4..8) == Some(b"ftyp") {
+        find_c2pa_in_bmff(data)
+    } else {
+        None
+    }
+}
+
+/// The UUID of the top-level ISO BMFF box holding a C2PA manifest store.
+const C2PA_BMFF_UUID: [u8; 16] = [
+    0xD8, 0xFE, 0xC3, 0xD6, 0x1B, 0x0E, 0x48, 0x3C, 0x92, 0x97, 0x58, 0x28, 0x87, 0x7E, 0xC4, 0x81,
+];
+
+/// Indicates whether the data starts with a JUMBF superbox labelled as a C2PA manifest store.
+fn is_c2pa_jumbf(data: &[u8]) -> bool {
+    // The superbox header is followed by a description box: its own header, a 16 byte UUID
+    // starting with "c2pa", a toggles byte, then the label.
+    data.get(4..8) == Some(b"jumb")
+        && data.get(12..16) == Some(b"jumd")
+        && data.get(16..20) == Some(b"c2pa")
+}
+
+fn find_c2pa_in_jpeg(data: &[u8]) -> Option<Range<usize>> {
+    let mut manifest: Option<(&[u8], Range<usize>)> = None;
+    let mut pos = 2;
+    while let Some(&[0xFF, marker, len_hi, len_lo]) = data.get(pos..pos + 4) {
+        if marker == 0xDA {
+            break;
+        }
+        let end = pos + 2 + u16::from_be_bytes([len_hi, len_lo]) as usize;
+        // APP11 segments hold JPEG XT packets: a "JP" marker, a box instance number, and a packet
+        // sequence number, followed by the JUMBF box. Only the first packet holds the whole box
+        // header; the rest repeat just its length and type, so are matched by instance number.
+        let instance = data.get(pos + 6..pos + 8);
+        let is_c2pa = marker == 0xEB
+            && data.get(pos + 4..pos + 6) == Some(b"JP")
+            && match manifest {
+                Some((first_instance, _)) => {
+                    instance == Some(first_instance)
+                        && data.get(pos + 16..pos + 20) == Some(b"jumb")
+                }
+                None => data.get(pos + 12..).map_or(false, is_c2pa_jumbf),
+            };
+        if is_c2pa {
+            if end > data.len() {
+                return None;
+            }
+            manifest = match manifest {
+                Some((first_instance, range)) => Some((first_instance, range.start..end)),
+                None => Some((instance?, pos..end)),
+            };
+        }
+        pos = end;
+    }
+    manifest.map(|(_, range)| range)
+}
+
+fn find_c2pa_in_png(data: &[u8]) -> Option<Range<usize>> {
+    let mut pos = 8;
+    while let Some(header) = data.get(pos..pos + 8) {
+        let len = u32::from_be_bytes(header[0..4].try_into().ok()?) as usize;
+        let content = pos + 8..pos + 8 + len;
+        if &header[4..8] == b"caBX" {
+            return (content.end <= data.len()).then_some(content);
+        }
+        // Skip the chunk contents and CRC.
+        pos = content.end + 4;
+    }
+    None
+}
+
+fn find_c2pa_in_webp(data: &[u8]) -> Option<Range<usize>> {
+    let mut pos = 12;
+    while let Some(header) = data.get(pos..pos + 8) {
+        let len = u32::from_le_bytes(header[4..8].try_into().ok()?) as usize;
+        let content = pos + 8..pos + 8 + len;
+        if &header[0..4] == b"C2PA" {
+            return (content.end <= data.len()).then_some(content);
+        }
+        // Chunks are padded to an even length.
+        pos = content.end + len % 2;
+    }
+    None
+}
+
+fn find_c2pa_in_bmff(data: &[u8]) -> Option<Range<usize>> {
+    let mut pos = 0;
+    while let Some(header) = data.get(pos..pos + 8) {
+        let (header_len, len) = match u32::from_be_bytes(header[0..4].try_into().ok()?) {
+            // The box extends to the end of the file.
+            0 => (8, data.len() - pos),
+            // The size is stored as a 64-bit value after the type.
+            1 => {
+                let len = u64::from_be_bytes(data.get(pos + 8..pos + 16)?.try_into().ok()?);
+                (16, usize::try_from(len).ok()?)
+            }
+            len => (8, len as usize),
+        };
+        if len < header_len {
+            return None;
+        }
+        let end = pos.checked_add(len)?;
+        if &header[4..8] == b"uuid"
+            && data.get(pos + header_len..pos + header_len + 16) == Some(&C2PA_BMFF_UUID)
+        {
+            return (end <= data.len()).then_some(pos..end);
+        }
+        pos = end;
+    }
+    None
+}
+
+/// Read the byte order marker at the start of a TIFF header.
+fn tiff_byte_order(data: &[u8]) -> Option<ByteOrder> {
+    match data.get(0..2)? {
+        b"II" => Some(ByteOrder::LittleEndian),
+        b"MM" => Some(ByteOrder::BigEndian),
+        _ => None,
+    }
+}
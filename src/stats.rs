@@ -0,0 +1,161 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Statistics about the camera gear and settings used across many files.
+//!
+//! # Examples
+//! ```
+//! # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+//! #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+//! #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+//! #               69, 78, 68, 174, 66, 96, 130];
+//! let photos: Vec<rexiv2::Metadata> = [200, 400, 400]
+//!     .iter()
+//!     .map(|iso| {
+//!         let meta = rexiv2::Metadata::new_from_buffer(&minipng)?;
+//!         meta.set_tag_numeric("Exif.Photo.ISOSpeedRatings", *iso)?;
+//!         Ok(meta)
+//!     })
+//!     .collect::<rexiv2::Result<_>>()?;
+//! let stats: rexiv2::stats::GearStats = photos.iter().collect();
+//! assert_eq!(stats.files, 3);
+//! assert_eq!(stats.iso_speeds.most_common(), Some((&400, 2)));
+//! # Ok::<(), rexiv2::Rexiv2Error>(())
+//! ```
+
+use super::{tags, Metadata};
+use std::borrow::Borrow;
+
+/// How often each value occurs among a set of files.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Distribution<T> {
+    /// The distinct values and their counts, ordered by value.
+    counts: Vec<(T, usize)>,
+}
+
+impl<T> Default for Distribution<T> {
+    fn default() -> Distribution<T> {
+        Distribution { counts: vec![] }
+    }
+}
+
+impl<T: PartialOrd> Distribution<T> {
+    /// Count one more occurrence of the given value.
+    pub fn add(&mut self, value: T) {
+        match self.counts.iter().position(|(known, _)| *known >= value) {
+            Some(index) if self.counts[index].0 == value => self.counts[index].1 += 1,
+            Some(index) => self.counts.insert(index, (value, 1)),
+            None => self.counts.push((value, 1)),
+        }
+    }
+
+    /// The number of times the given value occurred.
+    pub fn count(&self, value: &T) -> usize {
+        self.counts
+            .iter()
+            .find(|(known, _)| known == value)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// The most common value, along with its count. Ties go to the smallest value.
+    pub fn most_common(&self) -> Option<(&T, usize)> {
+        self.iter()
+            .reduce(|best, next| if next.1 > best.1 { next } else { best })
+    }
+
+    /// The distinct values along with their counts, ordered by value.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(value, count)| (value, *count))
+    }
+
+    /// The number of distinct values.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Indicates whether no values have been counted.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The total number of occurrences of all values.
+    pub fn total(&self) -> usize {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// Distributions of the camera gear and settings used across a set of files.
+///
+/// Files missing a value simply don't contribute to its distribution.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GearStats {
+    /// The number of files counted.
+    pub files: usize,
+    /// The focal lengths, in millimetres, rounded to one decimal place.
+    pub focal_lengths: Distribution<f64>,
+    /// The ISO speeds.
+    pub iso_speeds: Distribution<i32>,
+    /// The f-numbers, rounded to one decimal place.
+    pub apertures: Distribution<f64>,
+    /// The camera models, including the manufacturer's name.
+    pub cameras: Distribution<String>,
+    /// The lens models.
+    pub lenses: Distribution<String>,
+}
+
+impl GearStats {
+    /// Count the gear and settings used for one more file.
+    pub fn add(&mut self, meta: &Metadata) {
+        self.files += 1;
+        if let Some(focal_length) = meta.get_focal_length() {
+            self.focal_lengths.add(round_to_tenths(focal_length));
+        }
+        if let Some(iso_speed) = meta.get_iso_speed() {
+            self.iso_speeds.add(iso_speed);
+        }
+        if let Some(fnumber) = meta.get_fnumber() {
+            self.apertures.add(round_to_tenths(fnumber));
+        }
+        if let Some(camera) = meta.get_camera().and_then(|camera| camera.name()) {
+            self.cameras.add(camera);
+        }
+        if let Some(lens) = meta.get_trimmed_string(&[tags::exif::LENS_MODEL]) {
+            self.lenses.add(lens);
+        }
+    }
+}
+
+impl<M: Borrow<Metadata>> Extend<M> for GearStats {
+    fn extend<I: IntoIterator<Item = M>>(&mut self, iter: I) {
+        for meta in iter {
+            self.add(meta.borrow());
+        }
+    }
+}
+
+impl<M: Borrow<Metadata>> FromIterator<M> for GearStats {
+    fn from_iter<I: IntoIterator<Item = M>>(iter: I) -> GearStats {
+        let mut stats = GearStats::default();
+        stats.extend(iter);
+        stats
+    }
+}
+
+fn round_to_tenths(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
@@ -0,0 +1,384 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{get_tag_type, is_iptc_tag, Metadata, Result, Rexiv2Error, TagType};
+use std::ffi;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// The layout used by a [`TableWriter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TableFormat {
+    /// Comma-separated values, quoted as described in RFC 4180.
+    Csv,
+    /// Tab-separated values. Tabs and line breaks within values are replaced by spaces.
+    Tsv,
+}
+
+/// Writes the values of chosen tags for a batch of files as a table, one row per file.
+///
+/// The first column holds the name of each file, followed by one column per tag, and missing
+/// tags are left empty. By default every value is interpreted for display (e.g. `1/200 s`), with
+/// any repeated values formatted as Exiv2 shows them. Such a table is meant to be read, not
+/// applied back with [`apply_table`].
+///
+/// With [`interpreted(false)`](TableWriter::interpreted) every value is written in its raw form
+/// instead (e.g. `1/200`). The values of XMP bags and sequences and of IPTC datasets are then
+/// listed separated by `"; "`, with each `\` or `;` within a value escaped by a preceding `\`,
+/// which is the form [`apply_table`] reads back.
+///
+/// # Examples
+/// ```
+/// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+/// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+/// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+/// #                78, 68, 174, 66, 96, 130];
+/// use rexiv2::{TableFormat, TableWriter};
+///
+/// let meta = rexiv2::Metadata::new_from_buffer(&minipng)?;
+/// meta.set_tag_string("Exif.Image.Artist", "Doe, Jane")?;
+/// meta.set_tag_multiple_strings("Xmp.dc.subject", &["fox", "dog; cat"])?;
+///
+/// let tags = ["Exif.Image.Artist", "Xmp.dc.subject"];
+/// let mut table = TableWriter::new(Vec::new(), &tags, TableFormat::Csv)?.interpreted(false);
+/// table.write_row("fox.png", &meta)?;
+/// assert_eq!(
+///     String::from_utf8(table.into_inner()?).unwrap(),
+///     "File,Exif.Image.Artist,Xmp.dc.subject\r\nfox.png,\"Doe, Jane\",fox; dog\; cat\r\n",
+/// );
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Debug)]
+pub struct TableWriter<W: Write> {
+    inner: W,
+    tags: Vec<String>,
+    format: TableFormat,
+    interpreted: bool,
+}
+
+impl<W: Write> TableWriter<W> {
+    /// Start a table with a column for each of the given tags, writing its header row.
+    pub fn new<S: AsRef<str>>(inner: W, tags: &[S], format: TableFormat) -> Result<Self> {
+        let mut writer = TableWriter {
+            inner,
+            tags: tags.iter().map(|tag| tag.as_ref().to_string()).collect(),
+            format,
+            interpreted: true,
+        };
+        let header: Vec<String> = std::iter::once("File".to_string())
+            .chain(writer.tags.iter().cloned())
+            .collect();
+        writer.write_fields(&header)?;
+        Ok(writer)
+    }
+
+    /// Write each value in its interpreted form, for display (the default), or in its raw form,
+    /// which [`apply_table`] can read back.
+    pub fn interpreted(mut self, interpreted: bool) -> Self {
+        self.interpreted = interpreted;
+        self
+    }
+
+    /// Add a row holding the chosen tags of already-loaded metadata.
+    pub fn write_row(&mut self, name: &str, meta: &Metadata) -> Result<()> {
+        let mut fields = vec![name.to_string()];
+        for tag in &self.tags {
+            fields.push(cell_value(meta, tag, self.interpreted)?);
+        }
+        self.write_fields(&fields)
+    }
+
+    /// Load the metadata of the file at the given path and add a row for it.
+    pub fn write_file<S: AsRef<ffi::OsStr>>(&mut self, path: S) -> Result<()> {
+        let meta = Metadata::new_from_path(&path)?;
+        self.write_row(&Path::new(&path).display().to_string(), &meta)
+    }
+
+    /// Flush any buffered output and return the underlying writer.
+    ///
+    /// Returns an error if the flush fails, in which case the end of the table may not have been
+    /// written.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn write_fields(&mut self, fields: &[String]) -> Result<()> {
+        let line = match self.format {
+            TableFormat::Csv => {
+                let fields: Vec<String> = fields.iter().map(|field| quote_csv(field)).collect();
+                fields.join(",") + "\r\n"
+            }
+            TableFormat::Tsv => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|field| field.replace(['\t', '\r', '\n'], " "))
+                    .collect();
+                fields.join("\t") + "\n"
+            }
+        };
+        self.inner.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Write a table of the chosen tags for each of the given files, as described for
+/// [`TableWriter`].
+///
+/// # Examples
+/// ```no_run
+/// let files = ["a.jpg", "b.jpg"];
+/// let tags = ["Exif.Photo.ExposureTime", "Exif.Photo.FNumber", "Exif.Photo.ISOSpeedRatings"];
+/// let csv = std::fs::File::create("settings.csv")?;
+/// rexiv2::write_table(csv, &files, &tags, rexiv2::TableFormat::Csv)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn write_table<W, P, S>(inner: W, files: &[P], tags: &[S], format: TableFormat) -> Result<W>
+where
+    W: Write,
+    P: AsRef<ffi::OsStr>,
+    S: AsRef<str>,
+{
+    let mut table = TableWriter::new(inner, tags, format)?;
+    for file in files {
+        table.write_file(file)?;
+    }
+    table.into_inner()
+}
+
+/// What happened when applying one row of a table with [`apply_table`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct RowOutcome {
+    /// The line of the table the row starts on, counting the header as line 1.
+    pub line: usize,
+    /// The file the row refers to.
+    pub path: PathBuf,
+    /// The number of tags set on the file, or the reason the row couldn't be applied.
+    pub result: Result<usize>,
+}
+
+/// Apply the tag values listed in a table to the files it names.
+///
+/// The table's header must name the file column first, followed by a column per tag. Each row
+/// then gives a file name, resolved relative to `dir`, and the new values of its tags. Rows
+/// naming an absolute path or one containing `..` are rejected rather than reaching outside
+/// `dir`. Empty cells leave a tag untouched.
+///
+/// Values are stored as given, so must be in the tag's raw form (e.g. `1/200` rather than
+/// `1/200 s`), with the values of list tags separated and escaped as described for
+/// [`TableWriter`]. A table written by a [`TableWriter`] with
+/// [`interpreted(false)`](TableWriter::interpreted) is in this form; one written by
+/// [`write_table`], whose values are interpreted, is not.
+///
+/// Rows are applied independently, and the outcome of each is reported, so one bad row doesn't
+/// stop the rest of the batch. With `dry_run` set, files are loaded and edited in memory to
+/// check that every edit would succeed, but nothing is saved. An error is only returned if the
+/// table itself can't be read.
+///
+/// # Examples
+/// ```no_run
+/// let csv = std::fs::File::open("edits.csv")?;
+/// for row in rexiv2::apply_table(csv, "photos", rexiv2::TableFormat::Csv, true)? {
+///     if let Err(err) = row.result {
+///         eprintln!("line {}: {}: {err}", row.line, row.path.display());
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn apply_table<R: Read, P: AsRef<Path>>(
+    mut input: R,
+    dir: P,
+    format: TableFormat,
+    dry_run: bool,
+) -> Result<Vec<RowOutcome>> {
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    let mut records = parse_records(&text, format)?.into_iter();
+    let tags = match records.next() {
+        Some((_, header)) if header.len() > 1 => header[1..].to_vec(),
+        _ => {
+            return Err(Rexiv2Error::InvalidValue(
+                "Table has no tag columns".to_string(),
+            ))
+        }
+    };
+
+    let outcomes = records
+        .map(|(line, fields)| {
+            let path = dir.as_ref().join(&fields[0]);
+            let result = if !is_relative_name(Path::new(&fields[0])) {
+                Err(Rexiv2Error::InvalidValue(format!(
+                    "File name {:?} is not relative to the table's directory",
+                    fields[0]
+                )))
+            } else if fields.len() != tags.len() + 1 {
+                Err(Rexiv2Error::InvalidValue(format!(
+                    "Expected {} fields, found {}",
+                    tags.len() + 1,
+                    fields.len()
+                )))
+            } else {
+                apply_row(&path, &tags, &fields[1..], dry_run)
+            };
+            RowOutcome { line, path, result }
+        })
+        .collect();
+    Ok(outcomes)
+}
+
+/// Whether a file name from a table stays within the directory it's resolved against, i.e. is
+/// neither absolute nor climbs out through `..`.
+fn is_relative_name(name: &Path) -> bool {
+    name.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Set the given tags of a single file, saving it unless this is a dry run.
+fn apply_row(path: &Path, tags: &[String], values: &[String], dry_run: bool) -> Result<usize> {
+    let meta = Metadata::new_from_path(path)?;
+    let mut count = 0;
+    for (tag, value) in tags
+        .iter()
+        .zip(values)
+        .filter(|(_, value)| !value.is_empty())
+    {
+        if is_list_tag(tag)? {
+            let values = split_list(value);
+            let values: Vec<&str> = values.iter().map(String::as_str).collect();
+            meta.set_tag_multiple_strings(tag, &values)?;
+        } else {
+            meta.set_tag_string(tag, value)?;
+        }
+        count += 1;
+    }
+    if !dry_run && count > 0 {
+        meta.save_to_file_atomic(path)?;
+    }
+    Ok(count)
+}
+
+/// Split a table into records of fields, each with the line it starts on. Blank lines are
+/// skipped.
+fn parse_records(text: &str, format: TableFormat) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut records = Vec::new();
+    let mut line = 1;
+    let mut chars = text.chars().peekable();
+    while chars.peek().is_some() {
+        let start = line;
+        let mut fields = vec![];
+        let mut field = String::new();
+        let mut quoted = false;
+        while let Some(c) = chars.next() {
+            match (format, c) {
+                (TableFormat::Csv, '"') if quoted => {
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        quoted = false;
+                    }
+                }
+                (TableFormat::Csv, '"') if field.is_empty() => quoted = true,
+                (TableFormat::Csv, ',') | (TableFormat::Tsv, '\t') if !quoted => {
+                    fields.push(std::mem::take(&mut field));
+                }
+                (_, '\r') if !quoted && chars.peek() == Some(&'\n') => {}
+                (_, '\n') if !quoted => {
+                    line += 1;
+                    break;
+                }
+                (_, c) => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+        }
+        if quoted {
+            return Err(Rexiv2Error::InvalidValue(format!(
+                "Unterminated quoted field starting on line {start}"
+            )));
+        }
+        if !fields.is_empty() || !field.is_empty() {
+            fields.push(field);
+            records.push((start, fields));
+        }
+    }
+    Ok(records)
+}
+
+/// Get the value of a tag for a table cell, either interpreted or in the raw form read back by
+/// [`apply_table`].
+fn cell_value(meta: &Metadata, tag: &str, interpreted: bool) -> Result<String> {
+    let value = if interpreted {
+        meta.get_tag_interpreted_string(tag)
+    } else if is_list_tag(tag)? {
+        meta.get_tag_multiple_strings(tag)
+            .map(|values| join_list(&values))
+    } else {
+        meta.get_tag_string(tag)
+    };
+    match value {
+        Err(Rexiv2Error::NoValue) => Ok(String::new()),
+        value => value,
+    }
+}
+
+/// Whether a tag's raw table cells list its values, rather than holding a single one.
+fn is_list_tag(tag: &str) -> Result<bool> {
+    Ok(is_iptc_tag(tag) || matches!(get_tag_type(tag)?, TagType::XmpBag | TagType::XmpSeq))
+}
+
+/// Join values into a single cell, escaping any `\` or `;` so the list can be split again.
+fn join_list(values: &[String]) -> String {
+    let escaped: Vec<String> = values
+        .iter()
+        .map(|value| value.replace('\\', r"\\").replace(';', r"\;"))
+        .collect();
+    escaped.join("; ")
+}
+
+/// Split a cell written by [`join_list`] back into its values. A `\` takes the character after
+/// it literally, and each unescaped `;` ends a value, along with one space following it.
+fn split_list(cell: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut value = String::new();
+    let mut chars = cell.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.extend(chars.next()),
+            ';' => {
+                values.push(std::mem::take(&mut value));
+                chars.next_if_eq(&' ');
+            }
+            c => value.push(c),
+        }
+    }
+    values.push(value);
+    values
+}
+
+/// Quote a CSV field if it contains any characters with special meaning.
+fn quote_csv(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::Result;
+use std::borrow::Cow;
+use std::ffi;
+
+/// A tag name that has been converted to the form gexiv2 needs ahead of time.
+///
+/// Every method taking a tag name accepts a `TagKey` as well as a plain string. Plain strings are
+/// converted on every call, so reusing a `TagKey` saves an allocation each time; this adds up when
+/// reading the same tags from many files.
+///
+/// # Examples
+/// ```
+/// # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1,
+/// #                0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84,
+/// #                8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73, 69,
+/// #                78, 68, 174, 66, 96, 130];
+/// let artist = rexiv2::TagKey::new("Exif.Image.Artist")?;
+/// let meta = rexiv2::Metadata::new_from_buffer(&minipng)?;
+/// meta.set_tag_string(&artist, "A. Photographer")?;
+/// assert_eq!(meta.get_tag_string(&artist), Ok("A. Photographer".to_string()));
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TagKey {
+    name: String,
+    c_name: ffi::CString,
+}
+
+impl TagKey {
+    /// Prepare the given tag name for repeated use.
+    ///
+    /// Fails if the name contains a nul byte.
+    pub fn new(name: &str) -> Result<TagKey> {
+        Ok(TagKey {
+            name: name.to_string(),
+            c_name: ffi::CString::new(name)?,
+        })
+    }
+
+    /// The tag name.
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+}
+
+impl std::fmt::Display for TagKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Types that can be used to name a tag: string slices, `String`s, and `TagKey`s.
+pub trait AsTagKey {
+    /// The tag name.
+    fn tag_name(&self) -> &str;
+
+    /// The tag name as a C string, as passed to gexiv2.
+    fn tag_c_str(&self) -> Result<Cow<'_, ffi::CStr>> {
+        Ok(Cow::Owned(ffi::CString::new(self.tag_name())?))
+    }
+}
+
+impl<T: AsTagKey + ?Sized> AsTagKey for &T {
+    fn tag_name(&self) -> &str {
+        (**self).tag_name()
+    }
+
+    fn tag_c_str(&self) -> Result<Cow<'_, ffi::CStr>> {
+        (**self).tag_c_str()
+    }
+}
+
+impl AsTagKey for str {
+    fn tag_name(&self) -> &str {
+        self
+    }
+}
+
+impl AsTagKey for String {
+    fn tag_name(&self) -> &str {
+        self
+    }
+}
+
+impl AsTagKey for TagKey {
+    fn tag_name(&self) -> &str {
+        &self.name
+    }
+
+    fn tag_c_str(&self) -> Result<Cow<'_, ffi::CStr>> {
+        Ok(Cow::Borrowed(&self.c_name))
+    }
+}
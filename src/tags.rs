@@ -0,0 +1,393 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Names of commonly used metadata tags.
+//!
+//! These can be passed anywhere a tag name is expected, and help avoid typos in string literals.
+//! See the [Exiv2 tag reference](https://exiv2.org/metadata.html) for the full list of tags.
+//!
+//! # Examples
+//! ```
+//! # let minipng = [137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0,
+//! #               1, 0, 0, 0, 1, 8, 0, 0, 0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65,
+//! #               84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27, 182, 238, 86, 0, 0, 0, 0, 73,
+//! #               69, 78, 68, 174, 66, 96, 130];
+//! # let meta = rexiv2::Metadata::new_from_buffer(&minipng).unwrap();
+//! use rexiv2::tags;
+//! meta.set_tag_string(tags::exif::DATE_TIME_ORIGINAL, "2022:08:07 11:19:44")?;
+//! assert!(meta.has_tag(tags::exif::DATE_TIME_ORIGINAL));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+/// Tags in the Exif domain.
+pub mod exif {
+    // Image (IFD0) tags.
+
+    /// The width of the image, in pixels.
+    pub const IMAGE_WIDTH: &str = "Exif.Image.ImageWidth";
+    /// The height of the image, in pixels.
+    pub const IMAGE_LENGTH: &str = "Exif.Image.ImageLength";
+    /// A title or description of the image.
+    pub const IMAGE_DESCRIPTION: &str = "Exif.Image.ImageDescription";
+    /// The manufacturer of the recording equipment.
+    pub const MAKE: &str = "Exif.Image.Make";
+    /// The model name or number of the recording equipment.
+    pub const MODEL: &str = "Exif.Image.Model";
+    /// The orientation of the image with respect to the stored rows and columns.
+    pub const ORIENTATION: &str = "Exif.Image.Orientation";
+    /// The number of pixels per resolution unit in the width direction.
+    pub const X_RESOLUTION: &str = "Exif.Image.XResolution";
+    /// The number of pixels per resolution unit in the height direction.
+    pub const Y_RESOLUTION: &str = "Exif.Image.YResolution";
+    /// The unit of the X and Y resolutions.
+    pub const RESOLUTION_UNIT: &str = "Exif.Image.ResolutionUnit";
+    /// The software used to create or process the image.
+    pub const SOFTWARE: &str = "Exif.Image.Software";
+    /// The date and time the image file was last changed.
+    pub const DATE_TIME: &str = "Exif.Image.DateTime";
+    /// The name of the person who created the image.
+    pub const ARTIST: &str = "Exif.Image.Artist";
+    /// The copyright notice of the person or organisation claiming rights to the image.
+    pub const COPYRIGHT: &str = "Exif.Image.Copyright";
+
+    // DNG tags, also stored in IFD0.
+
+    /// The version of the DNG specification the file conforms to.
+    pub const DNG_VERSION: &str = "Exif.Image.DNGVersion";
+    /// A unique, non-localized name for the camera model.
+    pub const UNIQUE_CAMERA_MODEL: &str = "Exif.Image.UniqueCameraModel";
+    /// The matrix converting XYZ values to camera color space under the first calibration
+    /// illuminant.
+    pub const COLOR_MATRIX_1: &str = "Exif.Image.ColorMatrix1";
+    /// The matrix converting XYZ values to camera color space under the second calibration
+    /// illuminant.
+    pub const COLOR_MATRIX_2: &str = "Exif.Image.ColorMatrix2";
+    /// The selected white balance at time of capture, as the camera coordinates of a neutral.
+    pub const AS_SHOT_NEUTRAL: &str = "Exif.Image.AsShotNeutral";
+    /// The amount, in EV units, by which to adjust the zero point of the exposure.
+    pub const BASELINE_EXPOSURE: &str = "Exif.Image.BaselineExposure";
+
+    // Photo (Exif IFD) tags.
+
+    /// The exposure time, in seconds.
+    pub const EXPOSURE_TIME: &str = "Exif.Photo.ExposureTime";
+    /// The f-number of the lens.
+    pub const F_NUMBER: &str = "Exif.Photo.FNumber";
+    /// The program used by the camera to set exposure.
+    pub const EXPOSURE_PROGRAM: &str = "Exif.Photo.ExposureProgram";
+    /// The ISO speed ratings of the camera.
+    pub const ISO_SPEED_RATINGS: &str = "Exif.Photo.ISOSpeedRatings";
+    /// Which measure of sensitivity `ISO_SPEED_RATINGS` records.
+    pub const SENSITIVITY_TYPE: &str = "Exif.Photo.SensitivityType";
+    /// The standard output sensitivity (SOS) of the camera.
+    pub const STANDARD_OUTPUT_SENSITIVITY: &str = "Exif.Photo.StandardOutputSensitivity";
+    /// The recommended exposure index (REI) of the camera.
+    pub const RECOMMENDED_EXPOSURE_INDEX: &str = "Exif.Photo.RecommendedExposureIndex";
+    /// The ISO speed of the camera, as defined by ISO 12232.
+    pub const ISO_SPEED: &str = "Exif.Photo.ISOSpeed";
+    /// The version of the Exif standard supported.
+    pub const EXIF_VERSION: &str = "Exif.Photo.ExifVersion";
+    /// The date and time the original image data was generated.
+    pub const DATE_TIME_ORIGINAL: &str = "Exif.Photo.DateTimeOriginal";
+    /// The date and time the image was stored as digital data.
+    pub const DATE_TIME_DIGITIZED: &str = "Exif.Photo.DateTimeDigitized";
+    /// The time zone offset of `DATE_TIME`.
+    pub const OFFSET_TIME: &str = "Exif.Photo.OffsetTime";
+    /// The time zone offset of `DATE_TIME_ORIGINAL`.
+    pub const OFFSET_TIME_ORIGINAL: &str = "Exif.Photo.OffsetTimeOriginal";
+    /// The time zone offset of `DATE_TIME_DIGITIZED`.
+    pub const OFFSET_TIME_DIGITIZED: &str = "Exif.Photo.OffsetTimeDigitized";
+    /// The shutter speed, in APEX units.
+    pub const SHUTTER_SPEED_VALUE: &str = "Exif.Photo.ShutterSpeedValue";
+    /// The lens aperture, in APEX units.
+    pub const APERTURE_VALUE: &str = "Exif.Photo.ApertureValue";
+    /// The brightness, in APEX units.
+    pub const BRIGHTNESS_VALUE: &str = "Exif.Photo.BrightnessValue";
+    /// The exposure bias, in APEX units.
+    pub const EXPOSURE_BIAS_VALUE: &str = "Exif.Photo.ExposureBiasValue";
+    /// The smallest f-number of the lens, in APEX units.
+    pub const MAX_APERTURE_VALUE: &str = "Exif.Photo.MaxApertureValue";
+    /// The distance to the subject, in metres.
+    pub const SUBJECT_DISTANCE: &str = "Exif.Photo.SubjectDistance";
+    /// The metering mode.
+    pub const METERING_MODE: &str = "Exif.Photo.MeteringMode";
+    /// The kind of light source.
+    pub const LIGHT_SOURCE: &str = "Exif.Photo.LightSource";
+    /// The status of the flash when the image was shot.
+    pub const FLASH: &str = "Exif.Photo.Flash";
+    /// The actual focal length of the lens, in millimetres.
+    pub const FOCAL_LENGTH: &str = "Exif.Photo.FocalLength";
+    /// Manufacturer-specific information.
+    pub const MAKER_NOTE: &str = "Exif.Photo.MakerNote";
+    /// Comments from the user.
+    pub const USER_COMMENT: &str = "Exif.Photo.UserComment";
+    /// Fractions of seconds for `DATE_TIME`.
+    pub const SUB_SEC_TIME: &str = "Exif.Photo.SubSecTime";
+    /// Fractions of seconds for `DATE_TIME_ORIGINAL`.
+    pub const SUB_SEC_TIME_ORIGINAL: &str = "Exif.Photo.SubSecTimeOriginal";
+    /// Fractions of seconds for `DATE_TIME_DIGITIZED`.
+    pub const SUB_SEC_TIME_DIGITIZED: &str = "Exif.Photo.SubSecTimeDigitized";
+    /// The colour space information tag.
+    pub const COLOR_SPACE: &str = "Exif.Photo.ColorSpace";
+    /// The valid width of the meaningful image, in pixels.
+    pub const PIXEL_X_DIMENSION: &str = "Exif.Photo.PixelXDimension";
+    /// The valid height of the meaningful image, in pixels.
+    pub const PIXEL_Y_DIMENSION: &str = "Exif.Photo.PixelYDimension";
+    /// The exposure mode set when the image was shot.
+    pub const EXPOSURE_MODE: &str = "Exif.Photo.ExposureMode";
+    /// The white balance mode set when the image was shot.
+    pub const WHITE_BALANCE: &str = "Exif.Photo.WhiteBalance";
+    /// The equivalent focal length assuming a 35mm film camera, in millimetres.
+    pub const FOCAL_LENGTH_IN_35MM_FILM: &str = "Exif.Photo.FocalLengthIn35mmFilm";
+    /// The type of scene that was shot.
+    pub const SCENE_CAPTURE_TYPE: &str = "Exif.Photo.SceneCaptureType";
+    /// The contrast processing applied by the camera.
+    pub const CONTRAST: &str = "Exif.Photo.Contrast";
+    /// The saturation processing applied by the camera.
+    pub const SATURATION: &str = "Exif.Photo.Saturation";
+    /// The sharpness processing applied by the camera.
+    pub const SHARPNESS: &str = "Exif.Photo.Sharpness";
+    /// An identifier assigned uniquely to each image.
+    pub const IMAGE_UNIQUE_ID: &str = "Exif.Photo.ImageUniqueID";
+    /// The name of the camera owner.
+    pub const CAMERA_OWNER_NAME: &str = "Exif.Photo.CameraOwnerName";
+    /// The serial number of the camera body.
+    pub const BODY_SERIAL_NUMBER: &str = "Exif.Photo.BodySerialNumber";
+    /// The specification of the lens that was used.
+    pub const LENS_SPECIFICATION: &str = "Exif.Photo.LensSpecification";
+    /// The manufacturer of the lens.
+    pub const LENS_MAKE: &str = "Exif.Photo.LensMake";
+    /// The model name or number of the lens.
+    pub const LENS_MODEL: &str = "Exif.Photo.LensModel";
+    /// The serial number of the lens.
+    pub const LENS_SERIAL_NUMBER: &str = "Exif.Photo.LensSerialNumber";
+    /// The offset of the interoperability IFD.
+    pub const INTEROPERABILITY_TAG: &str = "Exif.Photo.InteroperabilityTag";
+
+    // GPS tags.
+
+    /// The version of the GPS IFD.
+    pub const GPS_VERSION_ID: &str = "Exif.GPSInfo.GPSVersionID";
+    /// Whether the latitude is north or south.
+    pub const GPS_LATITUDE_REF: &str = "Exif.GPSInfo.GPSLatitudeRef";
+    /// The latitude, as degrees, minutes, and seconds.
+    pub const GPS_LATITUDE: &str = "Exif.GPSInfo.GPSLatitude";
+    /// Whether the longitude is east or west.
+    pub const GPS_LONGITUDE_REF: &str = "Exif.GPSInfo.GPSLongitudeRef";
+    /// The longitude, as degrees, minutes, and seconds.
+    pub const GPS_LONGITUDE: &str = "Exif.GPSInfo.GPSLongitude";
+    /// Whether the altitude is above or below sea level.
+    pub const GPS_ALTITUDE_REF: &str = "Exif.GPSInfo.GPSAltitudeRef";
+    /// The altitude, in metres.
+    pub const GPS_ALTITUDE: &str = "Exif.GPSInfo.GPSAltitude";
+    /// The UTC time of the GPS fix, as hours, minutes, and seconds.
+    pub const GPS_TIME_STAMP: &str = "Exif.GPSInfo.GPSTimeStamp";
+    /// The geodetic survey data used by the GPS receiver.
+    pub const GPS_MAP_DATUM: &str = "Exif.GPSInfo.GPSMapDatum";
+    /// The name of the method used for location finding.
+    pub const GPS_PROCESSING_METHOD: &str = "Exif.GPSInfo.GPSProcessingMethod";
+    /// The UTC date of the GPS fix.
+    pub const GPS_DATE_STAMP: &str = "Exif.GPSInfo.GPSDateStamp";
+
+    // Interoperability IFD tags.
+
+    /// The interoperability rule the file follows, such as `R98` for DCF basic files.
+    pub const INTEROPERABILITY_INDEX: &str = "Exif.Iop.InteroperabilityIndex";
+    /// The version of the interoperability rule, such as `0100`.
+    pub const INTEROPERABILITY_VERSION: &str = "Exif.Iop.InteroperabilityVersion";
+
+    // Thumbnail (IFD1) tags.
+
+    /// The orientation of the thumbnail with respect to its stored rows and columns.
+    pub const THUMBNAIL_ORIENTATION: &str = "Exif.Thumbnail.Orientation";
+}
+
+/// Tags in the IPTC domain.
+pub mod iptc {
+    /// The character set used by the application record.
+    pub const CHARACTER_SET: &str = "Iptc.Envelope.CharacterSet";
+    /// A shorthand reference for the object.
+    pub const OBJECT_NAME: &str = "Iptc.Application2.ObjectName";
+    /// The editorial urgency of the content.
+    pub const URGENCY: &str = "Iptc.Application2.Urgency";
+    /// The subject of the object.
+    pub const CATEGORY: &str = "Iptc.Application2.Category";
+    /// Supplemental categories further refining the subject.
+    pub const SUPPLEMENTAL_CATEGORY: &str = "Iptc.Application2.SuppCategory";
+    /// Identifies objects that recur often and predictably.
+    pub const FIXTURE_ID: &str = "Iptc.Application2.FixtureId";
+    /// Keywords describing the object.
+    pub const KEYWORDS: &str = "Iptc.Application2.Keywords";
+    /// Editorial instructions concerning the use of the object.
+    pub const SPECIAL_INSTRUCTIONS: &str = "Iptc.Application2.SpecialInstructions";
+    /// The date the intellectual content of the object was created.
+    pub const DATE_CREATED: &str = "Iptc.Application2.DateCreated";
+    /// The time the intellectual content of the object was created.
+    pub const TIME_CREATED: &str = "Iptc.Application2.TimeCreated";
+    /// The program used to originate the object.
+    pub const PROGRAM: &str = "Iptc.Application2.Program";
+    /// The name of the creator of the object.
+    pub const BYLINE: &str = "Iptc.Application2.Byline";
+    /// The title of the creator of the object.
+    pub const BYLINE_TITLE: &str = "Iptc.Application2.BylineTitle";
+    /// The city where the object originates.
+    pub const CITY: &str = "Iptc.Application2.City";
+    /// The location within a city where the object originates.
+    pub const SUB_LOCATION: &str = "Iptc.Application2.SubLocation";
+    /// The province or state where the object originates.
+    pub const PROVINCE_STATE: &str = "Iptc.Application2.ProvinceState";
+    /// The code of the country where the object originates.
+    pub const COUNTRY_CODE: &str = "Iptc.Application2.CountryCode";
+    /// The name of the country where the object originates.
+    pub const COUNTRY_NAME: &str = "Iptc.Application2.CountryName";
+    /// A code representing the location of original transmission.
+    pub const TRANSMISSION_REFERENCE: &str = "Iptc.Application2.TransmissionReference";
+    /// A publishable entry providing a synopsis of the contents of the object.
+    pub const HEADLINE: &str = "Iptc.Application2.Headline";
+    /// The provider of the object.
+    pub const CREDIT: &str = "Iptc.Application2.Credit";
+    /// The original owner of the intellectual content of the object.
+    pub const SOURCE: &str = "Iptc.Application2.Source";
+    /// The copyright notice of the object.
+    pub const COPYRIGHT: &str = "Iptc.Application2.Copyright";
+    /// A textual description of the object.
+    pub const CAPTION: &str = "Iptc.Application2.Caption";
+    /// The name of the person who wrote the caption.
+    pub const WRITER: &str = "Iptc.Application2.Writer";
+}
+
+/// Tags in the XMP domain.
+pub mod xmp {
+    /// Contributors to the resource (other than the creators).
+    pub const DC_CONTRIBUTOR: &str = "Xmp.dc.contributor";
+    /// The creators of the resource.
+    pub const DC_CREATOR: &str = "Xmp.dc.creator";
+    /// A textual description of the content of the resource.
+    pub const DC_DESCRIPTION: &str = "Xmp.dc.description";
+    /// The file format of the resource.
+    pub const DC_FORMAT: &str = "Xmp.dc.format";
+    /// Informal rights statements.
+    pub const DC_RIGHTS: &str = "Xmp.dc.rights";
+    /// Descriptive phrases or keywords specifying the topic of the resource.
+    pub const DC_SUBJECT: &str = "Xmp.dc.subject";
+    /// The title of the resource.
+    pub const DC_TITLE: &str = "Xmp.dc.title";
+
+    /// The date and time the resource was created.
+    pub const XMP_CREATE_DATE: &str = "Xmp.xmp.CreateDate";
+    /// The name of the first tool used to create the resource.
+    pub const XMP_CREATOR_TOOL: &str = "Xmp.xmp.CreatorTool";
+    /// A user-assigned label, such as a colour label.
+    pub const XMP_LABEL: &str = "Xmp.xmp.Label";
+    /// The date and time the metadata was last changed.
+    pub const XMP_METADATA_DATE: &str = "Xmp.xmp.MetadataDate";
+    /// The date and time the resource was last modified.
+    pub const XMP_MODIFY_DATE: &str = "Xmp.xmp.ModifyDate";
+    /// A user-assigned rating.
+    pub const XMP_RATING: &str = "Xmp.xmp.Rating";
+    /// Whether the resource was picked (1) or rejected (-1) by the user.
+    pub const XMP_DM_PICK: &str = "Xmp.xmpDM.pick";
+
+    /// Whether the resource is rights-managed.
+    pub const XMP_RIGHTS_MARKED: &str = "Xmp.xmpRights.Marked";
+    /// Instructions on how the resource can be legally used.
+    pub const XMP_RIGHTS_USAGE_TERMS: &str = "Xmp.xmpRights.UsageTerms";
+    /// A web page describing the owner and rights of the resource.
+    pub const XMP_RIGHTS_WEB_STATEMENT: &str = "Xmp.xmpRights.WebStatement";
+
+    /// The URL of the license the resource is made available under, per Creative Commons.
+    pub const CC_LICENSE: &str = "Xmp.cc.license";
+
+    /// A unique identifier for all versions and renditions of a resource.
+    pub const XMP_MM_DOCUMENT_ID: &str = "Xmp.xmpMM.DocumentID";
+    /// A unique identifier for a specific incarnation of a resource.
+    pub const XMP_MM_INSTANCE_ID: &str = "Xmp.xmpMM.InstanceID";
+    /// The identifier of the original document from which the resource is derived.
+    pub const XMP_MM_ORIGINAL_DOCUMENT_ID: &str = "Xmp.xmpMM.OriginalDocumentID";
+
+    /// The name of the raw file the resource was developed from, per Adobe Camera Raw.
+    pub const CRS_RAW_FILE_NAME: &str = "Xmp.crs.RawFileName";
+
+    /// The date and time the original image data was generated.
+    pub const EXIF_DATE_TIME_ORIGINAL: &str = "Xmp.exif.DateTimeOriginal";
+    /// The date and time the image was stored as digital data.
+    pub const EXIF_DATE_TIME_DIGITIZED: &str = "Xmp.exif.DateTimeDigitized";
+    /// The UTC date and time of the GPS fix.
+    pub const EXIF_GPS_TIME_STAMP: &str = "Xmp.exif.GPSTimeStamp";
+    /// The name of the method used for location finding.
+    pub const EXIF_GPS_PROCESSING_METHOD: &str = "Xmp.exif.GPSProcessingMethod";
+    /// The geodetic survey data used by the GPS receiver.
+    pub const EXIF_GPS_MAP_DATUM: &str = "Xmp.exif.GPSMapDatum";
+    /// The date and time the file was last changed.
+    pub const TIFF_DATE_TIME: &str = "Xmp.tiff.DateTime";
+    /// The manufacturer of the recording equipment.
+    pub const TIFF_MAKE: &str = "Xmp.tiff.Make";
+    /// The model name or number of the recording equipment.
+    pub const TIFF_MODEL: &str = "Xmp.tiff.Model";
+    /// The name of the camera owner.
+    pub const EXIF_EX_CAMERA_OWNER_NAME: &str = "Xmp.exifEX.CameraOwnerName";
+    /// The name of the camera owner, as recorded by Adobe applications.
+    pub const AUX_OWNER_NAME: &str = "Xmp.aux.OwnerName";
+
+    /// The date the intellectual content of the resource was created.
+    pub const PHOTOSHOP_DATE_CREATED: &str = "Xmp.photoshop.DateCreated";
+    /// A synopsis of the contents of the resource.
+    pub const PHOTOSHOP_HEADLINE: &str = "Xmp.photoshop.Headline";
+    /// The provider of the resource.
+    pub const PHOTOSHOP_CREDIT: &str = "Xmp.photoshop.Credit";
+    /// The original owner of the intellectual content of the resource.
+    pub const PHOTOSHOP_SOURCE: &str = "Xmp.photoshop.Source";
+    /// The city where the resource originates.
+    pub const PHOTOSHOP_CITY: &str = "Xmp.photoshop.City";
+    /// The province or state where the resource originates.
+    pub const PHOTOSHOP_STATE: &str = "Xmp.photoshop.State";
+    /// The country where the resource originates.
+    pub const PHOTOSHOP_COUNTRY: &str = "Xmp.photoshop.Country";
+    /// An identifier for the job or assignment the resource was created for.
+    pub const PHOTOSHOP_TRANSMISSION_REFERENCE: &str = "Xmp.photoshop.TransmissionReference";
+    /// Instructions concerning the use of the resource.
+    pub const PHOTOSHOP_INSTRUCTIONS: &str = "Xmp.photoshop.Instructions";
+
+    /// A short description of the resource for people who can't see it.
+    pub const IPTC_ALT_TEXT: &str = "Xmp.iptc.AltTextAccessibility";
+    /// A longer description of the resource for people who can't see it.
+    pub const IPTC_EXT_DESCR: &str = "Xmp.iptc.ExtDescrAccessibility";
+    /// The contact details of the creator of the resource, as an IPTC Core structure.
+    pub const IPTC_CREATOR_CONTACT_INFO: &str = "Xmp.iptc.CreatorContactInfo";
+    /// How the resource was created, as a URI from the IPTC digital source type vocabulary.
+    pub const IPTC_EXT_DIGITAL_SOURCE_TYPE: &str = "Xmp.iptcExt.DigitalSourceType";
+    /// Regions of the resource, such as cropping suggestions, as IPTC Extension structures.
+    pub const IPTC_EXT_IMAGE_REGION: &str = "Xmp.iptcExt.ImageRegion";
+    /// The locations where the content was created, as IPTC Extension structures.
+    pub const IPTC_EXT_LOCATION_CREATED: &str = "Xmp.iptcExt.LocationCreated";
+    /// The locations shown in the content, as IPTC Extension structures.
+    pub const IPTC_EXT_LOCATION_SHOWN: &str = "Xmp.iptcExt.LocationShown";
+    /// The names of the people shown in the resource.
+    pub const IPTC_EXT_PERSON_IN_IMAGE: &str = "Xmp.iptcExt.PersonInImage";
+    /// Details of the people shown in the resource, as IPTC Extension structures.
+    pub const IPTC_EXT_PERSON_IN_IMAGE_DETAILS: &str = "Xmp.iptcExt.PersonInImageWDetails";
+
+    /// The collections, such as albums, that the resource belongs to.
+    pub const MWG_COLL_COLLECTIONS: &str = "Xmp.mwg-coll.Collections";
+
+    /// The suppliers of the resource, as PLUS structures.
+    pub const PLUS_IMAGE_SUPPLIER: &str = "Xmp.plus.ImageSupplier";
+
+    /// Keywords organised hierarchically, as used by Adobe Lightroom.
+    pub const LR_HIERARCHICAL_SUBJECT: &str = "Xmp.lr.hierarchicalSubject";
+}
@@ -0,0 +1,64 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::hash_map::RandomState;
+use std::ffi::OsStr;
+use std::fs::{File, OpenOptions};
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Distinguishes the temporary files created by the same process, on top of the random part.
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Create a new, empty file in `dir`, named `prefix` followed by a random part and `suffix`.
+///
+/// The file is created exclusively, so nothing already at that path (including a symlink) is
+/// followed or replaced; on Unix it is only accessible by its owner. Returns the open file along
+/// with its path, which the caller is responsible for removing.
+pub(crate) fn create_temp_file(
+    dir: &Path,
+    prefix: &OsStr,
+    suffix: &str,
+) -> io::Result<(File, PathBuf)> {
+    for _ in 0..100 {
+        // Each `RandomState` is seeded differently, which is random enough to make the name
+        // unpredictable without pulling in a dependency.
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(std::process::id());
+        hasher.write_u64(TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed));
+        let mut name = prefix.to_os_string();
+        name.push(format!("{:016x}{suffix}", hasher.finish()));
+        let path = dir.join(name);
+
+        let mut options = OpenOptions::new();
+        options.read(true).write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&path) {
+            Ok(file) => return Ok((file, path)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "Could not find an unused temporary file name",
+    ))
+}
@@ -0,0 +1,151 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers for testing code that uses rexiv2, available with the `test-utils` feature.
+//!
+//! These generate tiny but valid images, optionally carrying chosen tags, so that tests don't
+//! need binary fixture files.
+//!
+//! # Examples
+//! ```
+//! use rexiv2::test_utils::{sample_image_with_tags, SampleFormat};
+//!
+//! let jpeg = sample_image_with_tags(SampleFormat::Jpeg, &[("Exif.Image.Artist", "Jane Doe")])?;
+//! let meta = rexiv2::Metadata::new_from_buffer(&jpeg)?;
+//! assert_eq!(meta.get_tag_string("Exif.Image.Artist")?, "Jane Doe");
+//! # Ok::<(), rexiv2::Rexiv2Error>(())
+//! ```
+
+use super::{temp_file, MediaType, Metadata, Result};
+use std::io::Write;
+
+/// A baseline JPEG image holding a single grey pixel.
+const MINIMAL_JPEG: [u8; 141] = [
+    255, 216, 255, 219, 0, 67, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 255, 192, 0, 11, 8, 0, 1, 0, 1, 1, 1, 17, 0, 255, 196, 0, 20, 0,
+    1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 196, 0, 20, 16, 1, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 218, 0, 8, 1, 1, 0, 0, 63, 0, 63, 255, 217,
+];
+
+/// A PNG image holding a single black pixel.
+const MINIMAL_PNG: [u8; 67] = [
+    137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1, 0, 0, 0, 1, 8, 0, 0,
+    0, 0, 58, 126, 155, 85, 0, 0, 0, 10, 73, 68, 65, 84, 8, 215, 99, 248, 15, 0, 1, 1, 1, 0, 27,
+    182, 238, 86, 0, 0, 0, 0, 73, 69, 78, 68, 174, 66, 96, 130,
+];
+
+/// An uncompressed little-endian TIFF image holding a single grey pixel.
+const MINIMAL_TIFF: [u8; 111] = [
+    73, 73, 42, 0, 8, 0, 0, 0, 8, 0, 0, 1, 3, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 3, 0, 1, 0, 0, 0, 1,
+    0, 0, 0, 2, 1, 3, 0, 1, 0, 0, 0, 8, 0, 0, 0, 3, 1, 3, 0, 1, 0, 0, 0, 1, 0, 0, 0, 6, 1, 3, 0, 1,
+    0, 0, 0, 1, 0, 0, 0, 17, 1, 4, 0, 1, 0, 0, 0, 110, 0, 0, 0, 22, 1, 3, 0, 1, 0, 0, 0, 1, 0, 0,
+    0, 23, 1, 4, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 128,
+];
+
+/// The formats of image that can be generated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SampleFormat {
+    /// A baseline JPEG.
+    Jpeg,
+    /// A greyscale PNG.
+    Png,
+    /// An uncompressed TIFF. Its structural tags, such as `Exif.Image.ImageWidth`, are visible
+    /// as Exif tags.
+    Tiff,
+}
+
+impl SampleFormat {
+    /// The media type of images in this format.
+    pub fn media_type(self) -> MediaType {
+        match self {
+            SampleFormat::Jpeg => MediaType::Jpeg,
+            SampleFormat::Png => MediaType::Png,
+            SampleFormat::Tiff => MediaType::Tiff,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            SampleFormat::Jpeg => ".jpg",
+            SampleFormat::Png => ".png",
+            SampleFormat::Tiff => ".tif",
+        }
+    }
+}
+
+/// Get a 1×1 pixel image in the given format, with no metadata.
+///
+/// # Examples
+/// ```
+/// use rexiv2::test_utils::{sample_image, SampleFormat};
+///
+/// let meta = rexiv2::Metadata::new_from_buffer(&sample_image(SampleFormat::Png))?;
+/// assert_eq!(meta.get_media_type()?, rexiv2::MediaType::Png);
+/// assert_eq!(meta.get_pixel_width(), 1);
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+pub fn sample_image(format: SampleFormat) -> Vec<u8> {
+    match format {
+        SampleFormat::Jpeg => MINIMAL_JPEG.to_vec(),
+        SampleFormat::Png => MINIMAL_PNG.to_vec(),
+        SampleFormat::Tiff => MINIMAL_TIFF.to_vec(),
+    }
+}
+
+/// Get a 1×1 pixel image in the given format, carrying the given tags.
+///
+/// Each tag is set with `Metadata::set_tag_string`. As Exiv2 can only save metadata to files,
+/// the image is briefly written to the system's temporary directory.
+pub fn sample_image_with_tags(format: SampleFormat, tags: &[(&str, &str)]) -> Result<Vec<u8>> {
+    let (mut file, path) = temp_file::create_temp_file(
+        &std::env::temp_dir(),
+        std::ffi::OsStr::new("rexiv2-sample-"),
+        format.extension(),
+    )?;
+    let written = file.write_all(&sample_image(format));
+    // Close the file before Exiv2 opens it by name.
+    drop(file);
+    let result = written
+        .map_err(Into::into)
+        .and_then(|_| sample_metadata(format, tags))
+        .and_then(|meta| meta.save_to_file(&path))
+        .and_then(|_| Ok(std::fs::read(&path)?));
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+/// Load the metadata of a 1×1 pixel image in the given format, with the given tags set.
+///
+/// Unlike `sample_image_with_tags`, nothing is written to disk.
+///
+/// # Examples
+/// ```
+/// use rexiv2::test_utils::{sample_metadata, SampleFormat};
+///
+/// let meta = sample_metadata(SampleFormat::Tiff, &[("Xmp.dc.subject", "fox")])?;
+/// assert_eq!(meta.get_tag_multiple_strings("Xmp.dc.subject")?, vec!["fox"]);
+/// # Ok::<(), rexiv2::Rexiv2Error>(())
+/// ```
+pub fn sample_metadata(format: SampleFormat, tags: &[(&str, &str)]) -> Result<Metadata> {
+    let meta = Metadata::new_from_buffer(&sample_image(format))?;
+    for (tag, value) in tags {
+        meta.set_tag_string(*tag, value)?;
+    }
+    Ok(meta)
+}
@@ -0,0 +1,1156 @@
+// Copyright © 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+//
+// SPDX-FileCopyrightText: 2015–2022 Felix A. Crux <felixc@felixcrux.com> and CONTRIBUTORS
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use super::{
+    get_tag_type, parse_rationals_as_f64, registered_xmp_namespace, tags, Metadata, Result,
+    Rexiv2Error, TagType,
+};
+
+/// XMP namespaces that documents can refer to: the prefix Exiv2 uses in tag names, the prefix
+/// used within XMP documents, and the namespace URI.
+const NAMESPACES: [(&str, &str, &str); 40] = [
+    ("acdsee", "acdsee", "http://ns.acdsee.com/iptc/1.0/"),
+    ("aux", "aux", "http://ns.adobe.com/exif/1.0/aux/"),
+    ("cc", "cc", "http://creativecommons.org/ns#"),
+    ("crs", "crs", "http://ns.adobe.com/camera-raw-settings/1.0/"),
+    (
+        "crss",
+        "crss",
+        "http://ns.adobe.com/camera-raw-saved-settings/1.0/",
+    ),
+    ("dc", "dc", "http://purl.org/dc/elements/1.1/"),
+    ("digiKam", "digiKam", "http://www.digikam.org/ns/1.0/"),
+    ("dwc", "dwc", "http://rs.tdwg.org/dwc/index.htm"),
+    ("exif", "exif", "http://ns.adobe.com/exif/1.0/"),
+    ("exifEX", "exifEX", "http://cipa.jp/exif/1.0/"),
+    (
+        "GPano",
+        "GPano",
+        "http://ns.google.com/photos/1.0/panorama/",
+    ),
+    (
+        "iptc",
+        "Iptc4xmpCore",
+        "http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/",
+    ),
+    (
+        "iptcExt",
+        "Iptc4xmpExt",
+        "http://iptc.org/std/Iptc4xmpExt/2008-02-29/",
+    ),
+    ("lr", "lr", "http://ns.adobe.com/lightroom/1.0/"),
+    (
+        "MicrosoftPhoto",
+        "MicrosoftPhoto",
+        "http://ns.microsoft.com/photo/1.0/",
+    ),
+    ("MP", "MP", "http://ns.microsoft.com/photo/1.2/"),
+    (
+        "MPReg",
+        "MPReg",
+        "http://ns.microsoft.com/photo/1.2/t/Region#",
+    ),
+    (
+        "MPRI",
+        "MPRI",
+        "http://ns.microsoft.com/photo/1.2/t/RegionInfo#",
+    ),
+    (
+        "mwg-coll",
+        "mwg-coll",
+        "http://www.metadataworkinggroup.com/schemas/collections/",
+    ),
+    (
+        "mwg-kw",
+        "mwg-kw",
+        "http://www.metadataworkinggroup.com/schemas/keywords/",
+    ),
+    (
+        "mwg-rs",
+        "mwg-rs",
+        "http://www.metadataworkinggroup.com/schemas/regions/",
+    ),
+    ("pdf", "pdf", "http://ns.adobe.com/pdf/1.3/"),
+    (
+        "photoshop",
+        "photoshop",
+        "http://ns.adobe.com/photoshop/1.0/",
+    ),
+    ("plus", "plus", "http://ns.useplus.org/ldf/xmp/1.0/"),
+    ("stArea", "stArea", "http://ns.adobe.com/xmp/sType/Area#"),
+    (
+        "stDim",
+        "stDim",
+        "http://ns.adobe.com/xap/1.0/sType/Dimensions#",
+    ),
+    (
+        "stEvt",
+        "stEvt",
+        "http://ns.adobe.com/xap/1.0/sType/ResourceEvent#",
+    ),
+    ("stJob", "stJob", "http://ns.adobe.com/xap/1.0/sType/Job#"),
+    (
+        "stRef",
+        "stRef",
+        "http://ns.adobe.com/xap/1.0/sType/ResourceRef#",
+    ),
+    (
+        "stVer",
+        "stVer",
+        "http://ns.adobe.com/xap/1.0/sType/Version#",
+    ),
+    ("tiff", "tiff", "http://ns.adobe.com/tiff/1.0/"),
+    ("xmp", "xmp", "http://ns.adobe.com/xap/1.0/"),
+    ("xmpBJ", "xmpBJ", "http://ns.adobe.com/xap/1.0/bj/"),
+    (
+        "xmpDM",
+        "xmpDM",
+        "http://ns.adobe.com/xmp/1.0/DynamicMedia/",
+    ),
+    ("xmpG", "xmpG", "http://ns.adobe.com/xap/1.0/g/"),
+    (
+        "xmpidq",
+        "xmpidq",
+        "http://ns.adobe.com/xmp/Identifier/qual/1.0/",
+    ),
+    ("xmpMM", "xmpMM", "http://ns.adobe.com/xap/1.0/mm/"),
+    ("xmpNote", "xmpNote", "http://ns.adobe.com/xmp/note/"),
+    (
+        "xmpRights",
+        "xmpRights",
+        "http://ns.adobe.com/xap/1.0/rights/",
+    ),
+    ("xmpTPg", "xmpTPg", "http://ns.adobe.com/xap/1.0/t/pg/"),
+];
+
+/// How the value of an Exif or IPTC tag is turned into an XMP property.
+#[derive(Clone, Copy)]
+enum Conversion {
+    /// A single value, kept as it is.
+    Text,
+    /// Exif: a single value, as interpreted for display. IPTC: the first value.
+    Interpreted,
+    /// Several space-separated Exif values, or several IPTC values, as an ordered array.
+    Seq,
+    /// Several IPTC values, as an unordered array.
+    Bag,
+    /// A single Exif value, as the only item of an ordered array.
+    SeqItem,
+    /// A single value as the default language of a language alternative.
+    LangAlt,
+    /// An Exif date and time, along with the tags holding its fractional seconds and offset.
+    ExifDate(&'static str, &'static str),
+    /// The IPTC date and time created.
+    IptcDate,
+    /// An Exif version number stored as four ASCII digits, such as `0232`.
+    Version,
+    /// The Exif flash value, split into the fields of the XMP `Flash` structure.
+    Flash,
+    /// A GPS latitude or longitude, along with the tag holding its reference direction.
+    GpsCoordinate(&'static str),
+    /// The GPS version, written with dots, such as `2.3.0.0`.
+    GpsVersion,
+    /// The GPS date and time stamps.
+    GpsTimeStamp,
+}
+
+/// Exif tags and the XMP properties they are reconciled into, following the mapping defined
+/// by the Exif and XMP specifications and used by Exiv2.
+const EXIF_TO_XMP: [(&str, &str, Conversion); 75] = [
+    ("Exif.Image.ImageWidth", "tiff:ImageWidth", Conversion::Text),
+    (
+        "Exif.Image.ImageLength",
+        "tiff:ImageLength",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Image.BitsPerSample",
+        "tiff:BitsPerSample",
+        Conversion::Seq,
+    ),
+    (
+        "Exif.Image.Compression",
+        "tiff:Compression",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Image.PhotometricInterpretation",
+        "tiff:PhotometricInterpretation",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Image.ImageDescription",
+        "dc:description",
+        Conversion::LangAlt,
+    ),
+    ("Exif.Image.Make", "tiff:Make", Conversion::Text),
+    ("Exif.Image.Model", "tiff:Model", Conversion::Text),
+    (
+        "Exif.Image.Orientation",
+        "tiff:Orientation",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Image.SamplesPerPixel",
+        "tiff:SamplesPerPixel",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Image.XResolution",
+        "tiff:XResolution",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Image.YResolution",
+        "tiff:YResolution",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Image.PlanarConfiguration",
+        "tiff:PlanarConfiguration",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Image.ResolutionUnit",
+        "tiff:ResolutionUnit",
+        Conversion::Text,
+    ),
+    ("Exif.Image.Software", "xmp:CreatorTool", Conversion::Text),
+    (
+        "Exif.Image.DateTime",
+        "xmp:ModifyDate",
+        Conversion::ExifDate("Exif.Photo.SubSecTime", "Exif.Photo.OffsetTime"),
+    ),
+    ("Exif.Image.Artist", "dc:creator", Conversion::SeqItem),
+    ("Exif.Image.WhitePoint", "tiff:WhitePoint", Conversion::Seq),
+    (
+        "Exif.Image.PrimaryChromaticities",
+        "tiff:PrimaryChromaticities",
+        Conversion::Seq,
+    ),
+    (
+        "Exif.Image.YCbCrCoefficients",
+        "tiff:YCbCrCoefficients",
+        Conversion::Seq,
+    ),
+    (
+        "Exif.Image.YCbCrSubSampling",
+        "tiff:YCbCrSubSampling",
+        Conversion::Seq,
+    ),
+    (
+        "Exif.Image.YCbCrPositioning",
+        "tiff:YCbCrPositioning",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Image.ReferenceBlackWhite",
+        "tiff:ReferenceBlackWhite",
+        Conversion::Seq,
+    ),
+    ("Exif.Image.Copyright", "dc:rights", Conversion::LangAlt),
+    (
+        "Exif.Photo.ExposureTime",
+        "exif:ExposureTime",
+        Conversion::Text,
+    ),
+    ("Exif.Photo.FNumber", "exif:FNumber", Conversion::Text),
+    (
+        "Exif.Photo.ExposureProgram",
+        "exif:ExposureProgram",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.SpectralSensitivity",
+        "exif:SpectralSensitivity",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.ISOSpeedRatings",
+        "exif:ISOSpeedRatings",
+        Conversion::Seq,
+    ),
+    (
+        "Exif.Photo.ExifVersion",
+        "exif:ExifVersion",
+        Conversion::Version,
+    ),
+    (
+        "Exif.Photo.DateTimeOriginal",
+        "exif:DateTimeOriginal",
+        Conversion::ExifDate(
+            "Exif.Photo.SubSecTimeOriginal",
+            "Exif.Photo.OffsetTimeOriginal",
+        ),
+    ),
+    (
+        "Exif.Photo.DateTimeDigitized",
+        "xmp:CreateDate",
+        Conversion::ExifDate(
+            "Exif.Photo.SubSecTimeDigitized",
+            "Exif.Photo.OffsetTimeDigitized",
+        ),
+    ),
+    (
+        "Exif.Photo.ComponentsConfiguration",
+        "exif:ComponentsConfiguration",
+        Conversion::Seq,
+    ),
+    (
+        "Exif.Photo.CompressedBitsPerPixel",
+        "exif:CompressedBitsPerPixel",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.ShutterSpeedValue",
+        "exif:ShutterSpeedValue",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.ApertureValue",
+        "exif:ApertureValue",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.BrightnessValue",
+        "exif:BrightnessValue",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.ExposureBiasValue",
+        "exif:ExposureBiasValue",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.MaxApertureValue",
+        "exif:MaxApertureValue",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.SubjectDistance",
+        "exif:SubjectDistance",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.MeteringMode",
+        "exif:MeteringMode",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.LightSource",
+        "exif:LightSource",
+        Conversion::Text,
+    ),
+    ("Exif.Photo.Flash", "exif:Flash", Conversion::Flash),
+    (
+        "Exif.Photo.FocalLength",
+        "exif:FocalLength",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.SubjectArea",
+        "exif:SubjectArea",
+        Conversion::Seq,
+    ),
+    (
+        "Exif.Photo.UserComment",
+        "exif:UserComment",
+        Conversion::Interpreted,
+    ),
+    (
+        "Exif.Photo.FlashpixVersion",
+        "exif:FlashpixVersion",
+        Conversion::Version,
+    ),
+    ("Exif.Photo.ColorSpace", "exif:ColorSpace", Conversion::Text),
+    (
+        "Exif.Photo.PixelXDimension",
+        "exif:PixelXDimension",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.PixelYDimension",
+        "exif:PixelYDimension",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.FocalPlaneXResolution",
+        "exif:FocalPlaneXResolution",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.FocalPlaneYResolution",
+        "exif:FocalPlaneYResolution",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.FocalPlaneResolutionUnit",
+        "exif:FocalPlaneResolutionUnit",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.ExposureIndex",
+        "exif:ExposureIndex",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.SensingMethod",
+        "exif:SensingMethod",
+        Conversion::Text,
+    ),
+    ("Exif.Photo.FileSource", "exif:FileSource", Conversion::Text),
+    ("Exif.Photo.SceneType", "exif:SceneType", Conversion::Text),
+    (
+        "Exif.Photo.CustomRendered",
+        "exif:CustomRendered",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.ExposureMode",
+        "exif:ExposureMode",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.WhiteBalance",
+        "exif:WhiteBalance",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.DigitalZoomRatio",
+        "exif:DigitalZoomRatio",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.FocalLengthIn35mmFilm",
+        "exif:FocalLengthIn35mmFilm",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.SceneCaptureType",
+        "exif:SceneCaptureType",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.GainControl",
+        "exif:GainControl",
+        Conversion::Text,
+    ),
+    ("Exif.Photo.Contrast", "exif:Contrast", Conversion::Text),
+    ("Exif.Photo.Saturation", "exif:Saturation", Conversion::Text),
+    ("Exif.Photo.Sharpness", "exif:Sharpness", Conversion::Text),
+    (
+        "Exif.Photo.SubjectDistanceRange",
+        "exif:SubjectDistanceRange",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.ImageUniqueID",
+        "exif:ImageUniqueID",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.CameraOwnerName",
+        "exifEX:CameraOwnerName",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.BodySerialNumber",
+        "exifEX:BodySerialNumber",
+        Conversion::Text,
+    ),
+    (
+        "Exif.Photo.LensSpecification",
+        "exifEX:LensSpecification",
+        Conversion::Seq,
+    ),
+    ("Exif.Photo.LensMake", "exifEX:LensMake", Conversion::Text),
+    ("Exif.Photo.LensModel", "exifEX:LensModel", Conversion::Text),
+    (
+        "Exif.Photo.LensSerialNumber",
+        "exifEX:LensSerialNumber",
+        Conversion::Text,
+    ),
+];
+
+/// Exif GPS tags and the XMP properties they are reconciled into.
+const GPS_TO_XMP: [(&str, &str, Conversion); 19] = [
+    (
+        "Exif.GPSInfo.GPSVersionID",
+        "exif:GPSVersionID",
+        Conversion::GpsVersion,
+    ),
+    (
+        "Exif.GPSInfo.GPSLatitude",
+        "exif:GPSLatitude",
+        Conversion::GpsCoordinate("Exif.GPSInfo.GPSLatitudeRef"),
+    ),
+    (
+        "Exif.GPSInfo.GPSLongitude",
+        "exif:GPSLongitude",
+        Conversion::GpsCoordinate("Exif.GPSInfo.GPSLongitudeRef"),
+    ),
+    (
+        "Exif.GPSInfo.GPSAltitudeRef",
+        "exif:GPSAltitudeRef",
+        Conversion::Text,
+    ),
+    (
+        "Exif.GPSInfo.GPSAltitude",
+        "exif:GPSAltitude",
+        Conversion::Text,
+    ),
+    (
+        "Exif.GPSInfo.GPSTimeStamp",
+        "exif:GPSTimeStamp",
+        Conversion::GpsTimeStamp,
+    ),
+    (
+        "Exif.GPSInfo.GPSSatellites",
+        "exif:GPSSatellites",
+        Conversion::Text,
+    ),
+    ("Exif.GPSInfo.GPSStatus", "exif:GPSStatus", Conversion::Text),
+    (
+        "Exif.GPSInfo.GPSMeasureMode",
+        "exif:GPSMeasureMode",
+        Conversion::Text,
+    ),
+    ("Exif.GPSInfo.GPSDOP", "exif:GPSDOP", Conversion::Text),
+    (
+        "Exif.GPSInfo.GPSSpeedRef",
+        "exif:GPSSpeedRef",
+        Conversion::Text,
+    ),
+    ("Exif.GPSInfo.GPSSpeed", "exif:GPSSpeed", Conversion::Text),
+    (
+        "Exif.GPSInfo.GPSTrackRef",
+        "exif:GPSTrackRef",
+        Conversion::Text,
+    ),
+    ("Exif.GPSInfo.GPSTrack", "exif:GPSTrack", Conversion::Text),
+    (
+        "Exif.GPSInfo.GPSImgDirectionRef",
+        "exif:GPSImgDirectionRef",
+        Conversion::Text,
+    ),
+    (
+        "Exif.GPSInfo.GPSImgDirection",
+        "exif:GPSImgDirection",
+        Conversion::Text,
+    ),
+    (
+        "Exif.GPSInfo.GPSMapDatum",
+        "exif:GPSMapDatum",
+        Conversion::Text,
+    ),
+    (
+        "Exif.GPSInfo.GPSDestLatitude",
+        "exif:GPSDestLatitude",
+        Conversion::GpsCoordinate("Exif.GPSInfo.GPSDestLatitudeRef"),
+    ),
+    (
+        "Exif.GPSInfo.GPSDestLongitude",
+        "exif:GPSDestLongitude",
+        Conversion::GpsCoordinate("Exif.GPSInfo.GPSDestLongitudeRef"),
+    ),
+];
+
+/// IPTC datasets and the XMP properties they are reconciled into, following the IPTC Photo
+/// Metadata Standard.
+const IPTC_TO_XMP: [(&str, &str, Conversion); 22] = [
+    (
+        "Iptc.Application2.ObjectName",
+        "dc:title",
+        Conversion::LangAlt,
+    ),
+    (
+        "Iptc.Application2.Urgency",
+        "photoshop:Urgency",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.Category",
+        "photoshop:Category",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.SuppCategory",
+        "photoshop:SupplementalCategories",
+        Conversion::Bag,
+    ),
+    ("Iptc.Application2.Keywords", "dc:subject", Conversion::Bag),
+    (
+        "Iptc.Application2.SubLocation",
+        "Iptc4xmpCore:Location",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.SpecialInstructions",
+        "photoshop:Instructions",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.DateCreated",
+        "photoshop:DateCreated",
+        Conversion::IptcDate,
+    ),
+    ("Iptc.Application2.Byline", "dc:creator", Conversion::Seq),
+    (
+        "Iptc.Application2.BylineTitle",
+        "photoshop:AuthorsPosition",
+        Conversion::Text,
+    ),
+    ("Iptc.Application2.City", "photoshop:City", Conversion::Text),
+    (
+        "Iptc.Application2.ProvinceState",
+        "photoshop:State",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.CountryCode",
+        "Iptc4xmpCore:CountryCode",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.CountryName",
+        "photoshop:Country",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.TransmissionReference",
+        "photoshop:TransmissionReference",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.Headline",
+        "photoshop:Headline",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.Credit",
+        "photoshop:Credit",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.Source",
+        "photoshop:Source",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.Copyright",
+        "dc:rights",
+        Conversion::LangAlt,
+    ),
+    (
+        "Iptc.Application2.Caption",
+        "dc:description",
+        Conversion::LangAlt,
+    ),
+    (
+        "Iptc.Application2.Writer",
+        "photoshop:CaptionWriter",
+        Conversion::Text,
+    ),
+    (
+        "Iptc.Application2.Subject",
+        "Iptc4xmpCore:SubjectCode",
+        Conversion::Bag,
+    ),
+];
+
+/// A value in an XMP document.
+#[derive(Debug)]
+enum Node {
+    /// A value that isn't known yet, created to hold a nested value.
+    Unset,
+    Simple(String),
+    /// An array of the given kind (`Bag`, `Seq`, or `Alt`).
+    Array(&'static str, Vec<Node>),
+    LangAlt(Vec<(String, String)>),
+    /// A structure, with fields identified by their qualified names.
+    Struct(Vec<(String, Node)>),
+    /// A value along with its qualifiers, identified by their qualified names.
+    Qualified(Box<Node>, Vec<(String, Node)>),
+}
+
+/// A step along the path to a value, as named by an XMP tag.
+enum Step {
+    /// A top-level property or structure field, by its qualified name.
+    Field(String),
+    /// An array item, by its (1-based) index.
+    Item(usize),
+    /// A qualifier of the value so far, by its qualified name.
+    Qualifier(String),
+}
+
+impl Node {
+    /// Get the field of a structure with the given name, creating it if necessary.
+    fn field(&mut self, name: &str) -> Result<&mut Node> {
+        if let Node::Unset = self {
+            *self = Node::Struct(vec![]);
+        }
+        match self {
+            Node::Struct(fields) => Ok(entry(fields, name)),
+            Node::Qualified(value, _) => value.field(name),
+            _ => Err(Rexiv2Error::InvalidValue(format!(
+                "Not an XMP struct: {name}"
+            ))),
+        }
+    }
+
+    /// Get the item of an array at the given (1-based) index, creating it if necessary.
+    fn item(&mut self, index: usize) -> Result<&mut Node> {
+        if let Node::Unset = self {
+            *self = Node::Array("Bag", vec![]);
+        }
+        match self {
+            Node::Array(_, items) if index > 0 => {
+                while items.len() < index {
+                    items.push(Node::Unset);
+                }
+                Ok(&mut items[index - 1])
+            }
+            Node::Qualified(value, _) => value.item(index),
+            _ => Err(Rexiv2Error::InvalidValue(format!(
+                "Not an XMP array item: {index}"
+            ))),
+        }
+    }
+
+    /// Get the qualifier of this value with the given name, creating it if necessary.
+    fn qualifier(&mut self, name: &str) -> &mut Node {
+        if !matches!(self, Node::Qualified(..)) {
+            let value = std::mem::replace(self, Node::Unset);
+            *self = Node::Qualified(Box::new(value), vec![]);
+        }
+        match self {
+            Node::Qualified(_, qualifiers) => entry(qualifiers, name),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Fill in this value, unless it's already known.
+    fn set(&mut self, value: Node) {
+        match self {
+            Node::Unset => *self = value,
+            Node::Qualified(inner, _) => inner.set(value),
+            _ => {}
+        }
+    }
+}
+
+/// Get the entry with the given name from a list of named values, adding it if necessary.
+fn entry<'a>(entries: &'a mut Vec<(String, Node)>, name: &str) -> &'a mut Node {
+    let index = match entries.iter().position(|(entry, _)| entry == name) {
+        Some(index) => index,
+        None => {
+            entries.push((name.to_string(), Node::Unset));
+            entries.len() - 1
+        }
+    };
+    &mut entries[index].1
+}
+
+/// Build an XMP document holding the metadata's XMP properties, and optionally its Exif and
+/// IPTC tags converted to their XMP equivalents.
+pub(crate) fn build(meta: &Metadata, include_exif_iptc: bool) -> Result<String> {
+    let mut root = Node::Struct(vec![]);
+    for tag in meta.get_xmp_tags()? {
+        let value = xmp_value(meta, &tag)?;
+        let mut node = &mut root;
+        for step in xmp_path(&tag)? {
+            node = match step {
+                Step::Field(name) => node.field(&name)?,
+                Step::Item(index) => node.item(index)?,
+                Step::Qualifier(name) => node.qualifier(&name),
+            };
+        }
+        node.set(value);
+    }
+
+    if include_exif_iptc {
+        let conversions = EXIF_TO_XMP.iter().chain(&GPS_TO_XMP).chain(&IPTC_TO_XMP);
+        for &(tag, property, conversion) in conversions {
+            if !meta.has_tag(tag) {
+                continue;
+            }
+            // Properties already present in the XMP take precedence.
+            let node = root.field(property)?;
+            if let Node::Unset = node {
+                if let Some(value) = convert(meta, tag, conversion)? {
+                    *node = value;
+                }
+            }
+        }
+    }
+
+    let mut properties = match root {
+        Node::Struct(properties) => properties,
+        _ => {
+            return Err(Rexiv2Error::Internal(Some(
+                "Invalid XMP document".to_string(),
+            )))
+        }
+    };
+    properties.retain(|(_, value)| !matches!(value, Node::Unset));
+    properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    // Properties using a namespace whose URI isn't known can't be written, so are left out.
+    let mut namespaces = vec![];
+    properties.retain(|(name, value)| {
+        let mut prefixes = vec![];
+        collect_prefixes(name, value, &mut prefixes);
+        let uris: Option<Vec<(String, String)>> = prefixes
+            .into_iter()
+            .map(|prefix| Some((prefix.to_string(), namespace_uri(prefix)?)))
+            .collect();
+        match uris {
+            Some(uris) => {
+                namespaces.extend(uris);
+                true
+            }
+            None => false,
+        }
+    });
+    namespaces.sort_unstable();
+    namespaces.dedup();
+
+    let mut xml = String::from("<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n");
+    xml.push_str("<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n");
+    xml.push_str(" <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n");
+    xml.push_str("  <rdf:Description rdf:about=\"\"");
+    for (prefix, uri) in namespaces {
+        xml.push_str(&format!("\n    xmlns:{prefix}=\"{}\"", escape(&uri)));
+    }
+    xml.push_str(">\n");
+    for (name, value) in &properties {
+        write_property(&mut xml, name, "", value, 3);
+    }
+    xml.push_str("  </rdf:Description>\n </rdf:RDF>\n</x:xmpmeta>\n<?xpacket end=\"w\"?>\n");
+    Ok(xml)
+}
+
+/// Split an XMP tag name into the steps along its path, e.g.
+/// `Xmp.mwg-rs.Regions/mwg-rs:RegionList[1]/?xml:lang`.
+fn xmp_path(tag: &str) -> Result<Vec<Step>> {
+    let invalid = || Rexiv2Error::InvalidValue(format!("Invalid XMP tag: {tag}"));
+    let rest = tag.strip_prefix("Xmp.").ok_or_else(invalid)?;
+    let (key_prefix, path) = rest.split_once('.').ok_or_else(invalid)?;
+    let prefix = match NAMESPACES.iter().find(|(key, _, _)| *key == key_prefix) {
+        Some((_, prefix, _)) => prefix,
+        // Other namespaces use the same prefix in tag names as in documents.
+        None => key_prefix,
+    };
+
+    let mut steps = vec![];
+    for (i, component) in path.split('/').enumerate() {
+        let (name, mut indices) = match component.find('[') {
+            Some(start) => component.split_at(start),
+            None => (component, ""),
+        };
+        let (is_qualifier, name) = match name.strip_prefix('?') {
+            Some(name) => (true, name),
+            None => (false, name),
+        };
+        let name = if i == 0 {
+            format!("{prefix}:{name}")
+        } else {
+            name.to_string()
+        };
+        match name.split_once(':') {
+            Some((prefix, local)) if !prefix.is_empty() && !local.is_empty() => {}
+            _ => return Err(invalid()),
+        }
+        steps.push(if is_qualifier {
+            Step::Qualifier(name)
+        } else {
+            Step::Field(name)
+        });
+        while !indices.is_empty() {
+            let (index, rest) = indices
+                .strip_prefix('[')
+                .and_then(|indices| indices.split_once(']'))
+                .ok_or_else(invalid)?;
+            steps.push(Step::Item(index.parse().map_err(|_| invalid())?));
+            indices = rest;
+        }
+    }
+    Ok(steps)
+}
+
+/// Read the value of a native XMP tag.
+fn xmp_value(meta: &Metadata, tag: &str) -> Result<Node> {
+    let kind = match get_tag_type(tag) {
+        Ok(TagType::LangAlt) => return Ok(Node::LangAlt(meta.get_tag_lang_alt(tag)?)),
+        Ok(TagType::XmpBag) => "Bag",
+        Ok(TagType::XmpSeq) => "Seq",
+        Ok(TagType::XmpAlt) => "Alt",
+        _ => {
+            // Exiv2 lists nested structures and arrays ahead of their contents, with values
+            // giving their type.
+            return Ok(match meta.get_tag_string(tag)?.as_str() {
+                "type=\"Struct\"" => Node::Struct(vec![]),
+                "type=\"Bag\"" => Node::Array("Bag", vec![]),
+                "type=\"Seq\"" => Node::Array("Seq", vec![]),
+                "type=\"Alt\"" => Node::Array("Alt", vec![]),
+                value => Node::Simple(value.to_string()),
+            });
+        }
+    };
+    let items = meta.get_tag_multiple_strings(tag)?;
+    Ok(Node::Array(
+        kind,
+        items.into_iter().map(Node::Simple).collect(),
+    ))
+}
+
+/// Convert the value of an Exif or IPTC tag into an XMP value, if it's valid.
+fn convert(meta: &Metadata, tag: &str, conversion: Conversion) -> Result<Option<Node>> {
+    let is_iptc = tag.starts_with("Iptc.");
+    let values = if is_iptc {
+        meta.get_iptc_tag_multiple_strings(tag)?
+    } else {
+        let value = match conversion {
+            Conversion::Interpreted => meta.get_tag_interpreted_string(tag)?,
+            _ => meta.get_tag_string(tag)?,
+        };
+        let value = value.trim_end_matches('\0').trim().to_string();
+        match conversion {
+            Conversion::Seq => value.split_whitespace().map(str::to_string).collect(),
+            _ => vec![value],
+        }
+    };
+    let first = match values.first() {
+        Some(first) if !first.is_empty() => first.clone(),
+        _ => return Ok(None),
+    };
+    let array = |kind| Node::Array(kind, values.iter().cloned().map(Node::Simple).collect());
+
+    let node = match conversion {
+        Conversion::Text | Conversion::Interpreted => Node::Simple(first),
+        Conversion::Seq => array("Seq"),
+        Conversion::SeqItem => Node::Array("Seq", vec![Node::Simple(first)]),
+        Conversion::Bag => array("Bag"),
+        Conversion::LangAlt => Node::LangAlt(vec![("x-default".to_string(), first)]),
+        Conversion::ExifDate(subsec_tag, offset_tag) => {
+            let mut date = match meta.get_tag_datetime(tag) {
+                Ok(date) => date,
+                Err(_) => return Ok(None),
+            };
+            meta.add_exif_subsec_and_offset(&mut date, subsec_tag, offset_tag);
+            Node::Simple(date.to_xmp_string())
+        }
+        Conversion::IptcDate => match meta.get_iptc_datetime(tag, tags::iptc::TIME_CREATED) {
+            Ok(date) => Node::Simple(date.to_xmp_string()),
+            Err(_) => return Ok(None),
+        },
+        Conversion::Version => {
+            let digits: Option<String> = first
+                .split_whitespace()
+                .map(|byte| byte.parse::<u8>().ok().map(char::from))
+                .collect();
+            match digits {
+                Some(digits) if digits.chars().all(|c| c.is_ascii_digit()) => Node::Simple(digits),
+                _ => return Ok(None),
+            }
+        }
+        Conversion::Flash => {
+            let flash: u16 = match first.parse() {
+                Ok(flash) => flash,
+                Err(_) => return Ok(None),
+            };
+            let bool_value =
+                |bit: u16| Node::Simple(if flash & bit != 0 { "True" } else { "False" }.into());
+            Node::Struct(vec![
+                ("exif:Fired".to_string(), bool_value(0x01)),
+                (
+                    "exif:Return".to_string(),
+                    Node::Simple(((flash >> 1) & 0x03).to_string()),
+                ),
+                (
+                    "exif:Mode".to_string(),
+                    Node::Simple(((flash >> 3) & 0x03).to_string()),
+                ),
+                ("exif:Function".to_string(), bool_value(0x20)),
+                ("exif:RedEyeMode".to_string(), bool_value(0x40)),
+            ])
+        }
+        Conversion::GpsCoordinate(ref_tag) => {
+            let direction = meta.get_tag_string(ref_tag).unwrap_or_default();
+            let direction = direction.trim();
+            match parse_rationals_as_f64(&first) {
+                Some(parts) if parts.len() == 3 && !direction.is_empty() => {
+                    let minutes = parts[1] + parts[2] / 60.0;
+                    let minutes = format!("{minutes:.8}");
+                    let minutes = minutes.trim_end_matches('0').trim_end_matches('.');
+                    Node::Simple(format!("{},{minutes}{direction}", parts[0]))
+                }
+                _ => return Ok(None),
+            }
+        }
+        Conversion::GpsVersion => {
+            Node::Simple(first.split_whitespace().collect::<Vec<_>>().join("."))
+        }
+        Conversion::GpsTimeStamp => match meta.get_gps_timestamp() {
+            Ok(Some(mut timestamp)) => {
+                timestamp.offset_minutes = Some(0);
+                Node::Simple(timestamp.to_xmp_string())
+            }
+            _ => return Ok(None),
+        },
+    };
+    Ok(Some(node))
+}
+
+/// Add the namespace prefixes used by a property and any values nested within it. The `xml`
+/// and `rdf` prefixes are always available, so aren't included.
+fn collect_prefixes<'a>(name: &'a str, value: &'a Node, prefixes: &mut Vec<&'a str>) {
+    if let Some((prefix, _)) = name.split_once(':') {
+        if prefix != "xml" && prefix != "rdf" {
+            prefixes.push(prefix);
+        }
+    }
+    match value {
+        Node::Array(_, items) => {
+            for item in items {
+                collect_prefixes("", item, prefixes);
+            }
+        }
+        Node::Struct(fields) => {
+            for (name, value) in fields {
+                collect_prefixes(name, value, prefixes);
+            }
+        }
+        Node::Qualified(value, qualifiers) => {
+            collect_prefixes("", value, prefixes);
+            for (name, value) in qualifiers {
+                collect_prefixes(name, value, prefixes);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Look up the URI of a namespace from the prefix used for it in XMP documents, including
+/// namespaces registered with `register_xmp_namespace()`.
+fn namespace_uri(prefix: &str) -> Option<String> {
+    NAMESPACES
+        .iter()
+        .find(|(_, xml_prefix, _)| *xml_prefix == prefix)
+        .map(|(_, _, uri)| uri.to_string())
+        .or_else(|| registered_xmp_namespace(prefix))
+}
+
+/// Write a property, structure field, or array item, and everything nested within it, with the
+/// given attributes (each preceded by a space) added to its element.
+fn write_property(xml: &mut String, name: &str, attributes: &str, value: &Node, depth: usize) {
+    let indent = " ".repeat(depth);
+    match value {
+        Node::Unset => {}
+        Node::Simple(text) => xml.push_str(&format!(
+            "{indent}<{name}{attributes}>{}</{name}>\n",
+            escape(text)
+        )),
+        Node::Struct(_) => {
+            xml.push_str(&format!(
+                "{indent}<{name}{attributes} rdf:parseType=\"Resource\">\n"
+            ));
+            write_fields(xml, value, depth + 1);
+            xml.push_str(&format!("{indent}</{name}>\n"));
+        }
+        Node::Array(..) | Node::LangAlt(_) => {
+            xml.push_str(&format!("{indent}<{name}{attributes}>\n"));
+            write_items(xml, value, depth + 1);
+            xml.push_str(&format!("{indent}</{name}>\n"));
+        }
+        Node::Qualified(value, qualifiers) => {
+            let mut attributes = attributes.to_string();
+            let mut others = vec![];
+            for (name, qualifier) in qualifiers {
+                match qualifier {
+                    Node::Unset => {}
+                    // A language is given by the standard attribute rather than as a field.
+                    Node::Simple(lang) if name == "xml:lang" => {
+                        attributes.push_str(&format!(" xml:lang=\"{}\"", escape(lang)));
+                    }
+                    _ => others.push((name, qualifier)),
+                }
+            }
+            if others.is_empty() {
+                write_property(xml, name, &attributes, value, depth);
+                return;
+            }
+            // The general form for qualifiers, with the value itself held as `rdf:value`.
+            xml.push_str(&format!(
+                "{indent}<{name}{attributes} rdf:parseType=\"Resource\">\n"
+            ));
+            write_property(xml, "rdf:value", "", value, depth + 1);
+            for (name, qualifier) in others {
+                write_property(xml, name, "", qualifier, depth + 1);
+            }
+            xml.push_str(&format!("{indent}</{name}>\n"));
+        }
+    }
+}
+
+/// Write the items of an array or language alternative.
+fn write_items(xml: &mut String, value: &Node, depth: usize) {
+    let indent = " ".repeat(depth);
+    match value {
+        Node::Array(kind, items) => {
+            xml.push_str(&format!("{indent}<rdf:{kind}>\n"));
+            for item in items {
+                match item {
+                    // An empty item keeps the position of the ones after a gap in the array.
+                    Node::Unset => xml.push_str(&format!("{indent} <rdf:li/>\n")),
+                    _ => write_property(xml, "rdf:li", "", item, depth + 1),
+                }
+            }
+            xml.push_str(&format!("{indent}</rdf:{kind}>\n"));
+        }
+        Node::LangAlt(entries) => {
+            xml.push_str(&format!("{indent}<rdf:Alt>\n"));
+            for (lang, text) in entries {
+                xml.push_str(&format!(
+                    "{indent} <rdf:li xml:lang=\"{}\">{}</rdf:li>\n",
+                    escape(lang),
+                    escape(text)
+                ));
+            }
+            xml.push_str(&format!("{indent}</rdf:Alt>\n"));
+        }
+        _ => {}
+    }
+}
+
+/// Write the fields of a structure.
+fn write_fields(xml: &mut String, value: &Node, depth: usize) {
+    if let Node::Struct(fields) = value {
+        for (name, value) in fields {
+            write_property(xml, name, "", value, depth);
+        }
+    }
+}
+
+/// Escape text for use in XML content or attribute values.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -19,8 +19,10 @@
 extern crate gexiv2_sys as gexiv2;
 extern crate rexiv2;
 
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Once;
 
 static INIT: Once = Once::new();
@@ -30,6 +32,64 @@ fn test_setup() {
     INIT.call_once(|| rexiv2::initialize().expect("Unable to initialize rexiv2"));
 }
 
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A path in the temporary directory that no other call in this test run will return.
+fn temp_path(suffix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "rexiv2-test-{}-{}{suffix}",
+        std::process::id(),
+        TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Write the given data to a new file in the temporary directory, failing rather than reusing a
+/// file that is already there, and return its path.
+fn write_temp_file(data: &[u8], extension: &str) -> PathBuf {
+    loop {
+        let path = temp_path(&format!(".{extension}"));
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(mut file) => {
+                file.write_all(data).unwrap();
+                return path;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(err) => panic!("Unable to create a temporary file: {err}"),
+        }
+    }
+}
+
+/// A new, empty directory in the temporary directory, removed along with its contents when
+/// dropped, so also when a test fails.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> TempDir {
+        loop {
+            let path = temp_path("");
+            match std::fs::create_dir(&path) {
+                Ok(()) => return TempDir(path),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(err) => panic!("Unable to create a temporary directory: {err}"),
+            }
+        }
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
 #[test]
 fn new_from_str_path() {
     test_setup();
@@ -118,6 +178,7 @@ fn supports_bmff() {
     }
 
     let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.HEIC")).unwrap();
+    assert_eq!(meta.get_media_type().unwrap(), rexiv2::MediaType::Heic);
     let gps = meta.get_gps_info().unwrap();
     assert_eq!(gps.latitude as i32, -27);
     assert_eq!(gps.longitude as i32, 114);
@@ -129,6 +190,94 @@ fn supports_bmff() {
     assert!(!meta.supports_exif());
     assert!(!meta.supports_iptc());
     assert!(!meta.supports_xmp());
+    let capabilities = meta.capabilities();
+    assert!(capabilities.exif);
+    assert!(!capabilities.iptc);
+    assert!(capabilities.xmp);
+    assert_eq!(capabilities.write_support, rexiv2::WriteSupport::ReadOnly);
+}
+
+#[test]
+fn unsupported_bmff() {
+    test_setup();
+    let version = unsafe { gexiv2::gexiv2_get_version() };
+    let result = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.HEIC"));
+    if version >= 1300 {
+        assert!(result.is_ok());
+        return;
+    }
+    let found = format!(
+        "gexiv2 {}.{}.{}",
+        version / 10000,
+        version / 100 % 100,
+        version % 100
+    );
+    match result {
+        Err(rexiv2::Rexiv2Error::Unsupported { needed, found: actual }) => {
+            assert!(needed.starts_with("gexiv2 0.13 or later"));
+            assert_eq!(actual, found);
+        }
+        other => panic!("Expected an Unsupported error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn unsupported_display() {
+    let err = rexiv2::Rexiv2Error::Unsupported {
+        needed: "gexiv2 0.13 or later".to_string(),
+        found: "gexiv2 0.12.3".to_string(),
+    };
+    assert_eq!(
+        err.to_string(),
+        "Unsupported: needs gexiv2 0.13 or later, but found gexiv2 0.12.3"
+    );
+}
+
+#[test]
+fn invalid_values() {
+    test_setup();
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.png")).unwrap();
+    assert_eq!(
+        meta.set_interop_index("R98X"),
+        Err(rexiv2::Rexiv2Error::InvalidValue(
+            "Invalid interoperability index: \"R98X\"".to_string()
+        ))
+    );
+    assert!(matches!(
+        meta.set_rating(6),
+        Err(rexiv2::Rexiv2Error::InvalidValue(_))
+    ));
+    assert!(matches!(
+        "2022:13:01 00:00:00".parse::<rexiv2::ExifDateTime>(),
+        Err(rexiv2::Rexiv2Error::InvalidValue(_))
+    ));
+    assert!(!meta.has_tag("Exif.Iop.InteroperabilityIndex"));
+    assert!(!meta.has_tag("Xmp.xmp.Rating"));
+}
+
+#[test]
+fn set_utc_offset_out_of_range() {
+    test_setup();
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.png")).unwrap();
+    meta.set_tag_string("Exif.Photo.DateTimeOriginal", "2022:08:07 11:19:44")
+        .unwrap();
+    for (offset, convert_from) in [
+        (i16::MIN, None),
+        (i16::MAX, None),
+        (24 * 60, None),
+        (60, Some(i16::MIN)),
+        (60, Some(-24 * 60)),
+    ] {
+        assert!(matches!(
+            meta.set_utc_offset(offset, convert_from),
+            Err(rexiv2::Rexiv2Error::InvalidValue(_))
+        ));
+    }
+    assert_eq!(
+        meta.get_tag_string("Exif.Photo.DateTimeOriginal"),
+        Ok("2022:08:07 11:19:44".to_string())
+    );
+    assert!(!meta.has_tag("Exif.Photo.OffsetTimeOriginal"));
 }
 
 #[test]
@@ -179,3 +328,692 @@ fn get_tag_raw() {
         b"2020:07:12 11:16:35\0"
     );
 }
+
+#[test]
+fn iptc_other_charset() {
+    test_setup();
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.png")).unwrap();
+    meta.set_tag_string("Iptc.Application2.Caption", "Caf\u{e9}")
+        .unwrap();
+    // ISO 8859-5 (Cyrillic), which can't be decoded as Latin-1.
+    meta.set_tag_string("Iptc.Envelope.CharacterSet", "\x1b-L")
+        .unwrap();
+    assert_eq!(
+        meta.get_iptc_charset(),
+        rexiv2::IptcCharset::Other(b"\x1b-L".to_vec())
+    );
+    assert!(matches!(
+        meta.get_iptc_tag_string("Iptc.Application2.Caption"),
+        Err(rexiv2::Rexiv2Error::Unsupported { .. })
+    ));
+    assert!(matches!(
+        meta.convert_iptc_to_utf8(),
+        Err(rexiv2::Rexiv2Error::Unsupported { .. })
+    ));
+    assert_eq!(
+        meta.get_tag_string("Iptc.Envelope.CharacterSet"),
+        Ok("\x1b-L".to_string())
+    );
+    assert_eq!(
+        meta.get_tag_string("Iptc.Application2.Caption"),
+        Ok("Caf\u{e9}".to_string())
+    );
+}
+
+#[test]
+#[cfg(feature = "gio")]
+fn new_from_gio_stream() {
+    test_setup();
+    let data = include_bytes!("sample.png");
+    let meta = unsafe {
+        // The stream is small, so is left for the process to clean up.
+        let stream = gio_sys::g_memory_input_stream_new_from_data(
+            data.as_ptr() as *mut _,
+            data.len() as _,
+            None,
+        );
+        rexiv2::Metadata::new_from_gio_stream(stream).unwrap()
+    };
+    assert_eq!(meta.get_media_type(), Ok(rexiv2::MediaType::Png));
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn load_oriented_from_buffer() {
+    test_setup();
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.png")).unwrap();
+    let img = rexiv2::load_oriented_from_buffer(include_bytes!("sample.png")).unwrap();
+    assert_eq!(img.width() as i32, meta.get_pixel_width());
+    assert_eq!(img.height() as i32, meta.get_pixel_height());
+}
+
+#[test]
+fn detect_media_type() {
+    assert_eq!(
+        rexiv2::detect_media_type(include_bytes!("sample.png")),
+        Some(rexiv2::MediaType::Png)
+    );
+    assert_eq!(
+        rexiv2::detect_media_type(include_bytes!("sample.HEIC")),
+        Some(rexiv2::MediaType::Heic)
+    );
+    assert_eq!(
+        rexiv2::detect_media_type(include_bytes!("sample.webp")),
+        Some(rexiv2::MediaType::Webp)
+    );
+    // The header of an uncompressed 2x1 true-color TGA file, without the rest of the file.
+    let tga = b"\0\0\x02\0\0\0\0\0\0\0\0\0\x02\0\x01\0\x18\x20";
+    assert_eq!(rexiv2::detect_media_type(tga), Some(rexiv2::MediaType::Tga));
+}
+
+#[test]
+fn find_c2pa_manifest() {
+    // A JPEG file whose manifest store is split across two APP11 segments, the second of which
+    // only repeats the length and type of the superbox, followed by an unrelated JUMBF box.
+    let mut jumbf = vec![0, 0, 0, 48];
+    jumbf.extend_from_slice(
+        b"jumb\0\0\0\x1Bjumdc2pa\0\x11\0\x10\x80\0\0\xAA\0\x38\x9B\x71\x03c2pa\0",
+    );
+    let mut continuation = jumbf[..8].to_vec();
+    continuation.extend_from_slice(&jumbf[30..]);
+    let mut other = vec![0, 0, 0, 16];
+    other.extend_from_slice(b"jumb\0\0\0\x08jumd");
+    let mut jpeg = b"\xFF\xD8\xFF\xE0\0\x04\0\0".to_vec();
+    let start = jpeg.len();
+    for (instance, sequence, packet) in
+        [(1, 1, &jumbf[..30]), (1, 2, &continuation), (2, 1, &other)]
+    {
+        jpeg.extend_from_slice(&[0xFF, 0xEB, 0, 2 + 8 + packet.len() as u8]);
+        jpeg.extend_from_slice(&[b'J', b'P', 0, instance, 0, 0, 0, sequence]);
+        jpeg.extend_from_slice(packet);
+    }
+    let end = jpeg.len() - (4 + 8 + other.len());
+    jpeg.extend_from_slice(b"\xFF\xDA\0\x02\xFF\xD9");
+    assert_eq!(rexiv2::find_c2pa_manifest(&jpeg), Some(start..end));
+
+    // Truncated data doesn't report a manifest.
+    assert_eq!(rexiv2::find_c2pa_manifest(&jpeg[..end - 1]), None);
+    assert_eq!(
+        rexiv2::find_c2pa_manifest(include_bytes!("sample.png")),
+        None
+    );
+}
+
+/// Build a HEIF file holding several images, returning it along with the Exif artist of each
+/// non-primary top-level image, in the order they should be counted.
+fn synthetic_heif() -> (Vec<u8>, [&'static str; 2]) {
+    fn make_box(kind: &[u8; 4], content: &[u8]) -> Vec<u8> {
+        let mut data = (content.len() as u32 + 8).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.extend_from_slice(content);
+        data
+    }
+    fn full_box(kind: &[u8; 4], version: u8, flags: u32, content: &[u8]) -> Vec<u8> {
+        let mut data = ((u32::from(version) << 24) | flags).to_be_bytes().to_vec();
+        data.extend_from_slice(content);
+        make_box(kind, &data)
+    }
+    fn u16s(values: &[u16]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+    // A big-endian TIFF header and IFD holding only `Exif.Image.Artist`.
+    fn tiff_with_artist(artist: &str) -> Vec<u8> {
+        let mut tiff = b"MM\0*\0\0\0\x08\0\x01\x01\x3B\0\x02".to_vec();
+        tiff.extend_from_slice(&(artist.len() as u32 + 1).to_be_bytes());
+        tiff.extend_from_slice(&26u32.to_be_bytes());
+        tiff.extend_from_slice(&[0; 4]);
+        tiff.extend_from_slice(artist.as_bytes());
+        tiff.push(0);
+        tiff
+    }
+
+    // Item 2 is the primary image, listed after item 1. Item 5 is a thumbnail, 6 a depth map, 7
+    // a grid made of tiles 8 and 9, and 10 is hidden. Items 3 and 4 hold the Exif data of 1 and
+    // 7: the first in the `idat` box after an "Exif\0\0" marker, the second in `mdat` without.
+    let idat_exif = [&[0, 0, 0, 6][..], b"Exif\0\0", &tiff_with_artist("Second")].concat();
+    let mdat_exif = [&[0, 0, 0, 0][..], &tiff_with_artist("Third")].concat();
+    let mut iinf = u16s(&[10]);
+    for (id, item_type, flags) in [
+        (1, b"hvc1", 0),
+        (2, b"hvc1", 0),
+        (3, b"Exif", 0),
+        (4, b"Exif", 0),
+        (5, b"hvc1", 0),
+        (6, b"hvc1", 0),
+        (7, b"grid", 0),
+        (8, b"hvc1", 0),
+        (9, b"hvc1", 0),
+        (10, b"hvc1", 1),
+    ] {
+        let content = [u16s(&[id, 0]), item_type.to_vec(), vec![0]].concat();
+        iinf.extend(full_box(b"infe", 2, flags, &content));
+    }
+    let mut iref = vec![];
+    for (kind, from, to) in [
+        (b"cdsc", 3, &[1][..]),
+        (b"cdsc", 4, &[7]),
+        (b"thmb", 5, &[2]),
+        (b"auxl", 6, &[2]),
+        (b"dimg", 7, &[8, 9]),
+    ] {
+        let content = [u16s(&[from, to.len() as u16]), u16s(to)].concat();
+        iref.extend(make_box(kind, &content));
+    }
+
+    let build = |mdat_offset: u32| {
+        // Version 1, with 4 byte offsets and lengths, and no base offsets or extent indices.
+        let mut iloc = vec![0x44, 0x00];
+        iloc.extend(u16s(&[2]));
+        iloc.extend(u16s(&[3, 1, 0, 1]));
+        iloc.extend(0u32.to_be_bytes());
+        iloc.extend((idat_exif.len() as u32).to_be_bytes());
+        iloc.extend(u16s(&[4, 0, 0, 1]));
+        iloc.extend(mdat_offset.to_be_bytes());
+        iloc.extend((mdat_exif.len() as u32).to_be_bytes());
+        let meta = [
+            full_box(b"pitm", 0, 0, &u16s(&[2])),
+            full_box(b"iinf", 0, 0, &iinf),
+            full_box(b"iref", 0, 0, &iref),
+            full_box(b"iloc", 1, 0, &iloc),
+            make_box(b"idat", &idat_exif),
+        ]
+        .concat();
+        [
+            make_box(b"ftyp", b"heic\0\0\0\0mif1heic"),
+            full_box(b"meta", 0, 0, &meta),
+        ]
+        .concat()
+    };
+    let header_len = build(0).len() as u32;
+    let file = [build(header_len + 8), make_box(b"mdat", &mdat_exif)].concat();
+    (file, ["Second", "Third"])
+}
+
+#[test]
+fn heif_images() {
+    test_setup();
+    assert_eq!(rexiv2::heif_image_count(include_bytes!("sample.HEIC")), 1);
+
+    let (heif, artists) = synthetic_heif();
+    assert_eq!(rexiv2::heif_image_count(&heif), 3);
+    for (index, artist) in artists.iter().enumerate() {
+        let meta = rexiv2::Metadata::new_from_heif_image(&heif, index + 1).unwrap();
+        assert_eq!(
+            meta.get_tag_string("Exif.Image.Artist"),
+            Ok(artist.to_string())
+        );
+    }
+    assert_eq!(
+        rexiv2::Metadata::new_from_heif_image(&heif, 3).err(),
+        Some(rexiv2::Rexiv2Error::NoValue)
+    );
+
+    // Truncated and corrupted files are rejected without panicking.
+    for len in 0..heif.len() {
+        assert!(rexiv2::heif_image_count(&heif[..len]) <= 3);
+        assert!(rexiv2::Metadata::new_from_heif_image(&heif[..len], 2).is_err());
+    }
+    let mut garbage = heif.clone();
+    for byte in garbage.iter_mut().skip(24).step_by(7) {
+        *byte = 0xFF;
+    }
+    rexiv2::heif_image_count(&garbage);
+    let _ = rexiv2::Metadata::new_from_heif_image(&garbage, 1);
+}
+
+#[test]
+fn save_to_missing_file() {
+    test_setup();
+    let path = std::env::temp_dir().join(format!("rexiv2-missing-{}.png", std::process::id()));
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.png")).unwrap();
+    assert!(matches!(
+        meta.save_to_file(&path),
+        Err(rexiv2::Rexiv2Error::NotFound(_))
+    ));
+}
+
+#[test]
+fn save_to_file_atomic() {
+    test_setup();
+    let path = write_temp_file(include_bytes!("sample.png"), "png");
+
+    let meta = rexiv2::Metadata::new_from_path(&path).unwrap();
+    meta.set_tag_string("Xmp.xmp.Label", "Atomic").unwrap();
+    meta.save_to_file_atomic(&path).unwrap();
+
+    let saved = rexiv2::Metadata::new_from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        saved.get_tag_string("Xmp.xmp.Label"),
+        Ok("Atomic".to_string())
+    );
+}
+
+#[test]
+fn save_to_open_file() {
+    test_setup();
+    let path = write_temp_file(include_bytes!("sample.png"), "png");
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&path)
+        .unwrap();
+
+    let meta = rexiv2::Metadata::new_from_file(&file).unwrap();
+    meta.set_tag_string("Xmp.xmp.Label", "Open file").unwrap();
+    meta.save_to_open_file(&file).unwrap();
+    drop(file);
+
+    let saved = rexiv2::Metadata::new_from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(saved.get_media_type(), Ok(rexiv2::MediaType::Png));
+    assert_eq!(
+        saved.get_tag_string("Xmp.xmp.Label"),
+        Ok("Open file".to_string())
+    );
+}
+
+#[test]
+fn apply_table() {
+    test_setup();
+    let temp = TempDir::new();
+    let root = temp.path();
+    let dir = root.join("photos");
+    std::fs::create_dir(&dir).unwrap();
+    std::fs::write(dir.join("sample.png"), include_bytes!("sample.png")).unwrap();
+    // A file outside the table's directory, which rows must not be able to reach.
+    std::fs::write(root.join("sample.png"), include_bytes!("sample.png")).unwrap();
+
+    let csv = "File,Xmp.xmp.Label,Xmp.dc.subject\n\
+               sample.png,\"Edited, twice\",fox\\; cat; dog\n\
+               missing.png,Gone,\n\
+               ../sample.png,Escaped,\n\
+               /tmp/sample.png,Absolute,\n";
+    let escaped = format!("{},Escaped,\n", root.join("sample.png").display());
+    let csv = csv.to_string() + &escaped;
+    let dry_run =
+        rexiv2::apply_table(csv.as_bytes(), &dir, rexiv2::TableFormat::Csv, true).unwrap();
+    assert_eq!(dry_run.len(), 5);
+    assert_eq!(dry_run[0].result, Ok(2));
+    assert_eq!(dry_run[1].line, 3);
+    assert!(dry_run[1].result.is_err());
+    assert!(dry_run[2].result.is_err());
+    assert!(dry_run[3].result.is_err());
+    assert!(dry_run[4].result.is_err());
+    let unchanged = rexiv2::Metadata::new_from_path(dir.join("sample.png")).unwrap();
+    assert!(!unchanged.has_tag("Xmp.xmp.Label"));
+
+    rexiv2::apply_table(csv.as_bytes(), &dir, rexiv2::TableFormat::Csv, false).unwrap();
+    let saved = rexiv2::Metadata::new_from_path(dir.join("sample.png")).unwrap();
+    let outside = rexiv2::Metadata::new_from_path(root.join("sample.png")).unwrap();
+    assert!(!outside.has_tag("Xmp.xmp.Label"));
+    assert_eq!(
+        saved.get_tag_string("Xmp.xmp.Label"),
+        Ok("Edited, twice".to_string())
+    );
+    assert_eq!(
+        saved.get_tag_multiple_strings("Xmp.dc.subject"),
+        Ok(vec!["fox; cat".to_string(), "dog".to_string()])
+    );
+}
+
+#[test]
+fn to_xmp_document() {
+    test_setup();
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.png")).unwrap();
+    meta.set_tag_string("Xmp.dc.source", "Archive box 12")
+        .unwrap();
+    meta.set_xmp_qualifier("Xmp.dc.source", "xml:lang", "en")
+        .unwrap();
+    meta.set_tag_string("Xmp.xmp.Label", "").unwrap();
+    meta.set_tag_string(
+        "Xmp.mwg-rs.Regions/mwg-rs:RegionList[1]/mwg-rs:Name",
+        "Jane",
+    )
+    .unwrap();
+    meta.set_tag_string(
+        "Xmp.mwg-rs.Regions/mwg-rs:RegionList[3]/mwg-rs:Name",
+        "John",
+    )
+    .unwrap();
+    rexiv2::register_xmp_namespace("http://example.com/rexiv2/registered/", "rxreg").unwrap();
+    meta.set_tag_string("Xmp.rxreg.Note", "Kept").unwrap();
+
+    let xmp = meta.to_xmp_document(false).unwrap();
+    assert!(xmp.contains(r#"<dc:source xml:lang="en">Archive box 12</dc:source>"#));
+    assert!(xmp.contains("<xmp:Label></xmp:Label>"));
+    assert!(xmp.contains(r#"xmlns:mwg-rs="http://www.metadataworkinggroup.com/schemas/regions/""#));
+    let jane = xmp.find("<mwg-rs:Name>Jane</mwg-rs:Name>").unwrap();
+    let gap = xmp.find("<rdf:li/>").unwrap();
+    let john = xmp.find("<mwg-rs:Name>John</mwg-rs:Name>").unwrap();
+    assert!(jane < gap && gap < john);
+    assert!(xmp.contains(r#"xmlns:rxreg="http://example.com/rexiv2/registered/""#));
+    assert!(xmp.contains("<rxreg:Note>Kept</rxreg:Note>"));
+
+    // A namespace known to Exiv2 but not registered through rexiv2 can't be written out, so its
+    // properties are left out while the rest are kept.
+    let name = std::ffi::CString::new("http://example.com/rexiv2/unknown/").unwrap();
+    let prefix = std::ffi::CString::new("rxunknown").unwrap();
+    unsafe { gexiv2::gexiv2_metadata_register_xmp_namespace(name.as_ptr(), prefix.as_ptr()) };
+    meta.set_tag_string("Xmp.rxunknown.Note", "Lost").unwrap();
+    let xmp = meta.to_xmp_document(false).unwrap();
+    assert!(!xmp.contains("rxunknown"));
+    assert!(xmp.contains("<rxreg:Note>Kept</rxreg:Note>"));
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn cli() {
+    use std::process::Command;
+    test_setup();
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_rexiv2"))
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let path = write_temp_file(include_bytes!("sample.png"), "png");
+    let file = path.to_str().unwrap();
+    let original = write_temp_file(include_bytes!("sample.png"), "png");
+
+    assert!(
+        run(&["set", file, "Xmp.dc.subject=fox", "Exif.Image.Artist=Jane"])
+            .status
+            .success()
+    );
+    let get = run(&["get", file, "Exif.Image.Artist"]);
+    assert_eq!(String::from_utf8_lossy(&get.stdout), "Jane\n");
+    let diff = run(&["diff", original.to_str().unwrap(), file]);
+    assert_eq!(
+        String::from_utf8_lossy(&diff.stdout),
+        "+ Exif.Image.Artist: Jane\n+ Xmp.dc.subject: fox\n"
+    );
+
+    // A misspelt option is an error rather than being ignored.
+    let strip = run(&["strip", "--exfi", file]);
+    assert_eq!(strip.status.code(), Some(2));
+    assert!(rexiv2::Metadata::new_from_path(&path).unwrap().has_xmp());
+
+    assert!(run(&["strip", "--xmp", file]).status.success());
+    let meta = rexiv2::Metadata::new_from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&original).unwrap();
+    assert!(!meta.has_xmp());
+    assert!(meta.has_exif());
+}
+
+#[test]
+#[cfg(all(feature = "cli", unix))]
+fn cli_non_utf8_arguments() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::process::Command;
+    let run = |args: &[&OsStr]| {
+        Command::new(env!("CARGO_BIN_EXE_rexiv2"))
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let not_utf8 = OsStr::from_bytes(b"caf\xe9.png");
+
+    // A file name that isn't UTF-8 gives an error rather than a crash.
+    let get = run(&[OsStr::new("get"), not_utf8, OsStr::new("Exif.Image.Artist")]);
+    assert_eq!(get.status.code(), Some(1));
+    // Tag names must be text.
+    let path = write_temp_file(include_bytes!("sample.png"), "png");
+    let get = run(&[OsStr::new("get"), path.as_os_str(), not_utf8]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(get.status.code(), Some(2));
+}
+
+#[test]
+fn credit_info_keeps_other_supplier_fields() {
+    test_setup();
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.png")).unwrap();
+    let supplier_id = "Xmp.plus.ImageSupplier[1]/plus:ImageSupplierID";
+    meta.set_tag_string(supplier_id, "SUP-42").unwrap();
+    let credit = rexiv2::CreditInfo {
+        provider: Some("Example Press".to_string()),
+        ..Default::default()
+    };
+    credit.write_to(&meta).unwrap();
+    assert_eq!(rexiv2::CreditInfo::read_from(&meta), credit);
+    assert_eq!(meta.get_tag_string(supplier_id), Ok("SUP-42".to_string()));
+
+    rexiv2::CreditInfo::default().write_to(&meta).unwrap();
+    assert_eq!(rexiv2::CreditInfo::read_from(&meta).provider, None);
+    assert_eq!(meta.get_tag_string(supplier_id), Ok("SUP-42".to_string()));
+}
+
+#[test]
+fn edit_xmp_items_by_position() {
+    test_setup();
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.png")).unwrap();
+    meta.set_tag_string("Xmp.dc.subject[1]", "cat").unwrap();
+    meta.set_tag_string("Xmp.dc.subject[2]", "chien").unwrap();
+    meta.set_xmp_qualifier("Xmp.dc.subject[2]", "xml:lang", "fr")
+        .unwrap();
+
+    meta.insert_tag_value_at("Xmp.dc.subject", 0, "fish")
+        .unwrap();
+    meta.set_tag_value_at("Xmp.dc.subject", 1, "lion").unwrap();
+    assert_eq!(
+        meta.get_tag_value_at("Xmp.dc.subject", 2),
+        Ok("chien".to_string())
+    );
+    assert_eq!(
+        meta.get_xmp_qualifier("Xmp.dc.subject[3]", "xml:lang"),
+        Ok("fr".to_string())
+    );
+
+    meta.delete_tag_value_at("Xmp.dc.subject", 0).unwrap();
+    assert_eq!(
+        meta.get_tag_value_at("Xmp.dc.subject", 0),
+        Ok("lion".to_string())
+    );
+    assert_eq!(
+        meta.get_xmp_qualifier("Xmp.dc.subject[2]", "xml:lang"),
+        Ok("fr".to_string())
+    );
+    assert!(!meta.has_tag("Xmp.dc.subject[3]/?xml:lang"));
+
+    // An array in a namespace Exiv2 has no type information for keeps all its items.
+    rexiv2::register_xmp_namespace("http://example.com/rexiv2/items/", "rxitems").unwrap();
+    meta.insert_tag_value_at("Xmp.rxitems.List", 0, "b")
+        .unwrap();
+    meta.insert_tag_value_at("Xmp.rxitems.List", 0, "a")
+        .unwrap();
+    meta.insert_tag_value_at("Xmp.rxitems.List", 2, "c")
+        .unwrap();
+    assert_eq!(
+        meta.get_tag_value_at("Xmp.rxitems.List", 0),
+        Ok("a".to_string())
+    );
+    assert_eq!(
+        meta.get_tag_value_at("Xmp.rxitems.List", 2),
+        Ok("c".to_string())
+    );
+}
+
+#[test]
+fn image_regions() {
+    use rexiv2::{ImageRegion, RegionBoundary, RegionShape, RegionUnit};
+    test_setup();
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.png")).unwrap();
+    let regions = [
+        ImageRegion {
+            name: Some("Skyline".to_string()),
+            boundary: Some(RegionBoundary {
+                unit: RegionUnit::Relative,
+                shape: RegionShape::Polygon(vec![(0.0, 0.5), (1.0, 0.25), (0.5, 0.75)]),
+            }),
+            roles: vec![ImageRegion::ROLE_MAIN_SUBJECT.to_string()],
+            content_types: vec![
+                "http://cv.iptc.org/newscodes/imageregiontype/building".to_string(),
+                "http://cv.iptc.org/newscodes/imageregiontype/sky".to_string(),
+            ],
+            ..Default::default()
+        },
+        ImageRegion {
+            id: Some("crop".to_string()),
+            boundary: Some(RegionBoundary {
+                unit: RegionUnit::Pixel,
+                shape: RegionShape::Rectangle { x: 10.0, y: 20.0, width: 300.0, height: 200.0 },
+            }),
+            roles: vec![ImageRegion::ROLE_CROPPING.to_string()],
+            ..Default::default()
+        },
+        ImageRegion {
+            boundary: Some(RegionBoundary {
+                unit: RegionUnit::Pixel,
+                shape: RegionShape::Circle { x: 120.0, y: 80.0, radius: 40.5 },
+            }),
+            content_types: vec![ImageRegion::CONTENT_FACE.to_string()],
+            ..Default::default()
+        },
+    ];
+    meta.set_image_regions(&regions).unwrap();
+    assert_eq!(meta.get_image_regions(), regions);
+    assert_eq!(
+        meta.get_tag_string(
+            "Xmp.iptcExt.ImageRegion[1]/Iptc4xmpExt:RegionBoundary/Iptc4xmpExt:rbVertices[2]/\
+             Iptc4xmpExt:rbY"
+        ),
+        Ok("0.25".to_string())
+    );
+    assert_eq!(
+        meta.get_tag_string("Xmp.iptcExt.ImageRegion[1]/Iptc4xmpExt:rCtype[2]/xmp:Identifier[1]"),
+        Ok("http://cv.iptc.org/newscodes/imageregiontype/sky".to_string())
+    );
+
+    // Replacing the regions leaves nothing of the old ones behind.
+    meta.set_image_regions(&regions[2..]).unwrap();
+    assert_eq!(meta.get_image_regions(), &regions[2..]);
+    assert!(!meta.has_tag("Xmp.iptcExt.ImageRegion[2]/Iptc4xmpExt:rId"));
+    assert!(!meta.has_tag(
+        "Xmp.iptcExt.ImageRegion[1]/Iptc4xmpExt:RegionBoundary/Iptc4xmpExt:rbVertices[1]/\
+         Iptc4xmpExt:rbX"
+    ));
+    meta.set_image_regions(&[]).unwrap();
+    assert!(meta.get_image_regions().is_empty());
+}
+
+#[test]
+#[cfg(feature = "test-utils")]
+fn sample_images() {
+    use rexiv2::test_utils::{sample_image_with_tags, SampleFormat};
+    test_setup();
+    for format in [SampleFormat::Jpeg, SampleFormat::Png, SampleFormat::Tiff] {
+        let image = sample_image_with_tags(format, &[("Exif.Image.Artist", "Jane Doe")]).unwrap();
+        let meta = rexiv2::Metadata::new_from_buffer(&image).unwrap();
+        assert_eq!(meta.get_media_type(), Ok(format.media_type()));
+        assert_eq!(meta.dimensions(), Ok((1, 1)));
+        assert_eq!(
+            meta.get_tag_string("Exif.Image.Artist"),
+            Ok("Jane Doe".to_string())
+        );
+    }
+}
+
+#[test]
+fn save_as() {
+    test_setup();
+    let source = concat!(env!("CARGO_MANIFEST_DIR"), "/tst/sample.png");
+    // A fresh directory, since `save_as` refuses to overwrite an existing destination.
+    let dir = TempDir::new();
+    let destination = dir.path().join("saved.png");
+
+    let meta = rexiv2::Metadata::new_from_path(source).unwrap();
+    meta.set_tag_string("Xmp.xmp.Label", "Exported").unwrap();
+    meta.save_as(source, &destination).unwrap();
+    assert!(meta.save_as(source, &destination).is_err());
+
+    let saved = rexiv2::Metadata::new_from_path(&destination).unwrap();
+    assert_eq!(
+        saved.get_tag_string("Xmp.xmp.Label"),
+        Ok("Exported".to_string())
+    );
+    let original = rexiv2::Metadata::new_from_path(source).unwrap();
+    assert!(!original.has_tag("Xmp.xmp.Label"));
+}
+
+#[test]
+#[cfg(feature = "camino")]
+fn new_from_utf8_path() {
+    test_setup();
+    let sample_path = camino::Utf8Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tst/sample.png"));
+    let meta = rexiv2::Metadata::new_from_path(sample_path).unwrap();
+    assert_eq!(meta.get_media_type().unwrap(), rexiv2::MediaType::Png);
+    let meta = rexiv2::Metadata::try_from(sample_path).unwrap();
+    assert_eq!(meta.get_media_type().unwrap(), rexiv2::MediaType::Png);
+}
+
+#[test]
+fn try_from_path() {
+    test_setup();
+    let sample_path = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tst/sample.png"));
+    let meta: rexiv2::Metadata = sample_path.try_into().unwrap();
+    assert_eq!(meta.get_media_type().unwrap(), rexiv2::MediaType::Png);
+}
+
+#[test]
+fn supports_webp() {
+    test_setup();
+    let meta = rexiv2::Metadata::new_from_buffer(include_bytes!("sample.webp")).unwrap();
+    assert_eq!(meta.get_media_type().unwrap(), rexiv2::MediaType::Webp);
+    assert_eq!(meta.get_pixel_width(), 1);
+    assert_eq!(meta.get_pixel_height(), 1);
+    assert!(meta.supports_exif());
+    assert!(meta.supports_xmp());
+    assert!(!meta.supports_iptc());
+}
+
+#[test]
+fn save_webp() {
+    test_setup();
+    // The sample with an ICC profile added in an ICCP chunk, which must come straight after VP8X.
+    const ICC_PROFILE: &[u8] = b"rexiv2 test ICC profile\0";
+    let mut webp = include_bytes!("sample.webp").to_vec();
+    let mut iccp = b"ICCP".to_vec();
+    iccp.extend_from_slice(&(ICC_PROFILE.len() as u32).to_le_bytes());
+    iccp.extend_from_slice(ICC_PROFILE);
+    webp.splice(30..30, iccp);
+    webp[20] |= 0x20;
+    let riff_size = (webp.len() as u32 - 8).to_le_bytes();
+    webp[4..8].copy_from_slice(&riff_size);
+    let path = write_temp_file(&webp, "webp");
+
+    let meta = rexiv2::Metadata::new_from_path(&path).unwrap();
+    meta.set_tag_string("Exif.Image.Artist", "A. Photographer")
+        .unwrap();
+    meta.set_tag_string("Xmp.xmp.Label", "WebP").unwrap();
+    meta.save_to_file(&path).unwrap();
+
+    let saved = rexiv2::Metadata::new_from_path(&path).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(saved.get_media_type().unwrap(), rexiv2::MediaType::Webp);
+    assert_eq!(
+        saved.get_tag_string("Exif.Image.Artist"),
+        Ok("A. Photographer".to_string())
+    );
+    assert_eq!(
+        saved.get_tag_string("Xmp.xmp.Label"),
+        Ok("WebP".to_string())
+    );
+    // The ICC profile is kept, and still flagged in the VP8X chunk.
+    assert_eq!(&data[12..16], b"VP8X");
+    assert_eq!(data[20] & 0x20, 0x20);
+    let icc_chunk = [
+        &b"ICCP"[..],
+        &(ICC_PROFILE.len() as u32).to_le_bytes(),
+        ICC_PROFILE,
+    ]
+    .concat();
+    assert!(data
+        .windows(icc_chunk.len())
+        .any(|window| window == icc_chunk));
+}